@@ -0,0 +1,581 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for the builders of the public organizer API for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::config::{self, Severity, TyrConfig};
+use crate::error::{Result, TyrError};
+use crate::organizer::categorise::CategoryConfig;
+use crate::organizer::classifier::{ClassifierOrganizer, Model, Prediction};
+use crate::organizer::feedback::Feedback;
+use crate::organizer::filename::{
+    group_similar_files, suggest_folder_name, SimilarityConfig, SimilarityOrganizer,
+};
+use crate::organizer::intelligent::{
+    cluster_files, sanitize_dirname, ClusterOrganizer, IntelligentConfig,
+};
+use crate::organizer::pipeline::{self, ExecuteResult, Grouping, Pipeline, Plan};
+use crate::organizer::strategy;
+use crate::organizer::transfer::{Output, TransferMode};
+
+/// Files that belong together, and the folder they would go into
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    /// Folder name, already safe to use on disk
+    pub name: String,
+    pub files: Vec<PathBuf>,
+}
+
+/// Config and output shared by every builder
+#[derive(Debug, Clone, Default)]
+struct Settings {
+    config: TyrConfig,
+    output: Output,
+    feedback: Arc<Feedback>,
+}
+
+impl Settings {
+    /// Fails on config errors; warnings (unknown stop word languages, ...) are let through
+    ///
+    /// The config's `[hooks]` are copied to the output, which runs them; hooks already set
+    /// on the output win.
+    fn finish(mut self) -> Result<Self> {
+        let errors: Vec<_> = self
+            .config
+            .validate()
+            .into_iter()
+            .filter(|issue| issue.severity == Severity::Error)
+            .collect();
+        if !errors.is_empty() {
+            return Err(TyrError::InvalidConfig(errors));
+        }
+        self.output.hooks.fill_from(&self.config.hooks);
+        Ok(self)
+    }
+
+    fn set_dest(&mut self, dir: PathBuf) {
+        self.output.dest_root = Some(dir);
+    }
+
+    fn set_copy(&mut self, copy: bool) {
+        self.output.mode = if copy {
+            TransferMode::Copy
+        } else {
+            TransferMode::Move
+        };
+    }
+}
+
+// Categorise
+
+/// Sorts files into category folders, then optionally groups them inside each category
+///
+/// The same run as `tyr DIR`: plan first, then execute the plan.
+pub struct Categorizer {
+    pipeline: Pipeline,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CategorizerBuilder {
+    settings: Settings,
+    grouping: Option<Grouping>,
+}
+
+impl Categorizer {
+    pub fn builder() -> CategorizerBuilder {
+        CategorizerBuilder::default()
+    }
+
+    /// Human readable list of the steps, e.g. "categorise → filename similarity"
+    pub fn describe(&self) -> String {
+        self.pipeline.describe()
+    }
+
+    /// Works out every move for `dir` without touching anything
+    pub fn plan(&self, dir: &Path) -> Result<Plan> {
+        check_dir(dir)?;
+        Ok(self.pipeline.plan(dir)?)
+    }
+
+    /// Carries out a plan; failed files are listed in the result, not returned as errors
+    pub fn execute(&self, plan: &Plan) -> ExecuteResult {
+        self.pipeline.execute(plan)
+    }
+
+    pub fn organize(&self, dir: &Path) -> Result<ExecuteResult> {
+        let plan = self.plan(dir)?;
+        Ok(self.execute(&plan))
+    }
+}
+
+impl CategorizerBuilder {
+    /// Every setting from a loaded tyr.toml; the other setters override parts of it
+    pub fn config(mut self, config: TyrConfig) -> Self {
+        self.settings.config = config;
+        self
+    }
+
+    /// Adds a category, or replaces the one with the same key
+    pub fn category(mut self, key: impl Into<String>, category: CategoryConfig) -> Self {
+        self.settings.config.categories.insert(key.into(), category);
+        self
+    }
+
+    /// Grouping inside each category folder, instead of the config's enable_* flags
+    pub fn grouping(mut self, grouping: Grouping) -> Self {
+        self.grouping = Some(grouping);
+        self
+    }
+
+    pub fn skip_patterns(mut self, patterns: Vec<String>) -> Self {
+        self.settings.config.skip_patterns = patterns;
+        self
+    }
+
+    /// Create the folders under `dir` instead of the directory being organized
+    pub fn dest(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.settings.set_dest(dir.into());
+        self
+    }
+
+    /// Copy files instead of moving them
+    pub fn copy(mut self, copy: bool) -> Self {
+        self.settings.set_copy(copy);
+        self
+    }
+
+    /// Destination and transfer mode in one, as parsed from `--dest` and `--copy`
+    pub fn output(mut self, output: Output) -> Self {
+        self.settings.output = output;
+        self
+    }
+
+    /// Corrections learned from earlier runs (see `Feedback::learn_from_journal`)
+    pub fn feedback(mut self, feedback: Feedback) -> Self {
+        self.settings.feedback = Arc::new(feedback);
+        self
+    }
+
+    pub fn build(self) -> Result<Categorizer> {
+        let settings = self.settings.finish()?;
+        let config = &settings.config;
+        let grouping = self.grouping.unwrap_or_else(|| config.grouping());
+        Ok(Categorizer {
+            pipeline: Pipeline::new(config.organizer_config(), grouping, settings.output)
+                .with_feedback(settings.feedback),
+        })
+    }
+}
+
+// Filename similarity
+
+/// Groups files whose names look alike (`tyr -f`)
+pub struct SimilarityGrouper {
+    settings: Settings,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SimilarityGrouperBuilder {
+    settings: Settings,
+}
+
+impl SimilarityGrouper {
+    pub fn builder() -> SimilarityGrouperBuilder {
+        SimilarityGrouperBuilder::default()
+    }
+
+    /// Groups file names without touching the disk; single-file groups are included
+    pub fn group(&self, filenames: &[String]) -> Vec<Group> {
+        let feedback = &self.settings.feedback;
+        group_similar_files(filenames, &self.settings.config.similarity_config, feedback)
+            .into_iter()
+            .map(|group| Group {
+                name: suggest_folder_name(&group, feedback),
+                files: group.files.iter().map(PathBuf::from).collect(),
+            })
+            .collect()
+    }
+
+    /// The organizer behind `plan`, for driving it step by step (the TUI does)
+    pub fn organizer(&self) -> SimilarityOrganizer {
+        SimilarityOrganizer::new(self.settings.config.similarity_config.clone())
+            .with_feedback(self.settings.feedback.clone())
+    }
+
+    /// Moves for the files directly inside `dir`; files without a look-alike stay put
+    pub fn plan(&self, dir: &Path) -> Result<Plan> {
+        check_dir(dir)?;
+        Ok(strategy::plan_directory(
+            &self.organizer(),
+            dir,
+            &self.settings.config.skip_patterns,
+            &self.settings.output,
+        )?)
+    }
+
+    pub fn execute(&self, plan: &Plan) -> ExecuteResult {
+        pipeline::execute_moves(plan)
+    }
+
+    pub fn organize(&self, dir: &Path) -> Result<ExecuteResult> {
+        let plan = self.plan(dir)?;
+        Ok(self.execute(&plan))
+    }
+}
+
+impl SimilarityGrouperBuilder {
+    /// Every setting from a loaded tyr.toml; the other setters override parts of it
+    pub fn config(mut self, config: TyrConfig) -> Self {
+        self.settings.config = config;
+        self
+    }
+
+    pub fn similarity(mut self, config: SimilarityConfig) -> Self {
+        self.settings.config.similarity_config = config;
+        self
+    }
+
+    pub fn skip_patterns(mut self, patterns: Vec<String>) -> Self {
+        self.settings.config.skip_patterns = patterns;
+        self
+    }
+
+    /// Create the folders under `dir` instead of the directory being organized
+    pub fn dest(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.settings.set_dest(dir.into());
+        self
+    }
+
+    /// Copy files instead of moving them
+    pub fn copy(mut self, copy: bool) -> Self {
+        self.settings.set_copy(copy);
+        self
+    }
+
+    /// Destination and transfer mode in one, as parsed from `--dest` and `--copy`
+    pub fn output(mut self, output: Output) -> Self {
+        self.settings.output = output;
+        self
+    }
+
+    /// Corrections learned from earlier runs (see `Feedback::learn_from_journal`)
+    pub fn feedback(mut self, feedback: Feedback) -> Self {
+        self.settings.feedback = Arc::new(feedback);
+        self
+    }
+
+    pub fn build(self) -> Result<SimilarityGrouper> {
+        Ok(SimilarityGrouper {
+            settings: self.settings.finish()?,
+        })
+    }
+}
+
+// Intelligent clustering
+
+/// Clusters files by name and content with TF-IDF and k-means (`tyr -i`)
+pub struct IntelligentGrouper {
+    settings: Settings,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IntelligentGrouperBuilder {
+    settings: Settings,
+}
+
+impl IntelligentGrouper {
+    pub fn builder() -> IntelligentGrouperBuilder {
+        IntelligentGrouperBuilder::default()
+    }
+
+    /// Clusters the given files; text files are read for their content
+    pub fn group(&self, files: &[PathBuf]) -> Result<Vec<Group>> {
+        let result = cluster_files(
+            files,
+            &self.settings.config.intelligent_config,
+            &self.settings.feedback,
+            &|_| {},
+        )?;
+        Ok(result
+            .groups
+            .into_iter()
+            .map(|group| Group {
+                name: sanitize_dirname(&group.suggested_name),
+                files: group.files,
+            })
+            .collect())
+    }
+
+    /// The organizer behind `plan`, for driving it step by step (the TUI does)
+    pub fn organizer(&self) -> ClusterOrganizer {
+        ClusterOrganizer::new(self.settings.config.intelligent_config.clone())
+            .with_feedback(self.settings.feedback.clone())
+    }
+
+    /// Moves for the files inside `dir`, one folder per cluster
+    pub fn plan(&self, dir: &Path) -> Result<Plan> {
+        check_dir(dir)?;
+        Ok(strategy::plan_directory(
+            &self.organizer(),
+            dir,
+            &self.settings.config.skip_patterns,
+            &self.settings.output,
+        )?)
+    }
+
+    pub fn execute(&self, plan: &Plan) -> ExecuteResult {
+        pipeline::execute_moves(plan)
+    }
+
+    pub fn organize(&self, dir: &Path) -> Result<ExecuteResult> {
+        let plan = self.plan(dir)?;
+        Ok(self.execute(&plan))
+    }
+}
+
+impl IntelligentGrouperBuilder {
+    /// Every setting from a loaded tyr.toml; the other setters override parts of it
+    pub fn config(mut self, config: TyrConfig) -> Self {
+        self.settings.config = config;
+        self
+    }
+
+    pub fn intelligent(mut self, config: IntelligentConfig) -> Self {
+        self.settings.config.intelligent_config = config;
+        self
+    }
+
+    pub fn skip_patterns(mut self, patterns: Vec<String>) -> Self {
+        self.settings.config.skip_patterns = patterns;
+        self
+    }
+
+    /// Create the folders under `dir` instead of the directory being organized
+    pub fn dest(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.settings.set_dest(dir.into());
+        self
+    }
+
+    /// Copy files instead of moving them
+    pub fn copy(mut self, copy: bool) -> Self {
+        self.settings.set_copy(copy);
+        self
+    }
+
+    /// Destination and transfer mode in one, as parsed from `--dest` and `--copy`
+    pub fn output(mut self, output: Output) -> Self {
+        self.settings.output = output;
+        self
+    }
+
+    /// Corrections learned from earlier runs (see `Feedback::learn_from_journal`)
+    pub fn feedback(mut self, feedback: Feedback) -> Self {
+        self.settings.feedback = Arc::new(feedback);
+        self
+    }
+
+    pub fn build(self) -> Result<IntelligentGrouper> {
+        Ok(IntelligentGrouper {
+            settings: self.settings.finish()?,
+        })
+    }
+}
+
+// Trained classifier
+
+/// Sends files to the folders of a tree learned with `tyr train` (`tyr classify`)
+///
+/// Plans go into the trained tree unless a destination is set.
+pub struct Classifier {
+    organizer: ClassifierOrganizer,
+    settings: Settings,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ClassifierBuilder {
+    settings: Settings,
+    model: Option<Model>,
+}
+
+impl Classifier {
+    pub fn builder() -> ClassifierBuilder {
+        ClassifierBuilder::default()
+    }
+
+    pub fn organizer(&self) -> &ClassifierOrganizer {
+        &self.organizer
+    }
+
+    /// Where the model would put each file directly inside `dir`
+    pub fn predict(&self, dir: &Path) -> Result<Vec<Prediction>> {
+        check_dir(dir)?;
+        let files = strategy::list_files(dir, &self.settings.config.skip_patterns)?;
+        Ok(self.organizer.predict(&files))
+    }
+
+    /// Moves for `predictions` of files in `dir`; unsure ones go to the review folder
+    pub fn plan_predictions(&self, dir: &Path, predictions: &[Prediction]) -> Result<Plan> {
+        let assignments = self.organizer.assignments(predictions);
+        Ok(strategy::plan(
+            dir,
+            &self.settings.output,
+            &self.organizer,
+            &assignments,
+            None,
+        )?)
+    }
+
+    pub fn plan(&self, dir: &Path) -> Result<Plan> {
+        let predictions = self.predict(dir)?;
+        self.plan_predictions(dir, &predictions)
+    }
+
+    pub fn execute(&self, plan: &Plan) -> ExecuteResult {
+        pipeline::execute_moves(plan)
+    }
+
+    pub fn organize(&self, dir: &Path) -> Result<ExecuteResult> {
+        let plan = self.plan(dir)?;
+        Ok(self.execute(&plan))
+    }
+}
+
+impl ClassifierBuilder {
+    /// Every setting from a loaded tyr.toml; the other setters override parts of it
+    pub fn config(mut self, config: TyrConfig) -> Self {
+        self.settings.config = config;
+        self
+    }
+
+    /// Use this model instead of the one `tyr train` saved
+    pub fn model(mut self, model: Model) -> Self {
+        self.model = Some(model);
+        self
+    }
+
+    pub fn skip_patterns(mut self, patterns: Vec<String>) -> Self {
+        self.settings.config.skip_patterns = patterns;
+        self
+    }
+
+    /// Create the folders under `dir` instead of the trained tree
+    pub fn dest(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.settings.set_dest(dir.into());
+        self
+    }
+
+    /// Copy files instead of moving them
+    pub fn copy(mut self, copy: bool) -> Self {
+        self.settings.set_copy(copy);
+        self
+    }
+
+    /// Destination and transfer mode in one, as parsed from `--dest` and `--copy`
+    pub fn output(mut self, output: Output) -> Self {
+        self.settings.output = output;
+        self
+    }
+
+    pub fn build(self) -> Result<Classifier> {
+        let mut settings = self.settings.finish()?;
+        let model = match self.model {
+            Some(model) => model,
+            None => {
+                let path = config::get_model_path()?;
+                if !path.exists() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::NotFound,
+                        "No trained model yet, run 'tyr train <organized folder>' first",
+                    )
+                    .into());
+                }
+                Model::load(&path)?
+            }
+        };
+        // Predicted folders are relative to the trained tree
+        settings
+            .output
+            .dest_root
+            .get_or_insert_with(|| model.root.clone());
+        Ok(Classifier {
+            organizer: ClassifierOrganizer::new(model, settings.config.classifier_config.clone()),
+            settings,
+        })
+    }
+}
+
+fn check_dir(dir: &Path) -> Result<()> {
+    if dir.is_dir() {
+        Ok(())
+    } else {
+        Err(TyrError::NotADirectory(dir.to_path_buf()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_similarity_grouper_plans_and_undoes() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        for name in ["invoice_2024_01.pdf", "invoice_2024_02.pdf", "holiday.jpg"] {
+            fs::write(base.join(name), name).unwrap();
+        }
+
+        let grouper = SimilarityGrouper::builder().build().unwrap();
+        let plan = grouper.plan(base).unwrap();
+        assert_eq!(plan.moves.len(), 2);
+        let folder = plan.moves[0].to.parent().unwrap();
+        assert!(plan.moves.iter().all(|m| m.to.parent() == Some(folder)));
+
+        let result = grouper.execute(&plan);
+        assert_eq!(result.files_moved, 2);
+        assert!(base.join("holiday.jpg").exists());
+        assert_eq!(result.journal.undo().restored, 2);
+        assert!(base.join("invoice_2024_01.pdf").exists());
+    }
+
+    #[test]
+    fn test_output_hooks_win_over_config_hooks() {
+        let mut config = TyrConfig::default();
+        config.hooks.pre_run = "echo config".to_string();
+        config.hooks.post_run = "echo config".to_string();
+        let mut output = Output::default();
+        output.hooks.pre_run = "echo output".to_string();
+
+        let settings = Settings {
+            config,
+            output,
+            ..Settings::default()
+        }
+        .finish()
+        .unwrap();
+        assert_eq!(settings.output.hooks.pre_run, "echo output");
+        assert_eq!(settings.output.hooks.post_run, "echo config");
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_config() {
+        let mut config = TyrConfig::default();
+        config.similarity_config.jaccard_weight = 2.0;
+        let result = SimilarityGrouper::builder().config(config).build();
+        assert!(matches!(result, Err(TyrError::InvalidConfig(_))));
+
+        let categorizer = Categorizer::builder().build().unwrap();
+        assert!(matches!(
+            categorizer.plan(Path::new("/nonexistent/tyr")),
+            Err(TyrError::NotADirectory(_))
+        ));
+    }
+}
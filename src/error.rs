@@ -0,0 +1,331 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for typed errors, per-file failures and exit codes for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::config::ConfigIssue;
+
+/// Exit code when nothing could be done (or a command failed outright)
+pub const EXIT_FAILURE: i32 = 1;
+/// Exit code when the config cannot be parsed or has errors
+pub const EXIT_CONFIG: i32 = 2;
+/// Exit code when some files were handled and others failed
+pub const EXIT_PARTIAL: i32 = 3;
+
+/// Errors of the library API, carrying the path involved where there is one
+#[derive(Debug)]
+pub enum TyrError {
+    /// A config file is not valid TOML or has a value of the wrong type
+    ConfigParse {
+        file: PathBuf,
+        line: Option<usize>,
+        message: String,
+    },
+    /// The config has errors (warnings are not reported)
+    InvalidConfig(Vec<ConfigIssue>),
+    /// The directory to organize does not exist or is a file
+    NotADirectory(PathBuf),
+    NotFound(PathBuf),
+    /// The target name is taken, or no free `_N` name was left
+    Conflict(PathBuf),
+    PermissionDenied(PathBuf),
+    /// A move to another filesystem failed in its copy-then-delete fallback
+    CrossDevice {
+        from: PathBuf,
+        to: PathBuf,
+        source: io::Error,
+    },
+    ExtractFailed {
+        archive: PathBuf,
+        source: io::Error,
+    },
+    TrashFailed {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// A `[hooks]` command exited with an error; a failing `pre_run` stops the run
+    HookFailed {
+        hook: &'static str,
+        message: String,
+    },
+    /// Some files of a run failed; see the run's failure records for which and why
+    PartialFailure {
+        failed: usize,
+        total: usize,
+    },
+    Io {
+        path: Option<PathBuf>,
+        source: io::Error,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, TyrError>;
+
+impl TyrError {
+    /// Classifies an I/O error on `path` by its kind
+    pub fn io(path: &Path, source: io::Error) -> Self {
+        let path = path.to_path_buf();
+        match source.kind() {
+            io::ErrorKind::NotFound => TyrError::NotFound(path),
+            io::ErrorKind::PermissionDenied => TyrError::PermissionDenied(path),
+            io::ErrorKind::AlreadyExists => TyrError::Conflict(path),
+            _ => TyrError::Io {
+                path: Some(path),
+                source,
+            },
+        }
+    }
+
+    /// Classifies a failed move or copy of `from` to `to`
+    pub fn transfer(from: &Path, to: &Path, source: io::Error) -> Self {
+        match source.kind() {
+            io::ErrorKind::AlreadyExists if to.exists() => TyrError::Conflict(to.to_path_buf()),
+            // A file sits where one of the target folders should be
+            io::ErrorKind::AlreadyExists => TyrError::Io {
+                path: to.parent().map(Path::to_path_buf),
+                source,
+            },
+            io::ErrorKind::PermissionDenied | io::ErrorKind::NotFound => TyrError::io(from, source),
+            _ if source.kind() == io::ErrorKind::CrossesDevices || !same_device(from, to) => {
+                TyrError::CrossDevice {
+                    from: from.to_path_buf(),
+                    to: to.to_path_buf(),
+                    source,
+                }
+            }
+            _ => TyrError::io(from, source),
+        }
+    }
+
+    /// The file or directory the error is about
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            TyrError::ConfigParse { file, .. } => Some(file),
+            TyrError::NotADirectory(path)
+            | TyrError::NotFound(path)
+            | TyrError::Conflict(path)
+            | TyrError::PermissionDenied(path)
+            | TyrError::TrashFailed { path, .. } => Some(path),
+            TyrError::CrossDevice { from, .. } => Some(from),
+            TyrError::ExtractFailed { archive, .. } => Some(archive),
+            TyrError::Io { path, .. } => path.as_deref(),
+            TyrError::InvalidConfig(_)
+            | TyrError::HookFailed { .. }
+            | TyrError::PartialFailure { .. } => None,
+        }
+    }
+
+    /// Process exit code for a run that ended with this error
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            TyrError::ConfigParse { .. } | TyrError::InvalidConfig(_) => EXIT_CONFIG,
+            TyrError::PartialFailure { failed, total } if failed < total => EXIT_PARTIAL,
+            _ => EXIT_FAILURE,
+        }
+    }
+}
+
+impl fmt::Display for TyrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TyrError::ConfigParse {
+                file,
+                line: Some(line),
+                message,
+            } => write!(f, "{}:{}: {}", file.display(), line, message),
+            TyrError::ConfigParse { file, message, .. } => {
+                write!(f, "{}: {}", file.display(), message)
+            }
+            TyrError::InvalidConfig(issues) => {
+                write!(f, "invalid config")?;
+                for issue in issues {
+                    write!(f, "\n  {}", issue)?;
+                }
+                Ok(())
+            }
+            TyrError::NotADirectory(path) => write!(f, "not a directory: {}", path.display()),
+            TyrError::NotFound(path) => write!(f, "no such file: {}", path.display()),
+            TyrError::Conflict(path) => write!(f, "name already taken: {}", path.display()),
+            TyrError::PermissionDenied(path) => {
+                write!(f, "permission denied: {}", path.display())
+            }
+            TyrError::CrossDevice { from, to, source } => write!(
+                f,
+                "could not move {} to another filesystem ({}): {}",
+                from.display(),
+                to.display(),
+                source
+            ),
+            TyrError::ExtractFailed { archive, source } => {
+                write!(f, "could not extract {}: {}", archive.display(), source)
+            }
+            TyrError::TrashFailed { path, source } => {
+                write!(f, "could not trash {}: {}", path.display(), source)
+            }
+            TyrError::HookFailed { hook, message } => {
+                write!(f, "{} hook failed: {}", hook, message)
+            }
+            TyrError::PartialFailure { failed, total } => {
+                write!(f, "{} of {} files failed", failed, total)
+            }
+            TyrError::Io {
+                path: Some(path),
+                source,
+            } => write!(f, "{}: {}", path.display(), source),
+            TyrError::Io { path: None, source } => write!(f, "{}", source),
+        }
+    }
+}
+
+impl std::error::Error for TyrError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TyrError::CrossDevice { source, .. }
+            | TyrError::ExtractFailed { source, .. }
+            | TyrError::TrashFailed { source, .. }
+            | TyrError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Plans hand their failures on to the run's result; I/O errors are copied by kind and
+/// message, since `io::Error` cannot be cloned
+impl Clone for TyrError {
+    fn clone(&self) -> Self {
+        let copy = |e: &io::Error| io::Error::new(e.kind(), e.to_string());
+        match self {
+            TyrError::ConfigParse {
+                file,
+                line,
+                message,
+            } => TyrError::ConfigParse {
+                file: file.clone(),
+                line: *line,
+                message: message.clone(),
+            },
+            TyrError::InvalidConfig(issues) => TyrError::InvalidConfig(issues.clone()),
+            TyrError::NotADirectory(path) => TyrError::NotADirectory(path.clone()),
+            TyrError::NotFound(path) => TyrError::NotFound(path.clone()),
+            TyrError::Conflict(path) => TyrError::Conflict(path.clone()),
+            TyrError::PermissionDenied(path) => TyrError::PermissionDenied(path.clone()),
+            TyrError::CrossDevice { from, to, source } => TyrError::CrossDevice {
+                from: from.clone(),
+                to: to.clone(),
+                source: copy(source),
+            },
+            TyrError::ExtractFailed { archive, source } => TyrError::ExtractFailed {
+                archive: archive.clone(),
+                source: copy(source),
+            },
+            TyrError::TrashFailed { path, source } => TyrError::TrashFailed {
+                path: path.clone(),
+                source: copy(source),
+            },
+            TyrError::HookFailed { hook, message } => TyrError::HookFailed {
+                hook,
+                message: message.clone(),
+            },
+            TyrError::PartialFailure { failed, total } => TyrError::PartialFailure {
+                failed: *failed,
+                total: *total,
+            },
+            TyrError::Io { path, source } => TyrError::Io {
+                path: path.clone(),
+                source: copy(source),
+            },
+        }
+    }
+}
+
+impl From<io::Error> for TyrError {
+    fn from(source: io::Error) -> Self {
+        TyrError::Io { path: None, source }
+    }
+}
+
+/// A file a run could not handle, and why
+#[derive(Debug, Clone)]
+pub struct FileFailure {
+    pub path: PathBuf,
+    pub error: TyrError,
+}
+
+impl FileFailure {
+    pub fn new(path: &Path, error: TyrError) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            error,
+        }
+    }
+}
+
+impl fmt::Display for FileFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Most errors already name the path
+        match self.error.path() {
+            Some(path) if path == self.path => write!(f, "{}", self.error),
+            _ => write!(f, "{}: {}", self.path.display(), self.error),
+        }
+    }
+}
+
+/// Ok when nothing failed, otherwise a `PartialFailure` for the exit code
+pub fn check_failures(failed: usize, total: usize) -> Result<()> {
+    if failed == 0 {
+        Ok(())
+    } else {
+        Err(TyrError::PartialFailure { failed, total })
+    }
+}
+
+#[cfg(unix)]
+fn same_device(from: &Path, to: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    // `to` does not exist yet, its folder does (or its folder's folder, and so on)
+    let target = to.ancestors().skip(1).find_map(|dir| dir.metadata().ok());
+    match (from.metadata(), target) {
+        (Ok(from), Some(to)) => from.dev() == to.dev(),
+        _ => true,
+    }
+}
+
+#[cfg(not(unix))]
+fn same_device(_from: &Path, _to: &Path) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_io_errors_are_classified_with_their_path() {
+        let path = Path::new("/srv/uploads/a.pdf");
+        let denied = TyrError::io(path, io::Error::from(io::ErrorKind::PermissionDenied));
+        assert!(matches!(&denied, TyrError::PermissionDenied(p) if p == path));
+        assert_eq!(denied.path(), Some(path));
+
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("a.pdf");
+        std::fs::write(&target, "x").unwrap();
+        let exists = || io::Error::from(io::ErrorKind::AlreadyExists);
+        let taken = TyrError::transfer(path, &target, exists());
+        assert!(matches!(&taken, TyrError::Conflict(p) if *p == target));
+        // A file where the target folder should be is not a name conflict
+        let blocked = TyrError::transfer(path, &target.join("b.pdf"), exists());
+        assert_eq!(blocked.path(), Some(target.as_path()));
+
+        assert_eq!(check_failures(2, 5).unwrap_err().exit_code(), EXIT_PARTIAL);
+        assert_eq!(check_failures(5, 5).unwrap_err().exit_code(), EXIT_FAILURE);
+        assert_eq!(TyrError::InvalidConfig(Vec::new()).exit_code(), EXIT_CONFIG);
+    }
+}
@@ -0,0 +1,61 @@
+/*
+ * Tyr - ML-Powered, Blazingly Fast File Organizer with a Beautiful TUI.
+ * Copyright (C) 2026  Dawood Khan (Nurysso)
+ *
+ * Contact Maintainer Nurysso [@] proton.me
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Tyr's organizers as a library
+//!
+//! Each organizer is made with a builder, works out a [`Plan`] without touching the disk,
+//! and executes it into an [`ExecuteResult`] whose journal can undo the run:
+//!
+//! ```no_run
+//! use std::path::Path;
+//! use tyr::SimilarityGrouper;
+//!
+//! let grouper = SimilarityGrouper::builder()
+//!     .skip_patterns(vec!["*.part".to_string()])
+//!     .build()?;
+//!
+//! // Only the grouping, nothing is moved
+//! let names = vec!["invoice_01.pdf".to_string(), "invoice_02.pdf".to_string()];
+//! for group in grouper.group(&names) {
+//!     println!("{}: {:?}", group.name, group.files);
+//! }
+//!
+//! let plan = grouper.plan(Path::new("/srv/uploads"))?;
+//! let result = grouper.execute(&plan);
+//! println!("{} files moved", result.files_moved);
+//! # Ok::<(), tyr::TyrError>(())
+//! ```
+//!
+//! The modules under [`organizer`] hold the building blocks (and the TUIs of the binary).
+
+mod api;
+pub mod config;
+pub mod error;
+pub mod organizer;
+
+pub use api::{
+    Categorizer, CategorizerBuilder, Classifier, ClassifierBuilder, Group, IntelligentGrouper,
+    IntelligentGrouperBuilder, SimilarityGrouper, SimilarityGrouperBuilder,
+};
+pub use config::TyrConfig;
+pub use error::{FileFailure, Result, TyrError};
+pub use organizer::feedback::Feedback;
+pub use organizer::pipeline::{ExecuteResult, Grouping, Plan, PlannedMove};
+pub use organizer::strategy::{Assignment, Assignments, Organizer, Target};
+pub use organizer::transfer::{Output, TransferMode};
@@ -17,878 +17,1296 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 use chrono::Local;
-use serde::Deserialize;
 use std::env;
 use std::fs::{self, OpenOptions};
-use std::io::Write;
-use std::path::PathBuf;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 use std::process;
 
-mod organizer;
-use organizer::categorise::{FileOrganizerConfig, TuiApp};
-use organizer::filename::{FilenameTuiApp, SimilarityConfig};
-use organizer::intelligent::{IntelligentConfig, IntelligentTuiApp};
+use tyr::config::{self, LoadedConfig, TyrConfig};
+use tyr::error;
+use tyr::organizer::buckets::{BucketKind, BucketOrganizer};
+use tyr::organizer::categorise::FileOrganizer;
+use tyr::organizer::classifier::Model;
+use tyr::organizer::journal::{EntryKind, Journal};
+use tyr::organizer::pipeline::{ExecuteResult, Plan};
+use tyr::organizer::rename::RenameTuiApp;
+use tyr::organizer::report::{self, ReportFormat, ReportOptions, ReportTuiApp};
+use tyr::organizer::similar::{self, SimilarTuiApp};
+use tyr::organizer::strategy::{Organizer, Target};
+use tyr::organizer::transfer::{Output, TransferMode};
+use tyr::organizer::trash;
+use tyr::organizer::tui::OrganizerTuiApp;
+use tyr::{Categorizer, Classifier, Feedback, IntelligentGrouper, SimilarityGrouper, TyrError};
 
-/// Main configuration structure that includes all settings
-#[derive(Debug, Clone, Deserialize)]
-pub struct tyrConfig {
-    #[serde(default)]
-    pub log_file: Option<String>,
-
-    #[serde(default)]
-    pub enable_smart_grouping: bool,
-
-    #[serde(default)]
-    pub enable_intelligent_grouping: bool,
-
-    #[serde(default)]
-    pub similarity_config: SimilarityConfigToml,
+/// Log a message to the configured log file
+fn log_to_file(log_path: &Option<String>, message: &str) {
+    if let Some(path_str) = log_path {
+        let path = PathBuf::from(path_str);
+        let timestamp = Local::now().format("%Y-%m-%d %H:%M:%S");
+        let log_message = format!("[{}] {}\n", timestamp, message);
 
-    #[serde(default)]
-    pub intelligent_config: IntelligentConfigToml,
+        if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&path) {
+            let _ = file.write_all(log_message.as_bytes());
+        }
+    }
 }
 
-/// TOML representation of similarity config
-#[derive(Debug, Clone, Deserialize)]
-pub struct SimilarityConfigToml {
-    #[serde(default = "default_levenshtein_threshold")]
-    pub levenshtein_threshold: f64,
+fn print_help() {
+    println!("TTTTTTTTTTTTTTTTTTTTTTTYYYYYYY       YYYYYYYRRRRRRRRRRRRRRRRR");
+    println!("T:::::::::::::::::::::TY:::::Y       Y:::::YR::::::::::::::::R");
+    println!("T:::::::::::::::::::::TY:::::Y       Y:::::YR::::::RRRRRR:::::R");
+    println!("T:::::TT:::::::TT:::::TY::::::Y     Y::::::YRR:::::R     R:::::R");
+    println!("TTTTTT  T:::::T  TTTTTTYYY:::::Y   Y:::::YYY  R::::R     R:::::R");
+    println!("        T:::::T           Y:::::Y Y:::::Y     R::::R     R:::::R");
+    println!("        T:::::T            Y:::::Y:::::Y      R::::RRRRRR:::::R ");
+    println!("        T:::::T             Y:::::::::Y       R:::::::::::::RR  ");
+    println!("        T:::::T              Y:::::::Y        R::::RRRRRR:::::R ");
+    println!("        T:::::T               Y:::::Y         R::::R     R:::::R");
+    println!("        T:::::T               Y:::::Y         R::::R     R:::::R");
+    println!("        T:::::T               Y:::::Y         R::::R     R:::::R");
+    println!("      TT:::::::TT             Y:::::Y       RR:::::R     R:::::R");
+    println!("      T:::::::::T          YYYY:::::YYYY    R::::::R     R:::::R");
+    println!("      T:::::::::T          Y:::::::::::Y    R::::::R     R:::::R");
+    println!("      TTTTTTTTTTT          YYYYYYYYYYYYY    RRRRRRRR     RRRRRRR");
+    println!("    ML-Powered • Blazingly Fast • Beautiful TUI    ");
+    println!("USAGE:");
+    println!("    tyr [OPTIONS] [DIRECTORY]");
+    println!("\n    Without a mode flag, files are sorted into category folders and, if");
+    println!("    enable_smart_grouping or enable_intelligent_grouping is set in tyr.toml,");
+    println!("    grouped again inside each category folder.");
+    println!("\nOPTIONS:");
+    println!("    -v, --version    Shows version duh!)");
+    println!(
+        "    -c, --categorize    Organize files by category (images, videos, documents, etc.)"
+    );
+    println!("    -f, --filename      Group similar files based on filename patterns");
+    println!("    -i, --intelligent   Use ML-based clustering with TF-IDF content analysis");
+    println!("    -s, --size          Sort files into size buckets (Tiny, Small, Large, Huge)");
+    println!(
+        "    -a, --age           Sort files into age buckets (This week ... Older than a year)"
+    );
+    println!("    -r, --rename        Rename files in place from a template (date prefix, slugs)");
+    println!("    -nui, --no-ui       Skip UI and automatically organize files");
+    println!("    --dest <dir>        Create the folders in DIR instead of the target directory");
+    println!("    --copy              Copy files instead of moving them");
+    println!("    --explain           Show why -f and -i grouped files (also 'e' in the TUI)");
+    println!("    --config <path>     Use this config file instead of ~/.config/tyr/tyr.toml");
+    println!("    -h, --help          Show this help message");
+    println!("\nCOMMANDS:");
+    println!("    undo                            Move the files of the last run back");
+    println!("    report [DIR] [--format json|csv|md] [--output FILE] [--top N] [--stale-days N]");
+    println!(
+        "                                    Show what takes up space, without moving anything"
+    );
+    println!("    trash-list                      List the files in the trash");
+    println!("    feedback [clear]                Show (or forget) grouping corrections learned");
+    println!("    train <DIR>                     Learn the folders of an organized tree");
+    println!("    classify [DIR]                  Move files into the folders 'train' learned");
+    println!("    similar [DIR]                   Find almost identical text files and images");
+    println!("    restore <N|PATH>                Restore a trashed file (number from trash-list)");
+    println!("    config check [DIR]              Validate the config used for DIR");
+    println!("    config show [--effective] [DIR] Print the config files (or the merged result)");
+    println!("\nCONFIG:");
+    println!(
+        "    The user config is taken from --config, then $TYR_CONFIG, then the default path."
+    );
+    println!("    A .tyr.toml in the target directory or any parent overrides it, nearest last.");
+}
 
-    #[serde(default = "default_jaccard_threshold")]
-    pub jaccard_threshold: f64,
+/// Command line with the global options (`--config`, `-nui`, `--dest`, `--copy`,
+/// `--explain`) pulled out
+struct CliArgs {
+    config_path: Option<PathBuf>,
+    no_ui: bool,
+    explain: bool,
+    output: Output,
+    /// Mode or command followed by its own arguments
+    rest: Vec<String>,
+}
 
-    #[serde(default = "default_levenshtein_weight")]
-    pub levenshtein_weight: f64,
+fn parse_args(args: &[String]) -> Result<CliArgs, String> {
+    let mut cli = CliArgs {
+        config_path: None,
+        no_ui: false,
+        explain: false,
+        output: Output::default(),
+        rest: Vec::new(),
+    };
 
-    #[serde(default = "default_jaccard_weight")]
-    pub jaccard_weight: f64,
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-nui" | "--no-ui" => cli.no_ui = true,
+            "--explain" => cli.explain = true,
+            "--copy" => cli.output.mode = TransferMode::Copy,
+            "--config" => match iter.next() {
+                Some(path) => cli.config_path = Some(PathBuf::from(path)),
+                None => return Err("--config needs a file path".to_string()),
+            },
+            "--dest" => match iter.next() {
+                Some(dir) => cli.output.dest_root = Some(PathBuf::from(dir)),
+                None => return Err("--dest needs a directory".to_string()),
+            },
+            _ => {
+                if let Some(path) = arg.strip_prefix("--config=") {
+                    cli.config_path = Some(PathBuf::from(path));
+                } else if let Some(dir) = arg.strip_prefix("--dest=") {
+                    cli.output.dest_root = Some(PathBuf::from(dir));
+                } else {
+                    cli.rest.push(arg.clone());
+                }
+            }
+        }
+    }
 
-    #[serde(default = "default_min_similarity_score")]
-    pub min_similarity_score: f64,
+    Ok(cli)
 }
 
-/// TOML representation of intelligent config
-#[derive(Debug, Clone, Deserialize)]
-pub struct IntelligentConfigToml {
-    #[serde(default = "default_max_lines_to_read")]
-    pub max_lines_to_read: usize,
+/// Arguments of `tyr report`
+struct ReportArgs {
+    dir: Option<String>,
+    format: Option<ReportFormat>,
+    output: Option<PathBuf>,
+    options: ReportOptions,
+}
 
-    #[serde(default = "default_min_cluster_size")]
-    pub min_cluster_size: usize,
+fn parse_report_args(args: &[String]) -> Result<ReportArgs, String> {
+    let mut report = ReportArgs {
+        dir: None,
+        format: None,
+        output: None,
+        options: ReportOptions::default(),
+    };
 
-    #[serde(default = "default_max_clusters")]
-    pub max_clusters: usize,
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let mut value = |what: &str| {
+            inline
+                .clone()
+                .or_else(|| iter.next().cloned())
+                .ok_or_else(|| format!("{} needs {}", flag, what))
+        };
+        match flag {
+            "--format" => {
+                let name = value("a format (json, csv or md)")?;
+                report.format = Some(
+                    ReportFormat::from_name(&name)
+                        .ok_or_else(|| format!("Unknown report format '{}'", name))?,
+                );
+            }
+            "--output" | "-o" => report.output = Some(PathBuf::from(value("a file path")?)),
+            "--top" => {
+                report.options.top = value("a number")?
+                    .parse()
+                    .map_err(|_| "--top needs a number".to_string())?
+            }
+            "--stale-days" => {
+                report.options.stale_days = value("a number")?
+                    .parse()
+                    .map_err(|_| "--stale-days needs a number".to_string())?
+            }
+            _ if arg.starts_with('-') => return Err(format!("Unknown report option '{}'", arg)),
+            _ => report.dir = Some(arg.clone()),
+        }
+    }
 
-    #[serde(default = "default_filename_similarity_weight")]
-    pub filename_similarity_weight: f64,
+    Ok(report)
+}
 
-    #[serde(default = "default_content_similarity_weight")]
-    pub content_similarity_weight: f64,
+/// Uses the given directory argument, or the current directory when there is none
+fn resolve_target_dir(arg: Option<&String>) -> PathBuf {
+    match arg {
+        Some(path) => PathBuf::from(path),
+        None => match env::current_dir() {
+            Ok(dir) => dir,
+            Err(e) => {
+                eprintln!("✗ Error: Could not get current directory: {}", e);
+                process::exit(1);
+            }
+        },
+    }
+}
 
-    #[serde(default = "default_similarity_threshold")]
-    pub similarity_threshold: f64,
+/// Prints config problems and refuses to organize files with an invalid config
+fn ensure_valid_config(loaded: &LoadedConfig) {
+    for issue in &loaded.issues {
+        eprintln!("!  {}", issue);
+    }
 
-    #[serde(default = "default_max_iterations")]
-    pub max_iterations: usize,
+    if loaded.has_errors() {
+        eprintln!("✗ Error: Invalid configuration, run 'tyr config check' for details");
+        log_to_file(&loaded.config.log_file, "Error: Invalid configuration");
+        process::exit(error::EXIT_CONFIG);
+    }
 }
 
-// Default functions for serde - Similarity Config
-fn default_levenshtein_threshold() -> f64 {
-    0.7
-}
-fn default_jaccard_threshold() -> f64 {
-    0.5
-}
-fn default_levenshtein_weight() -> f64 {
-    0.6
-}
-fn default_jaccard_weight() -> f64 {
-    0.4
-}
-fn default_min_similarity_score() -> f64 {
-    0.65
-}
+fn run_config_command(args: &[String], loaded: &LoadedConfig) {
+    match args.first().map(|s| s.as_str()) {
+        Some("check") => {
+            if loaded.layers.is_empty() {
+                println!("Config: <built-in defaults>");
+            }
+            for layer in &loaded.layers {
+                println!("Config: {}", layer.display());
+            }
+            if loaded.issues.is_empty() {
+                println!("✓ Configuration is valid");
+                return;
+            }
 
-// Default functions for serde - Intelligent Config
-fn default_max_lines_to_read() -> usize {
-    100
-}
-fn default_min_cluster_size() -> usize {
-    2
-}
-fn default_max_clusters() -> usize {
-    20
-}
-fn default_filename_similarity_weight() -> f64 {
-    0.3
-}
-fn default_content_similarity_weight() -> f64 {
-    0.7
-}
-fn default_similarity_threshold() -> f64 {
-    0.65
+            for issue in &loaded.issues {
+                println!("   • {}", issue);
+            }
+            if loaded.has_errors() {
+                process::exit(error::EXIT_CONFIG);
+            }
+        }
+        Some("show") => {
+            if args.iter().any(|a| a == "--effective") {
+                match toml::to_string_pretty(&loaded.config) {
+                    Ok(content) => {
+                        println!("# Effective configuration, merged from:");
+                        for layer in &loaded.layers {
+                            println!("#   {}", layer.display());
+                        }
+                        println!("{}", content);
+                    }
+                    Err(e) => {
+                        eprintln!("✗ Error: Could not serialize config: {}", e);
+                        process::exit(1);
+                    }
+                }
+            } else if loaded.layers.is_empty() {
+                eprintln!("No config file, using built-in defaults");
+            } else {
+                for layer in &loaded.layers {
+                    match fs::read_to_string(layer) {
+                        Ok(content) => {
+                            if loaded.layers.len() > 1 {
+                                println!("# ==> {} <==", layer.display());
+                            }
+                            print!("{}", content);
+                        }
+                        Err(e) => {
+                            eprintln!("✗ Error: Could not read {}: {}", layer.display(), e);
+                            process::exit(1);
+                        }
+                    }
+                }
+            }
+        }
+        _ => {
+            eprintln!("✗ Error: Unknown config command");
+            eprintln!("\nUsage:");
+            eprintln!("  tyr config check [DIR]");
+            eprintln!("  tyr config show [--effective] [DIR]");
+            process::exit(1);
+        }
+    }
 }
-fn default_max_iterations() -> usize {
-    100
+
+/// Asks a yes/no question on the terminal, defaulting to no
+fn confirm(question: &str) -> io::Result<bool> {
+    print!("{} [y/N] ", question);
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
 }
 
-impl Default for SimilarityConfigToml {
-    fn default() -> Self {
-        Self {
-            levenshtein_threshold: 0.7,
-            jaccard_threshold: 0.5,
-            levenshtein_weight: 0.6,
-            jaccard_weight: 0.4,
-            min_similarity_score: 0.65,
+/// Prints the destination folders of a plan with their file counts
+fn print_plan(plan: &Plan) {
+    let root = plan.output.root(&plan.base_path);
+    let mut folders: Vec<(&Path, usize)> = Vec::new();
+    for planned in &plan.moves {
+        let folder = planned
+            .to
+            .parent()
+            .and_then(|p| p.strip_prefix(root).ok())
+            .unwrap_or(Path::new(""));
+        match folders.iter_mut().find(|(f, _)| *f == folder) {
+            Some((_, count)) => *count += 1,
+            None => folders.push((folder, 1)),
         }
     }
-}
 
-impl Default for IntelligentConfigToml {
-    fn default() -> Self {
-        Self {
-            max_lines_to_read: 100,
-            min_cluster_size: 2,
-            max_clusters: 20,
-            filename_similarity_weight: 0.3,
-            content_similarity_weight: 0.7,
-            similarity_threshold: 0.65,
-            max_iterations: 100,
+    folders.sort();
+
+    println!("\nPlan ({} files):", plan.moves.len() + plan.trash.len());
+    if plan.output.dest_root.is_some() {
+        println!("   Destination: {}", root.display());
+    }
+    for (folder, count) in folders {
+        println!("   {:30} ← {} files", folder.display().to_string(), count);
+    }
+    if !plan.trash.is_empty() {
+        println!("   {:30} ← {} files", "(Trash)", plan.trash.len());
+    }
+    if !plan.extract.is_empty() {
+        println!("   Archives to extract: {}", plan.extract.len());
+    }
+    if !plan.already_extracted.is_empty() {
+        println!("   Already extracted next to themselves:");
+        for path in &plan.already_extracted {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            println!("   • {}", name);
         }
     }
+    for failure in &plan.failures {
+        println!("!  {}", failure);
+    }
+    println!();
 }
 
-impl Default for tyrConfig {
-    fn default() -> Self {
-        Self {
-            log_file: None,
-            enable_smart_grouping: false,
-            enable_intelligent_grouping: false,
-            similarity_config: SimilarityConfigToml::default(),
-            intelligent_config: IntelligentConfigToml::default(),
-        }
+/// The `pre_run` hook error of a run it stopped
+fn aborted(result: &mut ExecuteResult) -> Option<TyrError> {
+    if result.aborted {
+        result.failures.pop().map(|failure| failure.error)
+    } else {
+        None
     }
 }
 
-/// Convert TOML config to runtime config
-impl From<SimilarityConfigToml> for SimilarityConfig {
-    fn from(toml_config: SimilarityConfigToml) -> Self {
-        SimilarityConfig {
-            levenshtein_threshold: toml_config.levenshtein_threshold,
-            jaccard_threshold: toml_config.jaccard_threshold,
-            levenshtein_weight: toml_config.levenshtein_weight,
-            jaccard_weight: toml_config.jaccard_weight,
-            min_similarity_score: toml_config.min_similarity_score,
-        }
+/// Logs every entry and failure of a run and saves its undo journal
+fn record_run(result: &ExecuteResult, tyr_config: &TyrConfig) {
+    for planned in &result.journal.moves {
+        log_to_file(
+            &tyr_config.log_file,
+            &format!(
+                "{}: {} -> {}",
+                match planned.kind {
+                    EntryKind::Trash => "Trashed",
+                    EntryKind::Extract => "Extracted",
+                    EntryKind::Move => "Moved",
+                    EntryKind::Copy => "Copied",
+                },
+                planned.from.display(),
+                planned.to.display()
+            ),
+        );
+    }
+    for failure in &result.failures {
+        eprintln!("!  {}", failure);
+        log_to_file(&tyr_config.log_file, &failure.to_string());
     }
-}
 
-impl From<IntelligentConfigToml> for IntelligentConfig {
-    fn from(toml_config: IntelligentConfigToml) -> Self {
-        IntelligentConfig {
-            max_lines_to_read: toml_config.max_lines_to_read,
-            min_cluster_size: toml_config.min_cluster_size,
-            max_clusters: toml_config.max_clusters,
-            filename_similarity_weight: toml_config.filename_similarity_weight,
-            content_similarity_weight: toml_config.content_similarity_weight,
-            similarity_threshold: toml_config.similarity_threshold,
-            max_iterations: toml_config.max_iterations,
+    // One journal for the whole run, so a single `tyr undo` reverts all of it
+    if !result.journal.moves.is_empty() {
+        match config::get_journal_dir().and_then(|dir| result.journal.save(&dir)) {
+            Ok(path) => log_to_file(
+                &tyr_config.log_file,
+                &format!("Undo journal: {}", path.display()),
+            ),
+            Err(e) => eprintln!("!  Could not save undo journal: {}", e),
         }
     }
 }
 
-/// Gets the config directory path in a cross-platform way
-fn get_config_dir() -> std::io::Result<PathBuf> {
-    let config_dir = if cfg!(target_os = "windows") {
-        // Windows: Use %APPDATA%\tyr
-        let appdata = env::var("APPDATA").map_err(|_| {
-            std::io::Error::new(
-                std::io::ErrorKind::NotFound,
-                "Could not determine APPDATA directory",
-            )
-        })?;
-        PathBuf::from(appdata).join("tyr")
-    } else {
-        // Unix/Linux/macOS: Use ~/.config/tyr
-        let home = env::var("HOME").map_err(|_| {
-            std::io::Error::new(
-                std::io::ErrorKind::NotFound,
-                "Could not determine HOME directory",
-            )
-        })?;
-        PathBuf::from(home).join(".config").join("tyr")
+/// Journals looked at for files the user moved out of tyr's groups since
+const FEEDBACK_JOURNALS: usize = 20;
+
+/// Loads the grouping corrections, after learning from what the user did to the folders
+/// of the last runs; an unreadable store is left alone and not used
+fn load_feedback(tyr_config: &TyrConfig) -> Feedback {
+    let loaded = config::get_feedback_path().and_then(|path| Ok((Feedback::load(&path)?, path)));
+    let (mut feedback, path) = match loaded {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("!  Not using earlier corrections: {}", e);
+            return Feedback::default();
+        }
     };
 
-    if !config_dir.exists() {
-        fs::create_dir_all(&config_dir)?;
-        println!("Created config directory: {}", config_dir.display());
+    let journals = config::get_journal_dir()
+        .and_then(|dir| Journal::recent(&dir, FEEDBACK_JOURNALS))
+        .unwrap_or_default();
+    let mut learned = false;
+    for journal in journals.iter().filter_map(|p| Journal::load(p).ok()) {
+        learned |= feedback.learn_from_journal(&journal);
     }
-
-    Ok(config_dir)
+    if learned {
+        log_to_file(
+            &tyr_config.log_file,
+            "Learned from files moved since the last runs",
+        );
+        save_feedback(&feedback, &path);
+    }
+    feedback
 }
 
-/// Gets the config file path: Windows: %APPDATA%\tyr\tyr.toml, Unix: ~/.config/tyr/tyr.toml
-fn get_config_path() -> std::io::Result<PathBuf> {
-    let config_dir = get_config_dir()?;
-    Ok(config_dir.join("tyr.toml"))
+fn save_feedback(feedback: &Feedback, path: &Path) {
+    if let Err(e) = feedback.save(path) {
+        eprintln!("!  Could not save corrections: {}", e);
+    }
 }
 
-/// Gets the default log file path: Windows: %APPDATA%\tyr\tyr.log, Unix: ~/.config/tyr/tyr.log
-fn get_default_log_path() -> std::io::Result<PathBuf> {
-    let config_dir = get_config_dir()?;
-    Ok(config_dir.join("tyr.log"))
+fn print_undo_hint(result: &ExecuteResult, copying: bool) {
+    if result.files_moved > 0 && copying {
+        println!("\nRun 'tyr undo' to delete the copies");
+    } else if result.files_moved > 0 {
+        println!("\nRun 'tyr undo' to move them back");
+    }
 }
 
-/// Load configuration from tyr.toml or create default
-fn load_tyr_config() -> tyrConfig {
-    let config_path = match get_config_path() {
-        Ok(path) => path,
-        Err(e) => {
-            eprintln!("Warning: Could not determine config path: {}", e);
-            return tyrConfig::default();
-        }
-    };
+fn run_pipeline_mode(
+    target_dir: PathBuf,
+    tyr_config: &TyrConfig,
+    no_ui: bool,
+    output: Output,
+    feedback: Feedback,
+) -> tyr::Result<()> {
+    let copying = output.mode == TransferMode::Copy;
+    let pipeline = Categorizer::builder()
+        .config(tyr_config.clone())
+        .output(output)
+        .feedback(feedback)
+        .build()?;
 
-    if config_path.exists() {
-        // Try to read and parse config using proper TOML deserialization
-        match fs::read_to_string(&config_path) {
-            Ok(content) => {
-                match toml::from_str::<tyrConfig>(&content) {
-                    Ok(mut config) => {
-                        // Convert relative log path to absolute if needed
-                        if let Some(ref log_file) = config.log_file {
-                            if log_file != "none" && !log_file.is_empty() {
-                                let log_path = PathBuf::from(log_file);
-                                // If it's a relative path, make it absolute relative to config dir
-                                if log_path.is_relative() {
-                                    if let Ok(config_dir) = get_config_dir() {
-                                        config.log_file = Some(
-                                            config_dir.join(log_path).to_string_lossy().to_string(),
-                                        );
-                                    }
-                                }
-                            } else {
-                                config.log_file = None;
-                            }
-                        }
+    log_to_file(
+        &tyr_config.log_file,
+        &format!(
+            "=== Starting Tyr (Pipeline: {} - No UI: {}) ===",
+            pipeline.describe(),
+            no_ui
+        ),
+    );
+    log_to_file(
+        &tyr_config.log_file,
+        &format!("Target directory: {}", target_dir.display()),
+    );
 
-                        // println!("✓ Loaded configuration from: {}", config_path.display());
-                        return config;
-                    }
-                    Err(e) => {
-                        eprintln!("Warning: Could not parse config file: {}", e);
-                        eprintln!("Using default configuration...");
-                    }
-                }
-            }
-            Err(e) => {
-                eprintln!("Warning: Could not read config file: {}", e);
-            }
-        }
-    } else {
-        // Create default config file with logging enabled
-        let default_log_path = match get_default_log_path() {
-            Ok(path) => path,
-            Err(e) => {
-                eprintln!("Warning: Could not determine log path: {}", e);
-                return tyrConfig::default();
-            }
-        };
+    println!("Tyr - {}", pipeline.describe());
 
-        // Convert path to string with forward slashes for cross-platform TOML compatibility
-        let log_path_str = default_log_path.to_string_lossy().replace('\\', "/");
-
-        let config_content = format!(
-            r#"# Tyr File Organizer Configuration
-batch_size = 100
-
-# Enable smart grouping using filename similarity detection
-# When enabled, files with similar names will be grouped together
-# even if they have different extensions
-enable_smart_grouping = false
-
-# Enable intelligent grouping using ML clustering with TF-IDF
-# This analyzes file content and uses advanced clustering algorithms
-# More powerful than smart grouping but slower
-enable_intelligent_grouping = false
-
-log_file = "{}"
-
-# Files/patterns to skip during organization
-skip_patterns = [
-    ".DS_Store",
-    "Thumbs.db",
-    ".git",
-    ".gitignore",
-    "desktop.ini",
-    ".localized"
-]
-
-# Smart grouping configuration (used in filename similarity mode)
-[similarity_config]
-# Levenshtein distance threshold (0.0 to 1.0)
-# Higher = stricter matching. Measures character-level similarity.
-levenshtein_threshold = 0.7
-
-# Jaccard similarity threshold (0.0 to 1.0)
-# Higher = stricter matching. Measures word/token overlap.
-jaccard_threshold = 0.5
-
-# Weight for Levenshtein distance in final score (0.0 to 1.0)
-levenshtein_weight = 0.6
-
-# Weight for Jaccard similarity in final score (0.0 to 1.0)
-# Note: levenshtein_weight + jaccard_weight should = 1.0
-jaccard_weight = 0.4
-
-# Minimum similarity score to group files together (0.0 to 1.0)
-# Higher = files must be more similar to be grouped
-# 0.65 is a good balance for most use cases
-min_similarity_score = 0.65
-
-# Intelligent grouping configuration (ML-based clustering)
-[intelligent_config]
-# Maximum number of lines to read from text files for content analysis
-max_lines_to_read = 100
-
-# Minimum number of files required to form a cluster/group
-min_cluster_size = 2
-
-# Maximum number of clusters to create
-# Higher values = more granular grouping
-max_clusters = 20
-
-# Weight for filename similarity (0.0 to 1.0)
-# How much the filename affects grouping decisions
-filename_similarity_weight = 0.3
-
-# Weight for content similarity (0.0 to 1.0)
-# How much file content affects grouping decisions (for text files)
-# Note: filename_similarity_weight + content_similarity_weight should = 1.0
-content_similarity_weight = 0.7
-
-# Minimum similarity threshold for grouping files (0.0 to 1.0)
-# Higher = files must be more similar to be grouped together
-similarity_threshold = 0.65
-
-# Maximum iterations for K-means clustering algorithm
-max_iterations = 100
-
-# Define your custom categories (used when intelligent grouping is disabled)
-# Each category has:
-#   - extensions: list of file extensions (without dot)
-#   - folder_name: optional custom folder name (defaults to category key)
-
-[categories.images]
-extensions = ["jpg", "jpeg", "png", "gif", "bmp", "svg", "webp", "tiff", "ico", "heic", "raw", "cr2", "nef", "orf", "sr2"]
-folder_name = "Images"
-
-[categories.videos]
-extensions = ["mp4", "avi", "mkv", "mov", "wmv", "flv", "webm", "m4v", "3gp", "mpg", "mpeg", "vob"]
-folder_name = "Videos"
-
-[categories.audio]
-extensions = ["mp3", "wav", "flac", "aac", "ogg", "wma", "m4a", "opus", "aiff", "ape", "alac"]
-folder_name = "Music"
-
-[categories.documents]
-extensions = ["pdf", "doc", "docx", "txt", "rtf", "odt", "pages", "tex", "md", "epub", "mobi"]
-folder_name = "Documents"
-
-[categories.spreadsheets]
-extensions = ["xls", "xlsx", "csv", "ods", "numbers"]
-folder_name = "Spreadsheets"
-
-[categories.presentations]
-extensions = ["ppt", "pptx", "odp", "key"]
-folder_name = "Presentations"
-
-[categories.archives]
-extensions = ["zip", "rar", "7z", "tar", "gz", "bz2", "xz", "dmg", "pkg", "deb", "rpm", "iso"]
-folder_name = "Archives"
-
-[categories.code]
-extensions = ["rs", "py", "js", "ts", "jsx", "tsx", "html", "css", "scss", "sass", "cpp", "c", "h", "hpp", "java", "go", "php", "rb", "swift", "kt", "dart", "scala", "sh", "bat", "ps1", "r", "lua", "vim"]
-folder_name = "Code"
-
-[categories.data]
-extensions = ["json", "xml", "yaml", "yml", "toml", "ini", "cfg", "conf", "sql", "db", "sqlite", "mdb"]
-folder_name = "Data"
-
-[categories.executables]
-extensions = ["exe", "msi", "app", "deb", "rpm", "dmg", "pkg", "appimage", "run"]
-folder_name = "Applications"
-
-[categories.fonts]
-extensions = ["ttf", "otf", "woff", "woff2", "eot"]
-folder_name = "Fonts"
-
-[categories.ebooks]
-extensions = ["epub", "mobi", "azw", "azw3", "cbr", "cbz"]
-folder_name = "Ebooks"
-
-[categories.3d_models]
-extensions = ["obj", "fbx", "stl", "blend", "dae", "3ds", "max", "gltf", "glb"]
-folder_name = "3D Models"
-
-[categories.design]
-extensions = ["psd", "ai", "xd", "sketch", "fig", "indd", "cdr"]
-folder_name = "Design Files"
-
-# Add your custom categories below:
-# [categories.my_custom_category]
-# extensions = ["ext1", "ext2", "ext3"]
-# folder_name = "My Custom Folder"
+    let plan = pipeline.plan(&target_dir)?;
+    if plan.moves.is_empty() && plan.trash.is_empty() {
+        println!("Nothing to organize");
+        return Ok(());
+    }
 
-"#,
-            log_path_str
-        );
+    print_plan(&plan);
 
-        if let Err(e) = fs::write(&config_path, config_content) {
-            eprintln!("!  Could not create config file: {}", e);
-        } else {
-            println!("✓ Created default config at: {}", config_path.display());
-        }
+    let verb = if copying { "Copy" } else { "Move" };
+    let question = if plan.trash.is_empty() {
+        format!("{} {} files?", verb, plan.moves.len())
+    } else {
+        format!(
+            "{} {} files and send {} to the trash?",
+            verb,
+            plan.moves.len(),
+            plan.trash.len()
+        )
+    };
+    if !no_ui && !confirm(&question)? {
+        println!("Cancelled, nothing was moved");
+        log_to_file(&tyr_config.log_file, "Pipeline cancelled by user");
+        return Ok(());
+    }
 
-        return tyrConfig {
-            log_file: Some(log_path_str),
-            enable_smart_grouping: false,
-            enable_intelligent_grouping: false,
-            similarity_config: SimilarityConfigToml::default(),
-            intelligent_config: IntelligentConfigToml::default(),
-        };
+    let mut result = pipeline.execute(&plan);
+    if let Some(error) = aborted(&mut result) {
+        return Err(error);
     }
 
-    tyrConfig::default()
+    record_run(&result, tyr_config);
+
+    println!("\n✦ Organization Complete!\n");
+    println!("Summary:");
+    println!(
+        "   • Files {}:  {}",
+        plan.output.verb().to_lowercase(),
+        result.files_moved
+    );
+    if result.files_trashed > 0 {
+        println!("   • Files trashed: {}", result.files_trashed);
+    }
+    if result.archives_extracted > 0 {
+        println!("   • Archives extracted: {}", result.archives_extracted);
+    }
+    println!("   • Files failed: {}", result.failures.len());
+    print_undo_hint(&result, copying);
+
+    log_to_file(&tyr_config.log_file, "Organization completed successfully");
+    error::check_failures(
+        result.failures.len(),
+        plan.moves.len() + plan.trash.len() + plan.extract.len(),
+    )
 }
 
-/// Log a message to the configured log file
-fn log_to_file(log_path: &Option<String>, message: &str) {
-    if let Some(path_str) = log_path {
-        let path = PathBuf::from(path_str);
-        let timestamp = Local::now().format("%Y-%m-%d %H:%M:%S");
-        let log_message = format!("[{}] {}\n", timestamp, message);
+fn run_undo_command(tyr_config: &TyrConfig) -> std::io::Result<()> {
+    let journal_dir = config::get_journal_dir()?;
+    let Some(path) = Journal::latest(&journal_dir)? else {
+        println!("Nothing to undo");
+        return Ok(());
+    };
 
-        if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&path) {
-            let _ = file.write_all(log_message.as_bytes());
-        }
+    let mut journal = Journal::load(&path)?;
+    println!(
+        "Undoing run from {} in {} ({} moves)",
+        journal.created,
+        journal.base_path.display(),
+        journal.moves.len()
+    );
+
+    let result = journal.undo();
+    for error in &result.errors {
+        eprintln!("!  {}", error);
+        log_to_file(&tyr_config.log_file, &format!("Undo: {}", error));
+    }
+    log_to_file(
+        &tyr_config.log_file,
+        &format!(
+            "Undo restored {} files from {}",
+            result.restored,
+            path.display()
+        ),
+    );
+
+    if result.remaining.is_empty() {
+        fs::remove_file(&path)?;
+    } else {
+        journal.moves = result.remaining;
+        journal.write_to(&path)?;
+        println!(
+            "{} moves could not be undone; fix the problems above and run 'tyr undo' again",
+            journal.moves.len()
+        );
     }
+
+    println!("✓ Restored {} files", result.restored);
+    Ok(())
 }
 
-fn print_help() {
-    println!("TTTTTTTTTTTTTTTTTTTTTTTYYYYYYY       YYYYYYYRRRRRRRRRRRRRRRRR");
-    println!("T:::::::::::::::::::::TY:::::Y       Y:::::YR::::::::::::::::R");
-    println!("T:::::::::::::::::::::TY:::::Y       Y:::::YR::::::RRRRRR:::::R");
-    println!("T:::::TT:::::::TT:::::TY::::::Y     Y::::::YRR:::::R     R:::::R");
-    println!("TTTTTT  T:::::T  TTTTTTYYY:::::Y   Y:::::YYY  R::::R     R:::::R");
-    println!("        T:::::T           Y:::::Y Y:::::Y     R::::R     R:::::R");
-    println!("        T:::::T            Y:::::Y:::::Y      R::::RRRRRR:::::R ");
-    println!("        T:::::T             Y:::::::::Y       R:::::::::::::RR  ");
-    println!("        T:::::T              Y:::::::Y        R::::RRRRRR:::::R ");
-    println!("        T:::::T               Y:::::Y         R::::R     R:::::R");
-    println!("        T:::::T               Y:::::Y         R::::R     R:::::R");
-    println!("        T:::::T               Y:::::Y         R::::R     R:::::R");
-    println!("      TT:::::::TT             Y:::::Y       RR:::::R     R:::::R");
-    println!("      T:::::::::T          YYYY:::::YYYY    R::::::R     R:::::R");
-    println!("      T:::::::::T          Y:::::::::::Y    R::::::R     R:::::R");
-    println!("      TTTTTTTTTTT          YYYYYYYYYYYYY    RRRRRRRR     RRRRRRR");
-    println!("    ML-Powered • Blazingly Fast • Beautiful TUI    ");
-    println!("USAGE:");
-    println!("    tyr [OPTIONS] [DIRECTORY]");
-    println!("\nOPTIONS:");
-    println!("    -v, --version    Shows version duh!)");
+/// `tyr train <organized-root>`: learns the folders of a hand-organized tree
+fn run_train_command(args: &[String], tyr_config: &TyrConfig) -> std::io::Result<()> {
+    let Some(root) = args.iter().find(|a| !a.starts_with('-')) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "train needs the organized folder to learn from, e.g. 'tyr train ~/Archive'",
+        ));
+    };
+    let root = Path::new(root);
+    if !root.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Directory does not exist: {}", root.display()),
+        ));
+    }
+    // Absolute, so `tyr classify` finds it from any directory
+    let root = root.canonicalize()?;
+
+    println!("Learning the folders of {}...", root.display());
+    let model = Model::train(
+        &root,
+        &tyr_config.classifier_config,
+        &tyr_config.skip_patterns,
+    )?;
+    let path = config::get_model_path()?;
+    model.save(&path)?;
+    log_to_file(
+        &tyr_config.log_file,
+        &format!("Trained on {} ({} files)", root.display(), model.files),
+    );
+
+    let mut folders: Vec<(&str, usize)> = model.folders().collect();
+    folders.sort_by_key(|(_, files)| std::cmp::Reverse(*files));
     println!(
-        "    -c, --categorize    Organize files by category (images, videos, documents, etc.)"
+        "\n✓ Learned {} folders from {} files",
+        folders.len(),
+        model.files
     );
-    println!("    -f, --filename      Group similar files based on filename patterns");
-    println!("    -i, --intelligent   Use ML-based clustering with TF-IDF content analysis");
-    println!("    -nui, --no-ui       Skip UI and automatically organize files");
-    println!("    -h, --help          Show this help message");
+    for (folder, files) in folders.iter().take(10) {
+        println!("   • {:30} {} files", folder, files);
+    }
+    if folders.len() > 10 {
+        println!("   ... and {} more", folders.len() - 10);
+    }
+    println!("\nModel saved to {}", path.display());
+    println!("Run 'tyr classify <folder>' to sort new files into it");
+    Ok(())
 }
 
-fn run_categorize_mode(
-    target_dir: PathBuf,
-    tyr_config: &tyrConfig,
+/// `tyr classify <inbox>`: moves files into the folders the trained model predicts
+fn run_classify_mode(
+    inbox: PathBuf,
+    tyr_config: &TyrConfig,
     no_ui: bool,
-) -> std::io::Result<()> {
-    let config_path = get_config_path()?;
+    output: Output,
+) -> tyr::Result<()> {
+    let classifier = Classifier::builder()
+        .config(tyr_config.clone())
+        .output(output)
+        .build()?;
+    let organizer = classifier.organizer();
 
-    log_to_file(
-        &tyr_config.log_file,
-        &format!("=== Starting Tyr (Categorize Mode - No UI: {}) ===", no_ui),
+    println!(
+        "Tyr - {} ({}, {} files)",
+        organizer.name(),
+        organizer.model().root.display(),
+        organizer.model().files
     );
     log_to_file(
         &tyr_config.log_file,
-        &format!("Target directory: {}", target_dir.display()),
+        &format!(
+            "=== Starting Tyr (Classify {} into {}) ===",
+            inbox.display(),
+            organizer.model().root.display()
+        ),
     );
 
-    println!("Tyr - Categorize Mode");
+    let predictions = classifier.predict(&inbox)?;
+    if predictions.is_empty() {
+        println!("Nothing to classify");
+        return Ok(());
+    }
 
-    // Load or create config
-    let config = if config_path.exists() {
-        match FileOrganizerConfig::load_from_file(&config_path) {
-            Ok(cfg) => {
-                log_to_file(&tyr_config.log_file, "Config loaded successfully");
-                cfg
+    println!();
+    let mut unsure = 0;
+    for prediction in &predictions {
+        let name = prediction
+            .file
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy();
+        let confidence = prediction.confidence * 100.0;
+        match organizer.target(prediction) {
+            Target::Folder(folder) if folder == prediction.folder => {
+                println!("   ✓ {:40} → {} ({:.0}%)", name, folder, confidence)
             }
-            Err(e) => {
-                eprintln!("!  Failed to load config: {}", e);
-                println!("Using default configuration...");
-                log_to_file(
-                    &tyr_config.log_file,
-                    &format!("Failed to load config: {}", e),
-                );
-                FileOrganizerConfig::default()
+            Target::Folder(folder) => {
+                unsure += 1;
+                println!(
+                    "   ? {:40} → {} (maybe {}, {:.0}%)",
+                    name, folder, prediction.folder, confidence
+                )
             }
+            Target::Trash | Target::Keep => {}
         }
-    } else {
-        println!("ℹ  No config file found, creating default config...");
-        let default_config = FileOrganizerConfig::default();
+    }
 
-        if let Err(e) = default_config.save_to_file(&config_path) {
-            eprintln!("! Could not save default config: {}", e);
-            log_to_file(
-                &tyr_config.log_file,
-                &format!("Could not save default config: {}", e),
+    let plan = classifier.plan_predictions(&inbox, &predictions)?;
+    print_plan(&plan);
+
+    let copying = plan.output.mode == TransferMode::Copy;
+    let verb = if copying { "Copy" } else { "Move" };
+    if !no_ui && !confirm(&format!("{} {} files?", verb, plan.moves.len()))? {
+        println!("Cancelled, nothing was moved");
+        return Ok(());
+    }
+
+    let mut result = classifier.execute(&plan);
+    if let Some(error) = aborted(&mut result) {
+        return Err(error);
+    }
+    record_run(&result, tyr_config);
+
+    println!("\n✦ Classification Complete!\n");
+    println!("Summary:");
+    println!(
+        "   • Files {}:  {}",
+        plan.output.verb().to_lowercase(),
+        result.files_moved
+    );
+    println!(
+        "   • To review in {}: {}",
+        tyr_config.classifier_config.review_folder, unsure
+    );
+    println!("   • Files failed: {}", result.failures.len());
+    print_undo_hint(&result, copying);
+
+    error::check_failures(result.failures.len(), plan.moves.len())
+}
+
+/// `tyr feedback [clear]`: what was learned from corrections, or forget it
+fn run_feedback_command(args: &[String]) -> std::io::Result<()> {
+    let path = config::get_feedback_path()?;
+    if args.first().map(String::as_str) == Some("clear") {
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+        println!("✓ Forgot all corrections");
+        return Ok(());
+    }
+
+    let feedback = Feedback::load(&path)?;
+    if feedback.is_empty() {
+        println!("No corrections learned yet");
+        return Ok(());
+    }
+    println!("Corrections learned ({}):", path.display());
+    println!("   • {} pairs kept together", feedback.must_link.len());
+    println!("   • {} pairs kept apart", feedback.cannot_link.len());
+    if !feedback.folder_names.is_empty() {
+        let names: Vec<&str> = feedback
+            .folder_names
+            .iter()
+            .map(|chosen| chosen.name.as_str())
+            .collect();
+        println!("   • Folder names: {}", names.join(", "));
+    }
+    println!("\nRun 'tyr feedback clear' to forget them");
+    Ok(())
+}
+
+/// `tyr similar [DIR]`: almost identical text files and images, reviewed before trashing
+fn run_similar_command(
+    target_dir: PathBuf,
+    tyr_config: &TyrConfig,
+    no_ui: bool,
+    output: &Output,
+) -> tyr::Result<()> {
+    if !target_dir.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Directory does not exist: {}", target_dir.display()),
+        )
+        .into());
+    }
+    let target_dir = target_dir.canonicalize().unwrap_or(target_dir);
+    if output.dest_root.is_some() || output.mode == TransferMode::Copy {
+        println!("!  --dest and --copy do not apply to near-duplicates, files are only trashed");
+    }
+
+    println!("Looking for near-duplicates in {}...", target_dir.display());
+    let sets = similar::find(
+        &target_dir,
+        &tyr_config.near_duplicate_config,
+        &tyr_config.skip_patterns,
+    )?;
+    log_to_file(
+        &tyr_config.log_file,
+        &format!(
+            "Near-duplicates in {}: {} sets",
+            target_dir.display(),
+            sets.len()
+        ),
+    );
+    if sets.is_empty() {
+        println!("✓ No near-duplicates found");
+        return Ok(());
+    }
+
+    // Without a review there is no telling which copy to keep, so nothing is trashed
+    if no_ui {
+        for set in &sets {
+            println!(
+                "\n   • {} {} files, {:.0}% alike",
+                match set.kind {
+                    similar::Kind::Text => "Text",
+                    similar::Kind::Image => "Image",
+                },
+                set.files.len(),
+                set.similarity * 100.0
             );
-        } else {
-            println!("✓ Default config created at: {}", config_path.display());
-            println!("   Edit this file to customize categories!");
-            log_to_file(&tyr_config.log_file, "Created default config");
+            for (i, file) in set.files.iter().enumerate() {
+                println!(
+                    "       {} {}",
+                    if i == 0 { "keep" } else { "    " },
+                    file.path
+                        .strip_prefix(&target_dir)
+                        .unwrap_or(&file.path)
+                        .display()
+                );
+            }
         }
+        println!("\nRun 'tyr similar' without -nui to pick the copies to trash");
+        return Ok(());
+    }
 
-        default_config
+    let output = Output {
+        hooks: tyr_config.hooks.clone(),
+        ..Output::default()
+    };
+    let mut app = SimilarTuiApp::new(target_dir, output, sets);
+    app.run()?;
+    let Some(mut result) = app.take_result() else {
+        println!("Cancelled, nothing was trashed");
+        return Ok(());
     };
+    if let Some(error) = aborted(&mut result) {
+        return Err(error);
+    }
+    record_run(&result, tyr_config);
 
-    // Launch TUI or auto-organize
-    let mut app = TuiApp::new(config, target_dir);
+    println!("\n✓ Trashed {} near-duplicates", result.files_trashed);
+    if result.files_trashed > 0 {
+        println!("\nRun 'tyr undo' to restore them");
+    }
+    error::check_failures(
+        result.failures.len(),
+        result.files_trashed + result.failures.len(),
+    )
+}
 
-    let result = if no_ui {
-        app.auto_organize()
-    } else {
-        app.run()
+fn run_trash_list_command() -> std::io::Result<()> {
+    let entries = trash::list()?;
+    if entries.is_empty() {
+        println!("The trash is empty");
+        return Ok(());
+    }
+
+    for (i, entry) in entries.iter().enumerate() {
+        let deleted = entry
+            .deleted
+            .map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "unknown date".to_string());
+        println!(
+            "{:4}  {}  {}",
+            i + 1,
+            deleted,
+            entry.original_path.display()
+        );
+    }
+    println!("\nRun 'tyr restore <number>' to put a file back");
+    Ok(())
+}
+
+fn run_restore_command(args: &[String], tyr_config: &TyrConfig) -> std::io::Result<()> {
+    let Some(wanted) = args.first() else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "restore needs a number from 'tyr trash-list' or the original path",
+        ));
     };
 
-    // Log completion
-    match &result {
-        Ok(_) => {
-            log_to_file(&tyr_config.log_file, "Organization completed successfully");
-            println!("\n✦ File organization complete!");
+    let entries = trash::list()?;
+    // A number from trash-list, otherwise the most recently trashed file with that path
+    let entry = match wanted.parse::<usize>() {
+        Ok(n) => n.checked_sub(1).and_then(|i| entries.get(i)),
+        Err(_) => {
+            let path = std::path::absolute(wanted)?;
+            entries.iter().find(|e| e.original_path == path)
         }
-        Err(e) => {
-            log_to_file(
-                &tyr_config.log_file,
-                &format!("Error during organization: {}", e),
-            );
-        }
-    }
+    };
+    let Some(entry) = entry else {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("'{}' is not in the trash", wanted),
+        ));
+    };
 
-    result
+    let restored = trash::restore(entry)?;
+    log_to_file(
+        &tyr_config.log_file,
+        &format!("Restored from trash: {}", restored.display()),
+    );
+    println!("✓ Restored {}", restored.display());
+    Ok(())
 }
 
-fn run_filename_mode(
-    target_dir: PathBuf,
-    tyr_config: &tyrConfig,
+fn run_report_command(
+    args: ReportArgs,
+    tyr_config: &TyrConfig,
     no_ui: bool,
 ) -> std::io::Result<()> {
+    let target_dir = resolve_target_dir(args.dir.as_ref());
+    if !target_dir.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Directory does not exist: {}", target_dir.display()),
+        ));
+    }
+    let target_dir = target_dir.canonicalize().unwrap_or(target_dir);
+
+    let report = report::scan(
+        &target_dir,
+        &tyr_config.organizer_config(),
+        &tyr_config.bucket_config,
+        args.options,
+    )?;
     log_to_file(
         &tyr_config.log_file,
         &format!(
-            "=== Starting Tyr (Filename Similarity Mode - No UI: {}) ===",
-            no_ui
+            "Report of {}: {} files",
+            target_dir.display(),
+            report.total_files
         ),
     );
+
+    // Without a format, the dashboard; or Markdown when there is no terminal to draw on
+    let format = match args.format {
+        Some(format) => format,
+        None if args.output.is_some() || no_ui => ReportFormat::Markdown,
+        None => return ReportTuiApp::new(report).run(),
+    };
+
+    let content = report.render(format);
+    match args.output {
+        Some(path) => {
+            fs::write(&path, content)?;
+            println!("✓ Report written to {}", path.display());
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+/// The organizer behind a mode flag
+fn organizer_for(mode: &str, tyr_config: &TyrConfig) -> tyr::Result<Box<dyn Organizer>> {
+    let buckets = |kind| Box::new(BucketOrganizer::new(tyr_config.bucket_config.clone(), kind));
+    Ok(match mode {
+        "-f" | "--filename" => Box::new(
+            SimilarityGrouper::builder()
+                .config(tyr_config.clone())
+                .feedback(load_feedback(tyr_config))
+                .build()?
+                .organizer(),
+        ),
+        "-i" | "--intelligent" => Box::new(
+            IntelligentGrouper::builder()
+                .config(tyr_config.clone())
+                .feedback(load_feedback(tyr_config))
+                .build()?
+                .organizer(),
+        ),
+        "-s" | "--size" => buckets(BucketKind::Size),
+        "-a" | "--age" => buckets(BucketKind::Age),
+        _ => Box::new(FileOrganizer::new(tyr_config.organizer_config())),
+    })
+}
+
+/// Runs one organizer (`-c`, `-f`, `-i`, `-s`, `-a`) through the shared TUI
+fn run_organizer_mode(
+    organizer: Box<dyn Organizer>,
+    target_dir: PathBuf,
+    tyr_config: &TyrConfig,
+    no_ui: bool,
+    explain: bool,
+    output: Output,
+) -> tyr::Result<()> {
+    let name = organizer.name();
+    log_to_file(
+        &tyr_config.log_file,
+        &format!("=== Starting Tyr ({} - No UI: {}) ===", name, no_ui),
+    );
     log_to_file(
         &tyr_config.log_file,
         &format!("Target directory: {}", target_dir.display()),
     );
-
-    println!("Tyr - Filename Similarity Mode");
-
-    // Load similarity config from tyr.toml
-    let similarity_config: SimilarityConfig = tyr_config.similarity_config.clone().into();
-
+    let schema = organizer.config_schema();
     log_to_file(
         &tyr_config.log_file,
         &format!(
-            "Using similarity config: min_score={:.2}, lev_weight={:.2}, jac_weight={:.2}",
-            similarity_config.min_similarity_score,
-            similarity_config.levenshtein_weight,
-            similarity_config.jaccard_weight
+            "Using [{}]: {}",
+            schema.section,
+            schema
+                .settings
+                .iter()
+                .map(|s| format!("{}={}", s.key, s.value))
+                .collect::<Vec<_>>()
+                .join(", ")
         ),
     );
 
-    // Launch TUI or auto-organize
-    let mut app = FilenameTuiApp::new(target_dir, similarity_config);
+    println!("Tyr - {}", name);
 
+    let learns = organizer.learns();
+    let copying = output.mode == TransferMode::Copy;
+    let mut app = OrganizerTuiApp::new(
+        organizer,
+        target_dir,
+        tyr_config.skip_patterns.clone(),
+        output,
+    );
+    app.show_explanations(explain);
     let result = if no_ui {
         app.auto_organize()
     } else {
         app.run()
     };
-
-    // Get logs from the app and write them to file
-    if tyr_config.log_file.is_some() {
-        let logs = app.get_logs();
-        for log_msg in logs {
-            log_to_file(&tyr_config.log_file, &log_msg);
-        }
+    if let Err(e) = &result {
+        log_to_file(
+            &tyr_config.log_file,
+            &format!("Error during organization: {}", e),
+        );
     }
-
-    // Log completion
-    match &result {
-        Ok(_) => {
-            log_to_file(&tyr_config.log_file, "Organization completed successfully");
-            println!("\n✦ File organization complete!");
-        }
-        Err(e) => {
-            log_to_file(
-                &tyr_config.log_file,
-                &format!("Error during organization: {}", e),
-            );
+    result?;
+
+    // Renamed and ungrouped folders count even when the run is cancelled afterwards
+    let learned = app.take_learned();
+    if !learned.is_empty() {
+        let path = config::get_feedback_path();
+        if let Ok((mut feedback, path)) = path.and_then(|p| Ok((Feedback::load(&p)?, p))) {
+            feedback.merge(learned);
+            save_feedback(&feedback, &path);
         }
     }
 
-    result
+    // Quitting before organizing moves nothing
+    let Some(mut result) = app.take_result() else {
+        println!("Cancelled, nothing was moved");
+        return Ok(());
+    };
+    if let Some(error) = aborted(&mut result) {
+        return Err(error);
+    }
+    result.journal.learn = learns;
+    record_run(&result, tyr_config);
+    print_undo_hint(&result, copying);
+
+    log_to_file(&tyr_config.log_file, "Organization completed successfully");
+    // A file the plugin failed on is still moved, so it is only counted once
+    let unmoved = result
+        .failures
+        .iter()
+        .filter(|f| !result.journal.moves.iter().any(|m| m.from == f.path))
+        .count();
+    error::check_failures(
+        result.failures.len(),
+        result.files_moved + result.files_trashed + unmoved,
+    )
 }
 
-fn run_intelligent_mode(
+fn run_rename_mode(
     target_dir: PathBuf,
-    tyr_config: &tyrConfig,
+    tyr_config: &TyrConfig,
     no_ui: bool,
-) -> std::io::Result<()> {
+    output: &Output,
+) -> tyr::Result<()> {
     log_to_file(
         &tyr_config.log_file,
-        &format!(
-            "=== Starting Tyr (Intelligent ML Mode - No UI: {}) ===",
-            no_ui
-        ),
+        &format!("=== Starting Tyr (Rename Mode - No UI: {}) ===", no_ui),
     );
     log_to_file(
         &tyr_config.log_file,
         &format!("Target directory: {}", target_dir.display()),
     );
 
-    println!("Tyr - Intelligent ML Mode");
-
-    // Load intelligent config from tyr.toml
-    let intelligent_config: IntelligentConfig = tyr_config.intelligent_config.clone().into();
+    println!("Tyr - Rename Mode");
+    if output.dest_root.is_some() || output.mode == TransferMode::Copy {
+        println!("!  --dest and --copy do not apply to renaming, files are renamed in place");
+    }
 
-    log_to_file(
-        &tyr_config.log_file,
-        &format!(
-            "Using intelligent config: max_clusters={}, min_cluster_size={}, filename_weight={:.2}, content_weight={:.2}",
-            intelligent_config.max_clusters,
-            intelligent_config.min_cluster_size,
-            intelligent_config.filename_similarity_weight,
-            intelligent_config.content_similarity_weight
-        ),
+    let mut app = RenameTuiApp::new(
+        tyr_config.rename_config.clone(),
+        target_dir,
+        tyr_config.skip_patterns.clone(),
     );
+    if app.renames().is_empty() {
+        println!("\n✦ Every file name already fits the template");
+        return Ok(());
+    }
 
-    // Launch TUI or auto-analyze
-    let mut app = IntelligentTuiApp::new(intelligent_config, target_dir);
+    let result = if no_ui { app.auto_rename() } else { app.run() };
+    if let Err(e) = &result {
+        log_to_file(
+            &tyr_config.log_file,
+            &format!("Error during renaming: {}", e),
+        );
+    }
+    result?;
 
-    let result = if no_ui { app.auto_analyze() } else { app.run() };
+    // Quitting the preview renames nothing
+    let Some(renamed) = app.take_result() else {
+        println!("Cancelled, nothing was renamed");
+        return Ok(());
+    };
+    for entry in &renamed.journal.moves {
+        log_to_file(
+            &tyr_config.log_file,
+            &format!(
+                "Renamed: {} -> {}",
+                entry.from.display(),
+                entry.to.display()
+            ),
+        );
+    }
+    for failure in &renamed.failures {
+        eprintln!("!  {}", failure);
+        log_to_file(&tyr_config.log_file, &failure.to_string());
+    }
 
-    // Log completion
-    match &result {
-        Ok(_) => {
-            log_to_file(
-                &tyr_config.log_file,
-                "Intelligent analysis completed successfully",
-            );
-            println!("\n✦ Intelligent analysis complete!");
-        }
-        Err(e) => {
-            log_to_file(
-                &tyr_config.log_file,
-                &format!("Error during intelligent analysis: {}", e),
-            );
+    // The journal is the old -> new mapping, so `tyr undo` restores the old names
+    if !renamed.journal.moves.is_empty() {
+        match config::get_journal_dir().and_then(|dir| renamed.journal.save(&dir)) {
+            Ok(path) => {
+                println!("   • Mapping saved to {}", path.display());
+                log_to_file(
+                    &tyr_config.log_file,
+                    &format!("Undo journal: {}", path.display()),
+                );
+            }
+            Err(e) => eprintln!("!  Could not save undo journal: {}", e),
         }
     }
 
-    result
+    println!(
+        "\n✦ Renamed {} files ('tyr undo' reverts them)",
+        renamed.renamed
+    );
+    error::check_failures(
+        renamed.failures.len(),
+        renamed.renamed + renamed.failures.len(),
+    )
 }
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-
-    // Load configuration
-    let tyr_config = load_tyr_config();
+    let args: Vec<String> = env::args().skip(1).collect();
 
     // No arguments - show help
-    if args.len() < 2 {
+    if args.is_empty() {
         print_help();
         process::exit(0);
     }
 
-    // Check for -nui flag
-    let no_ui = args.contains(&"-nui".to_string()) || args.contains(&"--no-ui".to_string());
-
-    let mode = &args[1];
+    let cli = match parse_args(&args) {
+        Ok(cli) => cli,
+        Err(message) => {
+            eprintln!("✗ Error: {}", message);
+            process::exit(1);
+        }
+    };
 
-    // Parse arguments
-    match mode.as_str() {
-        "-h" | "--help" => {
+    let mode = match cli.rest.first() {
+        Some(mode) => mode.as_str(),
+        None if cli.no_ui => {
+            eprintln!("✗ Error: -nui flag must be used with -c, -f, -i, -s, -a or -r mode");
+            eprintln!("\nExamples:");
+            eprintln!("  tyr -c -nui /path/to/folder");
+            eprintln!("  tyr -f -nui /path/to/folder");
+            eprintln!("  tyr -i -nui /path/to/folder");
+            eprintln!("  tyr -s -nui /path/to/folder");
+            eprintln!("  tyr -r -nui /path/to/folder");
+            process::exit(1);
+        }
+        None => {
             print_help();
             process::exit(0);
         }
-        "-c" | "--categorize" => {
-            // Find target directory (skip -nui flag if present)
-            let target_dir = if args.len() > 2 {
-                let mut path_arg = None;
-                for (i, arg) in args.iter().enumerate() {
-                    if i > 1 && arg != "-nui" && arg != "--no-ui" {
-                        path_arg = Some(arg);
-                        break;
-                    }
-                }
+    };
 
-                if let Some(path) = path_arg {
-                    PathBuf::from(path)
-                } else {
-                    match env::current_dir() {
-                        Ok(dir) => dir,
-                        Err(e) => {
-                            eprintln!("✗ Error: Could not get current directory: {}", e);
-                            log_to_file(
-                                &tyr_config.log_file,
-                                &format!("Error: Could not get current directory: {}", e),
-                            );
-                            process::exit(1);
-                        }
-                    }
-                }
-            } else {
-                match env::current_dir() {
-                    Ok(dir) => dir,
-                    Err(e) => {
-                        eprintln!("✗ Error: Could not get current directory: {}", e);
-                        log_to_file(
-                            &tyr_config.log_file,
-                            &format!("Error: Could not get current directory: {}", e),
-                        );
-                        process::exit(1);
-                    }
-                }
-            };
+    let is_organize_mode = matches!(
+        mode,
+        "-c" | "--categorize"
+            | "-f"
+            | "--filename"
+            | "-i"
+            | "--intelligent"
+            | "-s"
+            | "--size"
+            | "-a"
+            | "--age"
+            | "-r"
+            | "--rename"
+    );
+    // Anything that is not a flag or command is the directory for the default pipeline
+    let is_pipeline = !mode.starts_with('-')
+        && !matches!(
+            mode,
+            "config"
+                | "undo"
+                | "trash-list"
+                | "restore"
+                | "report"
+                | "feedback"
+                | "train"
+                | "classify"
+                | "similar"
+        );
+    let is_classify = mode == "classify";
 
-            if !target_dir.exists() {
-                eprintln!(
-                    "✗ Error: Directory does not exist: {}",
-                    target_dir.display()
-                );
-                log_to_file(
-                    &tyr_config.log_file,
-                    &format!("Error: Directory does not exist: {}", target_dir.display()),
-                );
+    let report_args = if mode == "report" {
+        match parse_report_args(&cli.rest[1..]) {
+            Ok(args) => Some(args),
+            Err(message) => {
+                eprintln!("✗ Error: {}", message);
                 process::exit(1);
             }
+        }
+    } else {
+        None
+    };
 
-            if let Err(e) = run_categorize_mode(target_dir, &tyr_config, no_ui) {
+    // The target directory decides which .tyr.toml files apply
+    let target_dir = if is_organize_mode || is_classify || mode == "similar" {
+        Some(resolve_target_dir(cli.rest.get(1)))
+    } else if is_pipeline {
+        Some(PathBuf::from(mode))
+    } else if let Some(args) = &report_args {
+        Some(resolve_target_dir(args.dir.as_ref()))
+    } else if mode == "config" {
+        Some(resolve_target_dir(
+            cli.rest.iter().skip(2).find(|a| !a.starts_with('-')),
+        ))
+    } else {
+        None
+    };
+
+    // Load configuration
+    let loaded = config::load(cli.config_path.as_deref(), target_dir.as_deref());
+    let tyr_config = loaded.config.clone();
+
+    // Parse arguments
+    match mode {
+        "config" => run_config_command(&cli.rest[1..], &loaded),
+        "-h" | "--help" => {
+            print_help();
+            process::exit(0);
+        }
+        "undo" => {
+            if let Err(e) = run_undo_command(&tyr_config) {
                 eprintln!("✗ Error: {}", e);
-                log_to_file(&tyr_config.log_file, &format!("Fatal error: {}", e));
+                log_to_file(&tyr_config.log_file, &format!("Undo failed: {}", e));
                 process::exit(1);
             }
         }
-        "-f" | "--filename" => {
-            // Find target directory (skip -nui flag if present)
-            let target_dir = if args.len() > 2 {
-                let mut path_arg = None;
-                for (i, arg) in args.iter().enumerate() {
-                    if i > 1 && arg != "-nui" && arg != "--no-ui" {
-                        path_arg = Some(arg);
-                        break;
-                    }
-                }
-
-                if let Some(path) = path_arg {
-                    PathBuf::from(path)
-                } else {
-                    match env::current_dir() {
-                        Ok(dir) => dir,
-                        Err(e) => {
-                            eprintln!("✗ Error: Could not get current directory: {}", e);
-                            log_to_file(
-                                &tyr_config.log_file,
-                                &format!("Error: Could not get current directory: {}", e),
-                            );
-                            process::exit(1);
-                        }
-                    }
-                }
-            } else {
-                match env::current_dir() {
-                    Ok(dir) => dir,
-                    Err(e) => {
-                        eprintln!("✗ Error: Could not get current directory: {}", e);
-                        log_to_file(
-                            &tyr_config.log_file,
-                            &format!("Error: Could not get current directory: {}", e),
-                        );
-                        process::exit(1);
-                    }
-                }
-            };
-
-            if !target_dir.exists() {
-                eprintln!(
-                    "✗ Error: Directory does not exist: {}",
-                    target_dir.display()
-                );
+        "report" => {
+            ensure_valid_config(&loaded);
+            let args = report_args.expect("report arguments are parsed above");
+            if let Err(e) = run_report_command(args, &tyr_config, cli.no_ui) {
+                eprintln!("✗ Error: {}", e);
+                log_to_file(&tyr_config.log_file, &format!("Report failed: {}", e));
+                process::exit(1);
+            }
+        }
+        "train" => {
+            ensure_valid_config(&loaded);
+            if let Err(e) = run_train_command(&cli.rest[1..], &tyr_config) {
+                eprintln!("✗ Error: {}", e);
+                log_to_file(&tyr_config.log_file, &format!("Training failed: {}", e));
+                process::exit(1);
+            }
+        }
+        "similar" => {
+            ensure_valid_config(&loaded);
+            let target_dir = target_dir.unwrap_or_default();
+            if let Err(e) = run_similar_command(target_dir, &tyr_config, cli.no_ui, &cli.output) {
+                eprintln!("✗ Error: {}", e);
                 log_to_file(
                     &tyr_config.log_file,
-                    &format!("Error: Directory does not exist: {}", target_dir.display()),
+                    &format!("Near-duplicate search failed: {}", e),
                 );
+                process::exit(e.exit_code());
+            }
+        }
+        "feedback" => {
+            if let Err(e) = run_feedback_command(&cli.rest[1..]) {
+                eprintln!("✗ Error: {}", e);
                 process::exit(1);
             }
-
-            if let Err(e) = run_filename_mode(target_dir, &tyr_config, no_ui) {
+        }
+        "trash-list" => {
+            if let Err(e) = run_trash_list_command() {
                 eprintln!("✗ Error: {}", e);
-                log_to_file(&tyr_config.log_file, &format!("Fatal error: {}", e));
                 process::exit(1);
             }
         }
-        "-i" | "--intelligent" => {
-            // Find target directory (skip -nui flag if present)
-            let target_dir = if args.len() > 2 {
-                let mut path_arg = None;
-                for (i, arg) in args.iter().enumerate() {
-                    if i > 1 && arg != "-nui" && arg != "--no-ui" {
-                        path_arg = Some(arg);
-                        break;
-                    }
-                }
-
-                if let Some(path) = path_arg {
-                    PathBuf::from(path)
-                } else {
-                    match env::current_dir() {
-                        Ok(dir) => dir,
-                        Err(e) => {
-                            eprintln!("✗ Error: Could not get current directory: {}", e);
-                            log_to_file(
-                                &tyr_config.log_file,
-                                &format!("Error: Could not get current directory: {}", e),
-                            );
-                            process::exit(1);
-                        }
-                    }
-                }
-            } else {
-                match env::current_dir() {
-                    Ok(dir) => dir,
-                    Err(e) => {
-                        eprintln!("✗ Error: Could not get current directory: {}", e);
-                        log_to_file(
-                            &tyr_config.log_file,
-                            &format!("Error: Could not get current directory: {}", e),
-                        );
-                        process::exit(1);
-                    }
-                }
-            };
+        "restore" => {
+            if let Err(e) = run_restore_command(&cli.rest[1..], &tyr_config) {
+                eprintln!("✗ Error: {}", e);
+                log_to_file(&tyr_config.log_file, &format!("Restore failed: {}", e));
+                process::exit(1);
+            }
+        }
+        _ if is_organize_mode || is_pipeline || is_classify => {
+            ensure_valid_config(&loaded);
 
+            let target_dir = target_dir.unwrap_or_default();
             if !target_dir.exists() {
                 eprintln!(
                     "✗ Error: Directory does not exist: {}",
@@ -900,21 +1318,54 @@ fn main() {
                 );
                 process::exit(1);
             }
+            if let Some(dest) = &cli.output.dest_root {
+                if dest.exists() && !dest.is_dir() {
+                    eprintln!("✗ Error: --dest is not a directory: {}", dest.display());
+                    process::exit(1);
+                }
+            }
+
+            // Absolute paths, so the undo journal works from any directory
+            let target_dir = target_dir.canonicalize().unwrap_or(target_dir);
+            let mut output = cli.output.clone();
+            output.hooks = tyr_config.hooks.clone();
+            output.dest_root = output.dest_root.map(|dest| {
+                dest.canonicalize()
+                    .or_else(|_| std::path::absolute(&dest))
+                    .unwrap_or(dest)
+            });
+            if cli.explain && !matches!(mode, "-f" | "--filename" | "-i" | "--intelligent") {
+                println!("!  --explain only applies to -f and -i");
+            }
+            let result: tyr::Result<()> = match mode {
+                "-r" | "--rename" => run_rename_mode(target_dir, &tyr_config, cli.no_ui, &output),
+                "classify" => run_classify_mode(target_dir, &tyr_config, cli.no_ui, output),
+                _ if is_organize_mode => organizer_for(mode, &tyr_config).and_then(|organizer| {
+                    run_organizer_mode(
+                        organizer,
+                        target_dir,
+                        &tyr_config,
+                        cli.no_ui,
+                        cli.explain,
+                        output,
+                    )
+                }),
+                _ => run_pipeline_mode(
+                    target_dir,
+                    &tyr_config,
+                    cli.no_ui,
+                    output,
+                    load_feedback(&tyr_config),
+                ),
+            };
 
-            if let Err(e) = run_intelligent_mode(target_dir, &tyr_config, no_ui) {
+            // 1 when nothing could be done, 3 when only some files failed
+            if let Err(e) = result {
                 eprintln!("✗ Error: {}", e);
-                log_to_file(&tyr_config.log_file, &format!("Fatal error: {}", e));
-                process::exit(1);
+                log_to_file(&tyr_config.log_file, &format!("Run failed: {}", e));
+                process::exit(e.exit_code());
             }
         }
-        "-nui" | "--no-ui" => {
-            eprintln!("✗ Error: -nui flag must be used with -c, -f, or -i mode");
-            eprintln!("\nExamples:");
-            eprintln!("  tyr -c -nui /path/to/folder");
-            eprintln!("  tyr -f -nui /path/to/folder");
-            eprintln!("  tyr -i -nui /path/to/folder");
-            process::exit(1);
-        }
         "-v" | "--version" => {
             println!("0.4.5");
             println!(
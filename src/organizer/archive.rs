@@ -0,0 +1,412 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for looking inside and extracting archives (zip, tar, tar.gz, tar.xz) for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use super::naming;
+
+/// Archive settings (`[archive_config]`)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ArchiveConfig {
+    /// Categorise an archive by what it holds instead of its extension
+    pub inspect_contents: bool,
+    /// Share of the entries one category needs before it decides for the archive
+    pub min_content_share: f64,
+    /// Extract archives into a sibling folder and organize what comes out
+    pub auto_extract: bool,
+    /// What happens to an archive whose sibling folder already holds its contents
+    pub extracted_action: ExtractedAction,
+    /// Archives (and extracted contents) bigger than this are left alone
+    pub max_size_mb: u64,
+}
+
+impl Default for ArchiveConfig {
+    fn default() -> Self {
+        Self {
+            inspect_contents: false,
+            min_content_share: 0.6,
+            auto_extract: false,
+            extracted_action: ExtractedAction::Keep,
+            max_size_mb: 512,
+        }
+    }
+}
+
+impl ArchiveConfig {
+    pub fn max_bytes(&self) -> u64 {
+        self.max_size_mb.saturating_mul(1024 * 1024)
+    }
+}
+
+/// What to do with an archive that was already extracted next to itself
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExtractedAction {
+    /// Organize it like any other file
+    #[default]
+    Keep,
+    /// Send it to the desktop trash
+    Trash,
+}
+
+/// Archive formats tyr can read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    Zip,
+    Tar,
+    TarGz,
+    TarXz,
+}
+
+impl ArchiveKind {
+    /// Recognises an archive by its (possibly compound) extension
+    pub fn from_path(path: &Path) -> Option<(ArchiveKind, String)> {
+        let name = path.file_name()?.to_str()?;
+        naming::extension_candidates(name)
+            .into_iter()
+            .find_map(|ext| {
+                let kind = match ext.as_str() {
+                    "zip" => ArchiveKind::Zip,
+                    "tar" => ArchiveKind::Tar,
+                    "tar.gz" | "tgz" => ArchiveKind::TarGz,
+                    "tar.xz" | "txz" => ArchiveKind::TarXz,
+                    _ => return None,
+                };
+                Some((kind, ext))
+            })
+    }
+}
+
+/// A file inside an archive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveEntry {
+    /// Relative path inside the archive
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+/// Lists the files of an archive; folders and entries that would land outside the
+/// extraction folder (absolute paths, `..`) are left out
+///
+/// `max_bytes` bounds how much an `.xz` stream may decompress to while listing.
+pub fn list_entries(path: &Path, max_bytes: u64) -> io::Result<Vec<ArchiveEntry>> {
+    let Some((kind, _)) = ArchiveKind::from_path(path) else {
+        return Err(unsupported(path));
+    };
+
+    let mut entries = Vec::new();
+    match kind {
+        ArchiveKind::Zip => {
+            let mut archive =
+                zip::ZipArchive::new(BufReader::new(File::open(path)?)).map_err(zip_error)?;
+            for i in 0..archive.len() {
+                let file = archive.by_index(i).map_err(zip_error)?;
+                if !file.is_file() {
+                    continue;
+                }
+                if let Some(name) = file.enclosed_name() {
+                    entries.push(ArchiveEntry {
+                        path: name,
+                        size: file.size(),
+                    });
+                }
+            }
+        }
+        _ => {
+            let mut archive = tar::Archive::new(tar_reader(path, kind, max_bytes)?);
+            for entry in archive.entries()? {
+                let entry = entry?;
+                if !entry.header().entry_type().is_file() {
+                    continue;
+                }
+                let name = entry.path()?.into_owned();
+                if is_enclosed(&name) {
+                    entries.push(ArchiveEntry {
+                        path: name,
+                        size: entry.size(),
+                    });
+                }
+            }
+        }
+    }
+    Ok(entries)
+}
+
+/// Extracts `path` into `into`, returning the files that were written
+///
+/// Refuses archives whose contents add up to more than `max_bytes`, so a zip bomb cannot
+/// fill the disk. Zip entries can lie about their size, so they are also counted as they
+/// are written and whatever was extracted is removed again once the limit is crossed.
+pub fn extract(path: &Path, into: &Path, max_bytes: u64) -> io::Result<Vec<PathBuf>> {
+    let Some((kind, _)) = ArchiveKind::from_path(path) else {
+        return Err(unsupported(path));
+    };
+
+    let entries = list_entries(path, max_bytes)?;
+    let total: u64 = entries.iter().map(|e| e.size).sum();
+    if total > max_bytes {
+        return Err(io::Error::other(format!(
+            "{} unpacks to {} MB, more than the {} MB limit",
+            path.display(),
+            total / (1024 * 1024),
+            max_bytes / (1024 * 1024)
+        )));
+    }
+
+    fs::create_dir_all(into)?;
+    match kind {
+        ArchiveKind::Zip => {
+            let mut written = Vec::new();
+            if let Err(e) = extract_zip(path, into, max_bytes, &mut written) {
+                for file in written {
+                    let _ = fs::remove_file(file);
+                }
+                return Err(e);
+            }
+        }
+        // `unpack` skips entries with `..` and absolute paths itself; tar headers can't
+        // under-report, the reader stops at the declared size
+        _ => tar::Archive::new(tar_reader(path, kind, max_bytes)?).unpack(into)?,
+    }
+
+    Ok(entries
+        .into_iter()
+        .map(|entry| into.join(entry.path))
+        .filter(|path| path.is_file())
+        .collect())
+}
+
+/// Writes the files of a zip one at a time, stopping once more than `max_bytes` came out
+fn extract_zip(
+    path: &Path,
+    into: &Path,
+    max_bytes: u64,
+    written: &mut Vec<PathBuf>,
+) -> io::Result<()> {
+    let mut archive = zip::ZipArchive::new(BufReader::new(File::open(path)?)).map_err(zip_error)?;
+    let mut left = max_bytes;
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i).map_err(zip_error)?;
+        let Some(name) = file.enclosed_name() else {
+            continue;
+        };
+        let target = into.join(name);
+        if file.is_dir() {
+            fs::create_dir_all(&target)?;
+            continue;
+        }
+        if !file.is_file() {
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut output = File::create(&target)?;
+        written.push(target.clone());
+        let copied = io::copy(&mut (&mut file).take(left.saturating_add(1)), &mut output)?;
+        if copied > left {
+            return Err(io::Error::other(format!(
+                "{} unpacks to more than the {} MB limit",
+                path.display(),
+                max_bytes / (1024 * 1024)
+            )));
+        }
+        left -= copied;
+
+        #[cfg(unix)]
+        if let Some(mode) = file.unix_mode() {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&target, fs::Permissions::from_mode(mode & 0o7777))?;
+        }
+    }
+    Ok(())
+}
+
+/// Folder an archive extracts into: next to it, named after it (`photos.tar.gz` → `photos`)
+pub fn extraction_dir(path: &Path) -> Option<PathBuf> {
+    let (_, ext) = ArchiveKind::from_path(path)?;
+    let name = path.file_name()?.to_str()?;
+    let stem = &name[..name.len() - ext.len() - 1];
+    Some(path.with_file_name(stem))
+}
+
+/// True when the archive's sibling folder already holds every entry at the same size
+pub fn is_extracted(path: &Path, entries: &[ArchiveEntry]) -> bool {
+    let Some(dir) = extraction_dir(path).filter(|dir| dir.is_dir()) else {
+        return false;
+    };
+    !entries.is_empty()
+        && entries.iter().all(|entry| {
+            fs::metadata(dir.join(&entry.path)).is_ok_and(|m| m.is_file() && m.len() == entry.size)
+        })
+}
+
+/// Decompressed tar stream of a tar, tar.gz or tar.xz file
+fn tar_reader(path: &Path, kind: ArchiveKind, max_bytes: u64) -> io::Result<Box<dyn Read>> {
+    let file = BufReader::new(File::open(path)?);
+    Ok(match kind {
+        ArchiveKind::TarGz => Box::new(flate2::read::GzDecoder::new(file)),
+        // lzma-rs only decompresses into a writer, so the stream is buffered (bounded)
+        ArchiveKind::TarXz => {
+            let mut output = LimitedWriter {
+                buffer: Vec::new(),
+                limit: max_bytes,
+            };
+            lzma_rs::xz_decompress(&mut { file }, &mut output).map_err(|e| match e {
+                lzma_rs::error::Error::IoError(e) => e,
+                e => io::Error::new(io::ErrorKind::InvalidData, e.to_string()),
+            })?;
+            Box::new(io::Cursor::new(output.buffer))
+        }
+        _ => Box::new(file),
+    })
+}
+
+/// In-memory writer that fails once more than `limit` bytes were written
+struct LimitedWriter {
+    buffer: Vec<u8>,
+    limit: u64,
+}
+
+impl Write for LimitedWriter {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        if (self.buffer.len() + data.len()) as u64 > self.limit {
+            return Err(io::Error::other("archive is larger than max_size_mb"));
+        }
+        self.buffer.extend_from_slice(data);
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn is_enclosed(path: &Path) -> bool {
+    path.components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+fn unsupported(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("{} is not a supported archive", path.display()),
+    )
+}
+
+fn zip_error(e: zip::result::ZipError) -> io::Error {
+    match e {
+        zip::result::ZipError::Io(e) => e,
+        e => io::Error::new(io::ErrorKind::InvalidData, e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_zip(path: &Path, files: &[(&str, &str)]) {
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        for (name, content) in files {
+            zip.start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn test_extract_zip_and_detect_extraction() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("papers.zip");
+        write_zip(&archive, &[("a.pdf", "one"), ("sub/b.pdf", "two")]);
+
+        let entries = list_entries(&archive, u64::MAX).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(!is_extracted(&archive, &entries));
+
+        let into = extraction_dir(&archive).unwrap();
+        assert_eq!(into, dir.path().join("papers"));
+        let files = extract(&archive, &into, u64::MAX).unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(fs::read_to_string(into.join("sub/b.pdf")).unwrap(), "two");
+        assert!(is_extracted(&archive, &entries));
+
+        // Over the limit nothing is written
+        assert!(extract(&archive, &dir.path().join("again"), 4).is_err());
+        assert!(!dir.path().join("again").exists());
+    }
+
+    #[test]
+    fn test_zip_that_under_reports_its_size_is_stopped() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("bomb.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&archive).unwrap());
+        zip.start_file(
+            "zeros.bin",
+            zip::write::SimpleFileOptions::default()
+                .compression_method(zip::CompressionMethod::Deflated),
+        )
+        .unwrap();
+        zip.write_all(&[0; 64 * 1024]).unwrap();
+        zip.finish().unwrap();
+
+        // Claim one byte in both the local and the central header
+        let mut bytes = fs::read(&archive).unwrap();
+        let local = 22;
+        let central = bytes
+            .windows(4)
+            .position(|w| w == [0x50, 0x4b, 0x01, 0x02])
+            .unwrap()
+            + 24;
+        for offset in [local, central] {
+            bytes[offset..offset + 4].copy_from_slice(&1u32.to_le_bytes());
+        }
+        fs::write(&archive, bytes).unwrap();
+        assert_eq!(list_entries(&archive, u64::MAX).unwrap()[0].size, 1);
+
+        let into = dir.path().join("bomb");
+        assert!(extract(&archive, &into, 1024).is_err());
+        assert!(!into.join("zeros.bin").exists());
+    }
+
+    #[test]
+    fn test_list_tar_gz() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("backup.tar.gz");
+        let encoder = flate2::write::GzEncoder::new(
+            File::create(&archive).unwrap(),
+            flate2::Compression::default(),
+        );
+        let mut builder = tar::Builder::new(encoder);
+        let mut header = tar::Header::new_gnu();
+        header.set_size(5);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "notes/readme.txt", &b"hello"[..])
+            .unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let entries = list_entries(&archive, u64::MAX).unwrap();
+        assert_eq!(
+            entries,
+            vec![ArchiveEntry {
+                path: PathBuf::from("notes/readme.txt"),
+                size: 5
+            }]
+        );
+        assert_eq!(extraction_dir(&archive).unwrap(), dir.path().join("backup"));
+    }
+}
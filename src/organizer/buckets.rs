@@ -0,0 +1,347 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for organizing files into size or age buckets (Tiny/Huge, This week/Older) for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, Metadata};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration as StdDuration, SystemTime};
+
+use super::strategy::{Assignment, Assignments, ConfigSchema, Organizer, Setting, Target};
+use super::template;
+
+const MB: f64 = 1024.0 * 1024.0;
+const DAY: u64 = 24 * 60 * 60;
+
+/// Size and age buckets (`[bucket_config]`)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct BucketConfig {
+    /// Smallest first; the last bucket takes everything bigger
+    pub size_buckets: Vec<SizeBucket>,
+    /// Newest first; the last bucket takes everything older
+    pub age_buckets: Vec<AgeBucket>,
+    /// Which timestamp the age is measured from
+    pub age_from: AgeSource,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SizeBucket {
+    pub name: String,
+    /// Upper limit in MB, inclusive
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_mb: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AgeBucket {
+    pub name: String,
+    /// Upper limit in days, inclusive
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_days: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AgeSource {
+    #[default]
+    Modified,
+    Accessed,
+}
+
+impl Default for BucketConfig {
+    fn default() -> Self {
+        let size = |name: &str, max_mb: Option<f64>| SizeBucket {
+            name: name.to_string(),
+            max_mb,
+        };
+        let age = |name: &str, max_days: Option<u64>| AgeBucket {
+            name: name.to_string(),
+            max_days,
+        };
+
+        Self {
+            size_buckets: vec![
+                size("Tiny", Some(0.1)),
+                size("Small", Some(10.0)),
+                size("Large", Some(1024.0)),
+                size("Huge", None),
+            ],
+            age_buckets: vec![
+                age("This week", Some(7)),
+                age("This month", Some(30)),
+                age("This year", Some(365)),
+                age("Older than a year", None),
+            ],
+            age_from: AgeSource::Modified,
+        }
+    }
+}
+
+impl BucketConfig {
+    /// Problems with the bucket lists, as messages for `tyr config check`
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        let lists = [
+            (
+                "size_buckets",
+                self.size_buckets
+                    .iter()
+                    .map(|b| (b.name.as_str(), b.max_mb))
+                    .collect::<Vec<_>>(),
+            ),
+            (
+                "age_buckets",
+                self.age_buckets
+                    .iter()
+                    .map(|b| (b.name.as_str(), b.max_days.map(|d| d as f64)))
+                    .collect(),
+            ),
+        ];
+
+        for (key, buckets) in lists {
+            if buckets.is_empty() {
+                problems.push(format!("{} must list at least one bucket", key));
+                continue;
+            }
+            let mut seen = HashSet::new();
+            for (name, _) in &buckets {
+                if matches!(name.trim(), "" | ".")
+                    || name.contains(['/', '\\'])
+                    || !template::stays_inside(Path::new(name))
+                {
+                    problems.push(format!("{}: '{}' is not a usable folder name", key, name));
+                } else if !seen.insert(*name) {
+                    problems.push(format!("{}: '{}' is listed twice", key, name));
+                }
+            }
+            // Only the last bucket may leave its limit out, and limits have to grow
+            let limits = &buckets[..buckets.len() - 1];
+            if let Some((name, _)) = limits.iter().find(|(_, limit)| limit.is_none()) {
+                problems.push(format!(
+                    "{}: '{}' needs a limit, only the last bucket may leave it out",
+                    key, name
+                ));
+            }
+            let limits: Vec<f64> = buckets.iter().filter_map(|(_, limit)| *limit).collect();
+            if limits.windows(2).any(|pair| pair[0] >= pair[1]) {
+                problems.push(format!(
+                    "{}: limits must increase from bucket to bucket",
+                    key
+                ));
+            }
+        }
+        problems
+    }
+}
+
+/// What the buckets are worked out from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BucketKind {
+    Size,
+    Age,
+}
+
+pub struct BucketOrganizer {
+    config: BucketConfig,
+    kind: BucketKind,
+}
+
+impl BucketOrganizer {
+    pub fn new(config: BucketConfig, kind: BucketKind) -> Self {
+        Self { config, kind }
+    }
+
+    /// Bucket names in order
+    pub fn bucket_names(&self) -> Vec<&str> {
+        match self.kind {
+            BucketKind::Size => self.config.size_buckets.iter().map(|b| &*b.name).collect(),
+            BucketKind::Age => self.config.age_buckets.iter().map(|b| &*b.name).collect(),
+        }
+    }
+
+    /// Bucket a file belongs in; everything past the last limit goes to the last bucket
+    pub fn bucket_for(&self, metadata: &Metadata, now: SystemTime) -> &str {
+        match self.kind {
+            BucketKind::Size => {
+                let mb = metadata.len() as f64 / MB;
+                let buckets = &self.config.size_buckets;
+                buckets
+                    .iter()
+                    .find(|b| b.max_mb.is_some_and(|max| mb <= max))
+                    .or(buckets.last())
+                    .map_or("Other", |b| &b.name)
+            }
+            BucketKind::Age => {
+                let timestamp = match self.config.age_from {
+                    AgeSource::Modified => metadata.modified(),
+                    AgeSource::Accessed => metadata.accessed(),
+                };
+                // Timestamps in the future count as brand new
+                let days = timestamp
+                    .ok()
+                    .and_then(|t| now.duration_since(t).ok())
+                    .unwrap_or(StdDuration::ZERO)
+                    .as_secs()
+                    / DAY;
+                let buckets = &self.config.age_buckets;
+                buckets
+                    .iter()
+                    .find(|b| b.max_days.is_some_and(|max| days <= max))
+                    .or(buckets.last())
+                    .map_or("Other", |b| &b.name)
+            }
+        }
+    }
+}
+
+impl Organizer for BucketOrganizer {
+    fn name(&self) -> &'static str {
+        match self.kind {
+            BucketKind::Size => "Size buckets",
+            BucketKind::Age => "Age buckets",
+        }
+    }
+
+    /// The buckets in order, with their limits
+    fn config_schema(&self) -> ConfigSchema {
+        let mut settings: Vec<Setting> = match self.kind {
+            BucketKind::Size => self
+                .config
+                .size_buckets
+                .iter()
+                .map(|b| Setting {
+                    key: b.name.clone(),
+                    value: b
+                        .max_mb
+                        .map_or("bigger".to_string(), |mb| format!("≤ {} MB", mb)),
+                })
+                .collect(),
+            BucketKind::Age => self
+                .config
+                .age_buckets
+                .iter()
+                .map(|b| Setting {
+                    key: b.name.clone(),
+                    value: b
+                        .max_days
+                        .map_or("older".to_string(), |days| format!("≤ {} days", days)),
+                })
+                .collect(),
+        };
+        if self.kind == BucketKind::Age {
+            settings.push(Setting {
+                key: "age_from".to_string(),
+                value: format!("{:?}", self.config.age_from).to_lowercase(),
+            });
+        }
+        ConfigSchema {
+            section: "bucket_config",
+            settings,
+        }
+    }
+
+    fn analyze(&self, files: &[PathBuf]) -> io::Result<Assignments> {
+        let now = SystemTime::now();
+        let mut assignments = Assignments::new();
+        for file in files {
+            // Gone since it was listed
+            let Ok(metadata) = fs::metadata(file) else {
+                continue;
+            };
+            assignments.push(Assignment {
+                file: file.clone(),
+                target: Target::Folder(self.bucket_for(&metadata, now).to_string()),
+            });
+        }
+        assignments.sort();
+        Ok(assignments)
+    }
+}
+
+/// Human readable size for reports, e.g. "3.2 MB"
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::organizer::transfer::Output;
+    use crate::organizer::{pipeline, strategy};
+    use std::fs::File;
+
+    #[test]
+    fn test_size_buckets() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        fs::write(base.join("note.txt"), "hi").unwrap();
+        fs::write(base.join("photo.jpg"), vec![0u8; 200 * 1024]).unwrap();
+
+        let organizer = BucketOrganizer::new(BucketConfig::default(), BucketKind::Size);
+
+        let plan = strategy::plan_directory(&organizer, base, &[], &Output::default()).unwrap();
+        let result = pipeline::execute_moves(&plan);
+        assert_eq!(result.files_moved, 2);
+        assert!(base.join("Tiny/note.txt").exists());
+        assert!(base.join("Small/photo.jpg").exists());
+    }
+
+    #[test]
+    fn test_bucket_names_must_be_single_unique_folders() {
+        let mut config = BucketConfig::default();
+        config.size_buckets[0].name = "..".to_string();
+        config.size_buckets[1].name = ".".to_string();
+        config.age_buckets[1].name = "This week".to_string();
+
+        assert_eq!(
+            config.validate(),
+            vec![
+                "size_buckets: '..' is not a usable folder name",
+                "size_buckets: '.' is not a usable folder name",
+                "age_buckets: 'This week' is listed twice",
+            ]
+        );
+        assert!(BucketConfig::default().validate().is_empty());
+    }
+
+    #[test]
+    fn test_age_bucket_uses_modification_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let old = dir.path().join("old.log");
+        fs::write(&old, "x").unwrap();
+        let now = SystemTime::now();
+        File::options()
+            .write(true)
+            .open(&old)
+            .unwrap()
+            .set_modified(now - StdDuration::from_secs(400 * DAY))
+            .unwrap();
+
+        let organizer = BucketOrganizer::new(BucketConfig::default(), BucketKind::Age);
+        let metadata = fs::metadata(&old).unwrap();
+        assert_eq!(organizer.bucket_for(&metadata, now), "Older than a year");
+        assert_eq!(
+            organizer.bucket_for(&metadata, now - StdDuration::from_secs(398 * DAY)),
+            "This week"
+        );
+    }
+}
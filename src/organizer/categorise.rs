@@ -13,7 +13,18 @@ use std::collections::HashMap;
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex};
+use std::sync::Mutex;
+
+use super::archive::{self, ArchiveConfig, ArchiveKind};
+use super::naming;
+use super::plugin::{Consult, Plugin, PluginConfig, Request};
+use super::sniff;
+use super::strategy::{self, Assignment, Assignments, ConfigSchema, Organizer, Setting, Target};
+use super::template::{self, TemplateContext};
+use crate::error::{FileFailure, TyrError};
+
+/// Plugin failures in one run after which it is no longer restarted
+const PLUGIN_MAX_FAILURES: usize = 3;
 
 // Configuration Structures
 
@@ -27,14 +38,50 @@ pub struct FileOrganizerConfig {
 
     #[serde(default)]
     pub skip_patterns: Vec<String>,
+
+    /// Categorise by sniffed content even when the extension is known
+    #[serde(default)]
+    pub prefer_sniffed_type: bool,
+
+    #[serde(default)]
+    pub archives: ArchiveConfig,
+
+    /// External classifier asked before or after the categories
+    #[serde(default)]
+    pub plugin: PluginConfig,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct CategoryConfig {
+    #[serde(default)]
     pub extensions: Vec<String>,
 
     #[serde(default)]
     pub folder_name: Option<String>,
+
+    /// Destination template such as `Media/Images/{ext}`; overrides `folder_name`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destination: Option<String>,
+
+    #[serde(default, skip_serializing_if = "CategoryAction::is_move")]
+    pub action: CategoryAction,
+}
+
+/// What happens to the files of a category
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CategoryAction {
+    /// Into the category folder
+    #[default]
+    Move,
+    /// Into the desktop trash (freedesktop.org spec), restorable with `tyr restore`
+    Trash,
+}
+
+impl CategoryAction {
+    fn is_move(&self) -> bool {
+        *self == CategoryAction::Move
+    }
 }
 
 fn default_batch_size() -> usize {
@@ -52,106 +99,142 @@ impl Default for FileOrganizerConfig {
                 ".git".to_string(),
                 ".gitignore".to_string(),
                 "desktop.ini".to_string(),
+                ".localized".to_string(),
+                "~$*".to_string(),
             ],
+            prefer_sniffed_type: false,
+            archives: ArchiveConfig::default(),
+            plugin: PluginConfig::default(),
         }
     }
 }
 
-fn create_default_categories() -> HashMap<String, CategoryConfig> {
-    let mut map = HashMap::new();
-
-    map.insert(
-        "images".to_string(),
-        CategoryConfig {
-            extensions: vec![
-                "jpg", "jpeg", "png", "gif", "bmp", "svg", "webp", "tiff", "ico", "heic", "raw",
-            ]
-            .into_iter()
-            .map(String::from)
-            .collect(),
-            folder_name: Some("Images".to_string()),
-        },
-    );
-
-    map.insert(
-        "videos".to_string(),
-        CategoryConfig {
-            extensions: vec![
-                "mp4", "avi", "mkv", "mov", "wmv", "flv", "webm", "m4v", "3gp", "mpg", "mpeg",
-            ]
-            .into_iter()
-            .map(String::from)
-            .collect(),
-            folder_name: Some("Videos".to_string()),
-        },
-    );
-
-    map.insert(
-        "audio".to_string(),
-        CategoryConfig {
-            extensions: vec![
-                "mp3", "wav", "flac", "aac", "ogg", "wma", "m4a", "opus", "aiff",
-            ]
-            .into_iter()
-            .map(String::from)
-            .collect(),
-            folder_name: Some("Audio".to_string()),
-        },
-    );
-
-    map.insert(
-        "documents".to_string(),
-        CategoryConfig {
-            extensions: vec![
-                "pdf", "doc", "docx", "txt", "rtf", "odt", "pages", "tex", "md", "epub",
-            ]
-            .into_iter()
-            .map(String::from)
-            .collect(),
-            folder_name: Some("Documents".to_string()),
-        },
-    );
-
-    map.insert(
-        "code".to_string(),
-        CategoryConfig {
-            extensions: vec![
-                "rs", "py", "js", "ts", "html", "css", "cpp", "c", "h", "java", "go",
-            ]
-            .into_iter()
-            .map(String::from)
-            .collect(),
-            folder_name: Some("Code".to_string()),
-        },
-    );
-
-    map
+/// Built-in categories, kept in sync with the template written by `config::write_default_config`
+pub(crate) fn create_default_categories() -> HashMap<String, CategoryConfig> {
+    let category = |extensions: &[&str], folder_name: &str| CategoryConfig {
+        extensions: extensions.iter().map(|e| e.to_string()).collect(),
+        folder_name: Some(folder_name.to_string()),
+        destination: None,
+        action: CategoryAction::Move,
+    };
+
+    HashMap::from([
+        (
+            "images".to_string(),
+            category(
+                &[
+                    "jpg", "jpeg", "png", "gif", "bmp", "svg", "webp", "tiff", "ico", "heic",
+                    "avif", "raw", "cr2", "nef", "orf", "sr2",
+                ],
+                "Images",
+            ),
+        ),
+        (
+            "videos".to_string(),
+            category(
+                &[
+                    "mp4", "avi", "mkv", "mov", "wmv", "flv", "webm", "m4v", "3gp", "mpg", "mpeg",
+                    "vob",
+                ],
+                "Videos",
+            ),
+        ),
+        (
+            "audio".to_string(),
+            category(
+                &[
+                    "mp3", "wav", "flac", "aac", "ogg", "wma", "m4a", "opus", "aiff", "ape", "alac",
+                ],
+                "Audio",
+            ),
+        ),
+        (
+            "documents".to_string(),
+            category(
+                &[
+                    "pdf", "doc", "docx", "txt", "rtf", "odt", "pages", "tex", "md",
+                ],
+                "Documents",
+            ),
+        ),
+        (
+            "spreadsheets".to_string(),
+            category(&["xls", "xlsx", "csv", "ods", "numbers"], "Spreadsheets"),
+        ),
+        (
+            "presentations".to_string(),
+            category(&["ppt", "pptx", "odp", "key"], "Presentations"),
+        ),
+        (
+            "archives".to_string(),
+            category(
+                &[
+                    "zip", "rar", "7z", "tar", "gz", "bz2", "xz", "zst", "iso", "tar.gz",
+                    "tar.bz2", "tar.xz", "tar.zst", "tgz",
+                ],
+                "Archives",
+            ),
+        ),
+        (
+            "code".to_string(),
+            category(
+                &[
+                    "rs", "py", "js", "ts", "jsx", "tsx", "html", "css", "scss", "sass", "cpp",
+                    "c", "h", "hpp", "java", "go", "php", "rb", "swift", "kt", "dart", "scala",
+                    "sh", "bat", "ps1", "r", "lua", "vim",
+                ],
+                "Code",
+            ),
+        ),
+        (
+            "data".to_string(),
+            category(
+                &[
+                    "json", "xml", "yaml", "yml", "toml", "ini", "cfg", "conf", "sql", "db",
+                    "sqlite", "mdb",
+                ],
+                "Data",
+            ),
+        ),
+        (
+            "executables".to_string(),
+            category(
+                &[
+                    "exe", "msi", "app", "deb", "rpm", "dmg", "pkg", "appimage", "run", "elf",
+                ],
+                "Applications",
+            ),
+        ),
+        (
+            "fonts".to_string(),
+            category(&["ttf", "otf", "woff", "woff2", "eot"], "Fonts"),
+        ),
+        (
+            "ebooks".to_string(),
+            category(&["epub", "mobi", "azw", "azw3", "cbr", "cbz"], "Ebooks"),
+        ),
+        (
+            "3d_models".to_string(),
+            category(
+                &[
+                    "obj", "fbx", "stl", "blend", "dae", "3ds", "max", "gltf", "glb",
+                ],
+                "3D Models",
+            ),
+        ),
+        (
+            "design".to_string(),
+            category(
+                &["psd", "ai", "xd", "sketch", "fig", "indd", "cdr"],
+                "Design Files",
+            ),
+        ),
+    ])
 }
 
-// Config Loading
-
 impl FileOrganizerConfig {
-    pub fn load_from_file(path: &Path) -> io::Result<Self> {
-        let content = fs::read_to_string(path)?;
-        toml::from_str(&content).map_err(|e| {
-            io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!("TOML parse error: {}", e),
-            )
-        })
-    }
-
-    pub fn save_to_file(&self, path: &Path) -> io::Result<()> {
-        let content = toml::to_string_pretty(self).map_err(|e| {
-            io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!("TOML serialize error: {}", e),
-            )
-        })?;
-        fs::write(path, content)
-    }
-
     /// Build reverse lookup map: extension -> (category_key, folder_name)
+    /// Compound extensions are plain keys (`tar.gz`); see `FileOrganizer::placement_for`
     pub fn build_extension_map(&self) -> HashMap<String, (String, String)> {
         let mut ext_map = HashMap::new();
 
@@ -164,7 +247,7 @@ impl FileOrganizerConfig {
 
             for ext in &config.extensions {
                 ext_map.insert(
-                    ext.to_lowercase(),
+                    ext.trim_start_matches('.').to_lowercase(),
                     (category_key.clone(), folder_name.clone()),
                 );
             }
@@ -172,634 +255,264 @@ impl FileOrganizerConfig {
 
         ext_map
     }
-}
-
-// Lazy Directory Manager (Reduces syscalls)
 
-pub struct LazyDirManager {
-    created_dirs: Arc<Mutex<HashMap<PathBuf, bool>>>,
-}
-
-impl LazyDirManager {
-    pub fn new() -> Self {
-        Self {
-            created_dirs: Arc::new(Mutex::new(HashMap::new())),
-        }
+    /// Multi-dot extensions declared by categories (`tar.gz`, `d.ts`, ...)
+    pub fn compound_extensions(&self) -> Vec<String> {
+        self.categories
+            .values()
+            .flat_map(|c| c.extensions.iter())
+            .map(|ext| ext.trim_start_matches('.').to_lowercase())
+            .filter(|ext| ext.contains('.'))
+            .collect()
     }
-
-    /// Only creates directory if not already created in this session
-    pub fn ensure_dir_exists(&self, path: &Path) -> io::Result<()> {
-        let mut cache = self.created_dirs.lock().unwrap();
-
-        if cache.contains_key(path) {
-            return Ok(());
-        }
-
-        // Check if exists before creating
-        if !path.exists() {
-            fs::create_dir_all(path)?;
-        }
-
-        cache.insert(path.to_path_buf(), true);
-        Ok(())
-    }
-}
-
-// Error-Safe Logger
-
-pub struct SafeLogger {
-    log_entries: Arc<Mutex<Vec<LogEntry>>>,
-}
-
-#[derive(Debug, Clone)]
-pub struct LogEntry {
-    pub level: LogLevel,
-    pub message: String,
-    #[allow(dead_code)]
-    pub details: Option<String>,
-}
-
-#[derive(Debug, Clone, PartialEq)]
-pub enum LogLevel {
-    Info,
-    Success,
-    #[allow(dead_code)]
-    Warning,
-    Error,
 }
 
-impl SafeLogger {
-    pub fn new() -> Self {
-        Self {
-            log_entries: Arc::new(Mutex::new(Vec::new())),
-        }
-    }
-
-    pub fn log(&self, level: LogLevel, message: String, details: Option<String>) {
-        if let Ok(mut entries) = self.log_entries.lock() {
-            entries.push(LogEntry {
-                level,
-                message,
-                details,
-            });
-        }
-    }
-
-    pub fn get_logs(&self) -> Vec<LogEntry> {
-        self.log_entries
-            .lock()
-            .map(|e| e.clone())
-            .unwrap_or_default()
-    }
-
-    // pub fn clear(&self) {
-    //     if let Ok(mut entries) = self.log_entries.lock() {
-    //         entries.clear();
-    //     }
-    // }
-}
-
-// Fast File Organizer (with parallelization)
+// File Organizer
 
 pub struct FileOrganizer {
     config: FileOrganizerConfig,
-    dir_manager: LazyDirManager,
-    logger: SafeLogger,
-}
-
-#[derive(Debug)]
-pub struct OrganizeResult {
-    pub files_organized: usize,
-    pub files_skipped: usize,
-    pub files_failed: usize,
-    pub category_counts: HashMap<String, usize>,
+    compound_extensions: Vec<String>,
+    /// Files the plugin failed on in the last `analyze`
+    failures: Mutex<Vec<FileFailure>>,
 }
 
 impl FileOrganizer {
     pub fn new(config: FileOrganizerConfig) -> Self {
         Self {
+            compound_extensions: config.compound_extensions(),
             config,
-            dir_manager: LazyDirManager::new(),
-            logger: SafeLogger::new(),
+            failures: Mutex::default(),
         }
     }
 
-    pub fn get_logger(&self) -> &SafeLogger {
-        &self.logger
+    /// Works out where every file in `base_path` goes without moving anything
+    ///
+    /// Skipped files are left out; the rest is sorted by path.
+    pub fn plan_directory(&self, base_path: &Path) -> io::Result<Assignments> {
+        let files = strategy::list_files(base_path, &self.config.skip_patterns)?;
+        self.analyze(&files)
     }
 
-    pub fn organize_directory(
+    /// Category folder for a file, relative to the target, or the trash for categories
+    /// with `action = "trash"`; `None` when no category claims it
+    ///
+    /// Goes by extension; the content is sniffed when the extension is missing or unknown,
+    /// or always with `prefer_sniffed_type`. Categories with a `destination` template get
+    /// it expanded here, which may give a nested path like `Documents/2024`.
+    fn category_target(
         &self,
-        base_path: &Path,
-        dry_run: bool,
-    ) -> io::Result<OrganizeResult> {
-        let ext_map = self.config.build_extension_map();
-
-        // Collect all file entries
-        let entries: Vec<_> = fs::read_dir(base_path)?
-            .filter_map(|e| e.ok())
-            .filter(|e| e.path().is_file())
-            .collect();
-
-        let category_counts = Arc::new(Mutex::new(HashMap::new()));
-        let files_organized = Arc::new(Mutex::new(0usize));
-        let files_skipped = Arc::new(Mutex::new(0usize));
-        let files_failed = Arc::new(Mutex::new(0usize));
-
-        // Process files in parallel for speed
-        entries.par_iter().for_each(|entry| {
-            let file_path = entry.path();
-            let filename = match file_path.file_name().and_then(|n| n.to_str()) {
-                Some(name) => name,
-                None => {
-                    *files_skipped.lock().unwrap() += 1;
-                    return;
-                }
-            };
-
-            // Skip system files
-            if self.should_skip_file(filename) {
-                self.logger
-                    .log(LogLevel::Info, format!("Skipped: {}", filename), None);
-                *files_skipped.lock().unwrap() += 1;
-                return;
-            }
-
-            // Get extension and category
-            let extension = file_path
-                .extension()
-                .and_then(|ext| ext.to_str())
-                .map(|ext| ext.to_lowercase())
-                .unwrap_or_else(|| "unknown".to_string());
-
-            let (_category_key, folder_name) = ext_map
-                .get(&extension)
-                .cloned()
-                .unwrap_or_else(|| ("extras".to_string(), "Extras".to_string()));
-
-            let target_dir = base_path.join(&folder_name);
-            let target_path = target_dir.join(filename);
-
-            // Handle naming conflicts
-            let final_target = match self.handle_naming_conflict(&target_path) {
-                Ok(path) => path,
-                Err(e) => {
-                    self.logger.log(
-                        LogLevel::Error,
-                        format!("Naming conflict for: {}", filename),
-                        Some(e.to_string()),
-                    );
-                    *files_failed.lock().unwrap() += 1;
-                    return;
-                }
-            };
-
-            if !dry_run {
-                // Lazy create directory
-                if let Err(e) = self.dir_manager.ensure_dir_exists(&target_dir) {
-                    self.logger.log(
-                        LogLevel::Error,
-                        format!("Failed to create dir: {}", folder_name),
-                        Some(e.to_string()),
-                    );
-                    *files_failed.lock().unwrap() += 1;
-                    return;
-                }
-
-                // Move file
-                if let Err(e) = fs::rename(&file_path, &final_target) {
-                    self.logger.log(
-                        LogLevel::Error,
-                        format!("Failed to move: {}", filename),
-                        Some(e.to_string()),
-                    );
-                    *files_failed.lock().unwrap() += 1;
-                    return;
-                }
-            }
-
-            self.logger.log(
-                LogLevel::Success,
-                format!("{} → {}", filename, folder_name),
-                None,
-            );
+        ext_map: &HashMap<String, (String, String)>,
+        file_path: &Path,
+    ) -> Option<Target> {
+        // Longest suffix wins, so `backup.tar.gz` matches `tar.gz` before `gz`
+        let by_extension = file_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| {
+                naming::extension_candidates(name)
+                    .into_iter()
+                    .find_map(|ext| ext_map.get(&ext).map(|category| (ext, category)))
+            });
 
-            *files_organized.lock().unwrap() += 1;
-            *category_counts
-                .lock()
-                .unwrap()
-                .entry(folder_name)
-                .or_insert(0) += 1;
-        });
-
-        // Fix: Extract values before creating the result to avoid borrow issues
-        let organized_count = *files_organized.lock().unwrap();
-        let skipped_count = *files_skipped.lock().unwrap();
-        let failed_count = *files_failed.lock().unwrap();
-        let counts = category_counts.lock().unwrap().clone();
-
-        Ok(OrganizeResult {
-            files_organized: organized_count,
-            files_skipped: skipped_count,
-            files_failed: failed_count,
-            category_counts: counts,
-        })
-    }
+        let by_content = if by_extension.is_none() || self.config.prefer_sniffed_type {
+            sniff::sniff_file(file_path)
+                .ok()
+                .flatten()
+                // Text and bare ZIP are too vague to overrule a known extension
+                // (notes.csv is text, report.docx is a ZIP)
+                .filter(|s| by_extension.is_none() || !(s.is_text() || s.is_container()))
+                .and_then(|s| {
+                    ext_map
+                        .get(s.extension)
+                        .map(|category| (s.extension.to_string(), category))
+                })
+        } else {
+            None
+        };
 
-    fn should_skip_file(&self, filename: &str) -> bool {
-        self.config
-            .skip_patterns
-            .iter()
-            .any(|pattern| filename.contains(pattern))
-    }
+        let (extension, (category_key, folder_name)) = by_content.or(by_extension)?;
+        let (category_key, folder_name) = self
+            .archive_category(ext_map, file_path)
+            .map_or((category_key, folder_name), |(key, folder)| (key, folder));
 
-    fn handle_naming_conflict(&self, target_path: &Path) -> io::Result<PathBuf> {
-        if !target_path.exists() {
-            return Ok(target_path.to_path_buf());
+        let category = self.config.categories.get(category_key);
+        if category.is_some_and(|c| c.action == CategoryAction::Trash) {
+            return Some(Target::Trash);
         }
 
-        let parent = target_path.parent().unwrap();
-        let stem = target_path
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("file");
-        let extension = target_path
-            .extension()
-            .and_then(|e| e.to_str())
-            .map(|e| format!(".{}", e))
-            .unwrap_or_default();
-
-        for i in 1..1000 {
-            let new_name = format!("{}_{}{}", stem, i, extension);
-            let new_path = parent.join(new_name);
-            if !new_path.exists() {
-                return Ok(new_path);
+        let folder = match category.and_then(|c| c.destination.as_deref()) {
+            Some(template) => {
+                let ctx = TemplateContext {
+                    path: file_path,
+                    extension: Some(&extension),
+                };
+                // Templates are validated when the config loads, so this only falls back
+                // for configs built in code
+                template::render(template, &ctx)
+                    .map(|path| path.to_string_lossy().into_owned())
+                    .unwrap_or_else(|_| folder_name.clone())
             }
-        }
-
-        Err(io::Error::new(
-            io::ErrorKind::AlreadyExists,
-            "Could not find available filename after 999 attempts",
-        ))
+            None => folder_name.clone(),
+        };
+        Some(Target::Folder(folder))
     }
-}
 
-// TUI Implementation
-
-use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
-use ratatui::{
-    backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
-    text::{Line, Span},
-    widgets::{Block, Borders, Gauge, List, ListItem, Paragraph},
-    Terminal,
-};
-use std::io::stdout;
-use std::time::Duration;
-
-pub struct TuiApp {
-    organizer: FileOrganizer,
-    base_path: PathBuf,
-    state: AppState,
-}
-
-enum AppState {
-    Ready,
-    Organizing,
-    Complete(OrganizeResult),
-}
-
-impl TuiApp {
-    pub fn new(config: FileOrganizerConfig, base_path: PathBuf) -> Self {
-        Self {
-            organizer: FileOrganizer::new(config),
-            base_path,
-            state: AppState::Ready,
+    /// Lets the `[plugin_config]` classifier overrule or fill in category targets
+    ///
+    /// The plugin is started once and asked file by file, so this part is sequential.
+    /// A file it fails on keeps its category and is recorded as a failure; the plugin
+    /// is restarted for the next file, up to `PLUGIN_MAX_FAILURES` times.
+    fn consult_plugin(&self, targets: &mut [(PathBuf, Option<Target>)]) -> io::Result<()> {
+        let settings = &self.config.plugin;
+        let asked = |target: &Option<Target>| match settings.consult {
+            Consult::Before => true,
+            Consult::After => target.is_none(),
+        };
+        self.failures.lock().unwrap().clear();
+        if !settings.is_enabled() || !targets.iter().any(|(_, target)| asked(target)) {
+            return Ok(());
         }
-    }
-
-    pub fn run(&mut self) -> io::Result<()> {
-        enable_raw_mode()?;
-        let mut stdout = stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-
-        let backend = CrosstermBackend::new(stdout);
-        let mut terminal = Terminal::new(backend)?;
-
-        let result = self.run_app(&mut terminal);
-
-        disable_raw_mode()?;
-        execute!(
-            terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture
-        )?;
-        terminal.show_cursor()?;
 
-        result
-    }
-
-    fn run_app(
-        &mut self,
-        terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
-    ) -> io::Result<()> {
-        loop {
-            terminal.draw(|f| self.draw_ui(f))?;
-
-            if event::poll(Duration::from_millis(100))? {
-                if let Event::Key(key) = event::read()? {
-                    match key.code {
-                        KeyCode::Char('q') | KeyCode::Esc => break,
-                        KeyCode::Char('s') => {
-                            if matches!(self.state, AppState::Ready) {
-                                self.start_organization(false)?;
-                            }
-                        }
-                        KeyCode::Char('d') => {
-                            if matches!(self.state, AppState::Ready) {
-                                self.start_organization(true)?;
-                            }
-                        }
-                        _ => {}
+        let mut plugin = Some(Plugin::spawn(settings)?);
+        let mut failures = Vec::new();
+        for (file, target) in targets.iter_mut().filter(|(_, target)| asked(target)) {
+            let Some(running) = plugin.as_mut() else {
+                break;
+            };
+            match running.ask(&Request::new(file, target.as_ref())) {
+                Ok(Some(answer)) => *target = Some(answer),
+                Ok(None) => {}
+                Err(e) => {
+                    // A failed plugin has been stopped, so the next file needs a new one
+                    plugin = None;
+                    if failures.len() + 1 < PLUGIN_MAX_FAILURES {
+                        plugin = Plugin::spawn(settings).ok();
                     }
+                    let e = match plugin {
+                        Some(_) => e,
+                        None => io::Error::new(
+                            e.kind(),
+                            format!("{}; the remaining files were not asked", e),
+                        ),
+                    };
+                    failures.push(FileFailure::new(file, TyrError::io(file, e)));
                 }
             }
         }
+        *self.failures.lock().unwrap() = failures;
         Ok(())
     }
 
-    fn start_organization(&mut self, dry_run: bool) -> io::Result<()> {
-        self.state = AppState::Organizing;
-        let result = self
-            .organizer
-            .organize_directory(&self.base_path, dry_run)?;
-        self.state = AppState::Complete(result);
-        Ok(())
-    }
-
-    fn draw_ui(&self, f: &mut ratatui::Frame) {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3),
-                Constraint::Min(10),
-                Constraint::Length(5),
-                Constraint::Length(3),
-            ])
-            .split(f.size());
-
-        // Title
-        let title = Paragraph::new(" Tyr - Extension-Based File Organizer")
-            .style(
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            )
-            .block(Block::default().borders(Borders::ALL));
-        f.render_widget(title, chunks[0]);
-
-        // Main content
-        match &self.state {
-            AppState::Ready => self.draw_ready_state(f, chunks[1]),
-            AppState::Organizing => self.draw_organizing_state(f, chunks[1]),
-            AppState::Complete(result) => self.draw_complete_state(f, chunks[1], result),
+    /// Category most of an archive's entries belong to, with `archives.inspect_contents`
+    ///
+    /// Only counts when it holds at least `min_content_share` of the entries, so a zip of
+    /// PDFs goes to Documents while a mixed bag stays with Archives.
+    fn archive_category<'a>(
+        &self,
+        ext_map: &'a HashMap<String, (String, String)>,
+        file_path: &Path,
+    ) -> Option<&'a (String, String)> {
+        let settings = &self.config.archives;
+        if !settings.inspect_contents || ArchiveKind::from_path(file_path).is_none() {
+            return None;
+        }
+        if fs::metadata(file_path).ok()?.len() > settings.max_bytes() {
+            return None;
         }
 
-        // Logs
-        self.draw_logs(f, chunks[2]);
-
-        // Controls
-        self.draw_controls(f, chunks[3]);
-    }
+        let entries = archive::list_entries(file_path, settings.max_bytes()).ok()?;
+        let mut counts: HashMap<&String, (usize, &(String, String))> = HashMap::new();
+        for entry in &entries {
+            let category = entry
+                .path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| {
+                    naming::extension_candidates(name)
+                        .into_iter()
+                        .find_map(|ext| ext_map.get(&ext))
+                });
+            // A zip of junk is still not something to trash unasked
+            if let Some(category) = category.filter(|(key, _)| {
+                self.config
+                    .categories
+                    .get(key)
+                    .is_none_or(|c| c.action == CategoryAction::Move)
+            }) {
+                counts.entry(&category.0).or_insert((0, category)).0 += 1;
+            }
+        }
 
-    fn draw_ready_state(&self, f: &mut ratatui::Frame, area: Rect) {
-        let categories = self.organizer.config.categories.len();
-
-        let text = vec![
-            Line::from(""),
-            Line::from(Span::styled(
-                " Ready to Organize Files",
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            )),
-            Line::from(""),
-            Line::from(vec![
-                Span::raw("Directory: "),
-                Span::styled(
-                    self.base_path.display().to_string(),
-                    Style::default().fg(Color::Yellow),
-                ),
-            ]),
-            Line::from(""),
-            Line::from(vec![Span::styled(
-                "How it works:",
-                Style::default().fg(Color::Cyan),
-            )]),
-            Line::from("  • Organizes files by extension into folders"),
-            Line::from(format!(
-                "  • {} categories configured (Images, Videos, Documents, etc.)",
-                categories
-            )),
-            Line::from("  • Creates folders only when needed"),
-            Line::from("  • Handles naming conflicts automatically"),
-            Line::from(""),
-            Line::from(Span::styled(
-                " Press 's' to start organizing",
-                Style::default().fg(Color::Green),
-            )),
-            Line::from(Span::styled(
-                " Press 'd' for dry run (preview only)",
-                Style::default().fg(Color::Yellow),
-            )),
-        ];
-
-        let widget =
-            Paragraph::new(text).block(Block::default().borders(Borders::ALL).title(" Status "));
-        f.render_widget(widget, area);
+        let (count, category) = counts
+            .into_values()
+            .max_by(|a, b| a.0.cmp(&b.0).then_with(|| b.1 .0.cmp(&a.1 .0)))?;
+        (count as f64 / entries.len() as f64 >= settings.min_content_share).then_some(category)
     }
+}
 
-    fn draw_organizing_state(&self, f: &mut ratatui::Frame, area: Rect) {
-        let gauge = Gauge::default()
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title(" Organizing Files "),
-            )
-            .gauge_style(Style::default().fg(Color::Cyan))
-            .label(" Sorting files by extension...")
-            .percent(50);
-        f.render_widget(gauge, area);
+impl Organizer for FileOrganizer {
+    fn name(&self) -> &'static str {
+        "Categorise"
     }
 
-    fn draw_complete_state(&self, f: &mut ratatui::Frame, area: Rect, result: &OrganizeResult) {
-        let mut lines = vec![
-            Line::from(Span::styled(
-                "✦ Organization Complete!",
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            )),
-            Line::from(""),
-            Line::from(vec![
-                Span::raw(" Files organized: "),
-                Span::styled(
-                    result.files_organized.to_string(),
-                    Style::default()
-                        .fg(Color::Green)
-                        .add_modifier(Modifier::BOLD),
-                ),
-            ]),
-            Line::from(vec![
-                Span::raw(" Files skipped: "),
-                Span::styled(
-                    result.files_skipped.to_string(),
-                    Style::default().fg(Color::Yellow),
-                ),
-            ]),
-            Line::from(vec![
-                Span::raw(" Files failed: "),
-                Span::styled(
-                    result.files_failed.to_string(),
-                    Style::default().fg(Color::Red),
-                ),
-            ]),
-            Line::from(""),
-            Line::from(Span::styled(
-                " Category Summary:",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            )),
-            Line::from(""),
-        ];
-
-        // Sort categories by count for better display
-        let mut sorted_categories: Vec<_> = result.category_counts.iter().collect();
-        sorted_categories.sort_by(|a, b| b.1.cmp(a.1));
-
-        for (category, count) in sorted_categories.iter().take(10) {
-            let icon = match category.as_str() {
-                name if name.contains("Image") => "",
-                name if name.contains("Video") => "",
-                name if name.contains("Audio") => "",
-                name if name.contains("Document") => "",
-                name if name.contains("Code") => "",
-                name if name.contains("Archive") => "",
-                _ => "",
-            };
-
-            lines.push(Line::from(vec![
-                Span::raw(format!("  {} ", icon)),
-                Span::styled(format!("{:15}", category), Style::default().fg(Color::Cyan)),
-                Span::raw(" → "),
-                Span::styled(
-                    format!("{} files", count),
-                    Style::default()
-                        .fg(Color::Green)
-                        .add_modifier(Modifier::BOLD),
+    /// One entry per category: its folder and how many extensions it claims
+    fn config_schema(&self) -> ConfigSchema {
+        let mut settings: Vec<Setting> = self
+            .config
+            .categories
+            .iter()
+            .map(|(key, category)| Setting {
+                key: key.clone(),
+                value: format!(
+                    "{} ({} extensions{})",
+                    category.folder_name.as_deref().unwrap_or(key),
+                    category.extensions.len(),
+                    if category.action == CategoryAction::Trash {
+                        ", trashed"
+                    } else {
+                        ""
+                    }
                 ),
-            ]));
+            })
+            .collect();
+        settings.sort_by(|a, b| a.key.cmp(&b.key));
+        if self.config.plugin.is_enabled() {
+            settings.push(Setting {
+                key: "plugin".to_string(),
+                value: self.config.plugin.describe(),
+            });
         }
-
-        if sorted_categories.len() > 10 {
-            lines.push(Line::from(""));
-            lines.push(Line::from(Span::styled(
-                format!("... and {} more categories", sorted_categories.len() - 10),
-                Style::default().fg(Color::Gray),
-            )));
+        ConfigSchema {
+            section: "categories",
+            settings,
         }
-
-        let widget =
-            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(" Results "));
-        f.render_widget(widget, area);
     }
 
-    fn draw_logs(&self, f: &mut ratatui::Frame, area: Rect) {
-        let _logs = self.organizer.get_logger().get_logs();
-        let items: Vec<ListItem> = _logs
-            .iter()
-            .rev()
-            .take(3)
-            .map(|log| {
-                let (style, icon) = match log.level {
-                    LogLevel::Success => (Style::default().fg(Color::Green), "✓"),
-                    LogLevel::Error => (Style::default().fg(Color::Red), "✗"),
-                    LogLevel::Warning => (Style::default().fg(Color::Yellow), "⚠"),
-                    LogLevel::Info => (Style::default().fg(Color::Gray), "ℹ"),
-                };
-                ListItem::new(format!("{} {}", icon, log.message)).style(style)
-            })
+    fn analyze(&self, files: &[PathBuf]) -> io::Result<Assignments> {
+        let ext_map = self.config.build_extension_map();
+        let mut targets: Vec<(PathBuf, Option<Target>)> = files
+            .par_iter()
+            .map(|file| (file.clone(), self.category_target(&ext_map, file)))
             .collect();
+        self.consult_plugin(&mut targets)?;
 
-        let list = List::new(items).block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(" Recent Activity "),
-        );
-        f.render_widget(list, area);
+        let mut assignments: Assignments = targets
+            .into_iter()
+            .map(|(file, target)| Assignment {
+                file,
+                target: target.unwrap_or_else(|| Target::Folder("Extras".to_string())),
+            })
+            .collect();
+        assignments.sort();
+        Ok(assignments)
     }
 
-    fn draw_controls(&self, f: &mut ratatui::Frame, area: Rect) {
-        let controls = match &self.state {
-            AppState::Ready => " 's' Start | 'd' Dry Run | 'q' Quit",
-            AppState::Organizing => " Organizing... Please wait",
-            AppState::Complete(_) => " 'q' Quit (or press any key to exit)",
-        };
-
-        let widget = Paragraph::new(controls)
-            .style(Style::default().fg(Color::Yellow))
-            .block(Block::default().borders(Borders::ALL).title(" Controls "));
-        f.render_widget(widget, area);
+    fn compound_extensions(&self) -> &[String] {
+        &self.compound_extensions
     }
 
-    /// Auto-organize files without UI interaction
-    /// Automatically starts organization (equivalent to pressing 's')
-    pub fn auto_organize(&mut self) -> io::Result<()> {
-        // Start organization (non-dry-run mode)
-        self.start_organization(false)?;
-
-        // Display results
-        if let AppState::Complete(result) = &self.state {
-            println!("\n✦ Organization Complete!\n");
-            println!("Summary:");
-            println!("   • Files organized: {}", result.files_organized);
-            println!("   • Files skipped:   {}", result.files_skipped);
-            println!("   • Files failed:    {}", result.files_failed);
-
-            if !result.category_counts.is_empty() {
-                println!("\nCategories:");
-
-                // Sort categories by count
-                let mut sorted_categories: Vec<_> = result.category_counts.iter().collect();
-                sorted_categories.sort_by(|a, b| b.1.cmp(a.1));
-
-                for (category, count) in sorted_categories {
-                    let icon = match category.as_str() {
-                        name if name.contains("Image") => "",
-                        name if name.contains("Video") => "",
-                        name if name.contains("Audio") || name.contains("Music") => "🎵",
-                        name if name.contains("Document") => "",
-                        name if name.contains("Code") => "",
-                        name if name.contains("Archive") => "",
-                        name if name.contains("Spreadsheet") => "",
-                        name if name.contains("Presentation") => "",
-                        _ => "",
-                    };
-                    println!("   {} {:20} → {} files", icon, category, count);
-                }
-            }
-
-            // Show recent logs
-            let _logs = self.organizer.get_logger().get_logs();
-        }
-
-        Ok(())
+    fn take_failures(&self) -> Vec<FileFailure> {
+        std::mem::take(&mut *self.failures.lock().unwrap())
     }
 }
 
@@ -824,4 +537,110 @@ mod tests {
         assert!(ext_map.contains_key("mp4"));
         assert!(ext_map.contains_key("pdf"));
     }
+
+    #[test]
+    fn test_longest_extension_wins() {
+        let mut config = FileOrganizerConfig::default();
+        config.categories.insert(
+            "types".to_string(),
+            CategoryConfig {
+                extensions: vec!["d.ts".to_string()],
+                folder_name: Some("Types".to_string()),
+                destination: None,
+                action: CategoryAction::Move,
+            },
+        );
+        config.categories.insert(
+            "junk".to_string(),
+            CategoryConfig {
+                extensions: vec!["part".to_string()],
+                folder_name: None,
+                destination: None,
+                action: CategoryAction::Trash,
+            },
+        );
+        let organizer = FileOrganizer::new(config);
+        let ext_map = organizer.config.build_extension_map();
+        let folder = |name: &str| {
+            organizer
+                .category_target(&ext_map, Path::new(name))
+                .unwrap()
+        };
+        let named = |name: &str| Target::Folder(name.to_string());
+
+        assert_eq!(folder("index.d.ts"), named("Types"));
+        assert_eq!(folder("index.ts"), named("Code"));
+        assert_eq!(folder("backup.tar.gz"), named("Archives"));
+        assert_eq!(folder("setup.exe.part"), Target::Trash);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_plugin_failure_keeps_the_category() {
+        let dir = tempfile::tempdir().unwrap();
+        let files: Vec<PathBuf> = ["a.pdf", "bad.txt", "c.jpg", "d.pdf", "e.pdf"]
+            .iter()
+            .map(|name| {
+                let path = dir.path().join(name);
+                fs::write(&path, "x").unwrap();
+                path
+            })
+            .collect();
+        let with_plugin = |script: &str| {
+            FileOrganizer::new(FileOrganizerConfig {
+                plugin: PluginConfig {
+                    command: vec!["sh".to_string(), "-c".to_string(), script.to_string()],
+                    consult: Consult::Before,
+                    timeout_secs: 5,
+                },
+                ..FileOrganizerConfig::default()
+            })
+        };
+        let targets = |assignments: Assignments| -> Vec<Target> {
+            assignments.into_iter().map(|a| a.target).collect()
+        };
+        let named = |name: &str| Target::Folder(name.to_string());
+
+        // Restarted after `bad.txt`, so the files after it are still asked
+        let organizer = with_plugin(
+            r#"while read -r line; do
+                 case "$line" in
+                   *bad*) exit 1 ;;
+                   *) echo '{"folder": "Sorted"}' ;;
+                 esac
+               done"#,
+        );
+        assert_eq!(
+            targets(organizer.analyze(&files).unwrap()),
+            vec![
+                named("Sorted"),
+                named("Documents"),
+                named("Sorted"),
+                named("Sorted"),
+                named("Sorted")
+            ]
+        );
+        let failures = organizer.take_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].path, files[1]);
+        assert!(organizer.take_failures().is_empty());
+
+        // Given up on after a few failures, the rest keep their categories
+        let organizer = with_plugin("read -r line; exit 1");
+        assert_eq!(
+            targets(organizer.analyze(&files).unwrap()),
+            vec![
+                named("Documents"),
+                named("Documents"),
+                named("Images"),
+                named("Documents"),
+                named("Documents")
+            ]
+        );
+        let failures = organizer.take_failures();
+        assert_eq!(failures.len(), PLUGIN_MAX_FAILURES);
+        assert!(failures[2]
+            .to_string()
+            .contains("the remaining files were not asked"));
+    }
 }
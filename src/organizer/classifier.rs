@@ -0,0 +1,465 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for learning folders from an organized tree and predicting them for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use chrono::Local;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use super::filename::tokenize_filename;
+use super::ignore::IgnoreMatcher;
+use super::intelligent::{build_tfidf_model, is_text_file, read_file_lines};
+use super::strategy::{Assignment, Assignments, ConfigSchema, Organizer, Target};
+use super::template;
+use super::tokenize;
+
+/// A file's content counts as much as this many name tokens
+const CONTENT_WEIGHT: f64 = 5.0;
+
+/// Laplace smoothing for words a folder has not seen
+const SMOOTHING: f64 = 1.0;
+
+/// Settings for `tyr train` and `tyr classify` (`[classifier_config]`)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ClassifierConfig {
+    /// Folder levels below the trained root a prediction can name (`Finance/Invoices` is 2)
+    pub max_depth: usize,
+    /// Files predicted with less confidence go to `review_folder` (0.0 to 1.0)
+    pub min_confidence: f64,
+    /// Folder under the trained root for files the model is unsure about
+    pub review_folder: String,
+    /// Lines of text files read for their content
+    pub max_lines_to_read: usize,
+    /// Languages whose stop words are dropped from names and contents
+    pub stop_words: Vec<String>,
+}
+
+impl Default for ClassifierConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: 2,
+            min_confidence: 0.6,
+            review_folder: "tyr-review".to_string(),
+            max_lines_to_read: 100,
+            stop_words: Vec::new(),
+        }
+    }
+}
+
+impl ClassifierConfig {
+    /// Problems with the settings, as messages for `tyr config check`
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.max_depth == 0 {
+            problems.push("max_depth must be greater than 0".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.min_confidence) {
+            problems.push(format!(
+                "min_confidence must be between 0.0 and 1.0 (got {})",
+                self.min_confidence
+            ));
+        }
+        if self.review_folder.trim().is_empty()
+            || !template::stays_inside(Path::new(&self.review_folder))
+        {
+            problems.push(format!(
+                "review_folder '{}' must be a folder inside the trained root",
+                self.review_folder
+            ));
+        }
+        problems
+    }
+}
+
+/// Word weights seen in the files of one folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FolderStats {
+    folder: String,
+    files: usize,
+    /// Sum of `weights`
+    total: f64,
+    weights: HashMap<String, f64>,
+}
+
+/// Multinomial naive Bayes over a file's name tokens, its extension and the TF-IDF
+/// weighted words of its content, one class per folder of the trained tree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Model {
+    /// Organized root the folders are relative to
+    pub root: PathBuf,
+    /// Local time of training, `%Y-%m-%d %H:%M:%S`
+    pub created: String,
+    pub files: usize,
+    folders: Vec<FolderStats>,
+    /// IDF of the content words found in two files or more
+    idf: HashMap<String, f64>,
+    /// Distinct words over all folders, for smoothing
+    vocabulary: usize,
+    stop_words: Vec<String>,
+    max_lines_to_read: usize,
+}
+
+/// Where the model would put a file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Prediction {
+    pub file: PathBuf,
+    /// Best folder, relative to the trained root
+    pub folder: String,
+    /// Model probability of `folder`, 0.0 to 1.0
+    pub confidence: f64,
+}
+
+impl Model {
+    /// Learns every folder of `root` (down to `max_depth`) from the files inside it
+    ///
+    /// Files directly in `root`, hidden folders, the review folder and `.tyrignore`
+    /// matches are not learned from.
+    pub fn train(
+        root: &Path,
+        config: &ClassifierConfig,
+        skip_patterns: &[String],
+    ) -> io::Result<Self> {
+        let mut ignore = IgnoreMatcher::for_directory(root, skip_patterns)?;
+        let mut labelled = Vec::new();
+        collect_files(root, root, config, &mut ignore, &mut labelled)?;
+
+        let folders: BTreeMap<&str, usize> =
+            labelled
+                .iter()
+                .fold(BTreeMap::new(), |mut counts, (_, folder)| {
+                    *counts.entry(folder.as_str()).or_insert(0) += 1;
+                    counts
+                });
+        if folders.len() < 2 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "{} needs at least two folders with files in them to learn from",
+                    root.display()
+                ),
+            ));
+        }
+
+        let contents: Vec<Option<String>> = labelled
+            .par_iter()
+            .map(|(path, _)| read_text(path, config.max_lines_to_read))
+            .collect();
+        let texts: Vec<String> = contents.iter().flatten().cloned().collect();
+
+        let mut model = Self {
+            root: root.to_path_buf(),
+            created: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            files: labelled.len(),
+            folders: Vec::new(),
+            idf: content_idf(&texts, &config.stop_words),
+            vocabulary: 0,
+            stop_words: config.stop_words.clone(),
+            max_lines_to_read: config.max_lines_to_read,
+        };
+
+        let mut stats: BTreeMap<String, FolderStats> = BTreeMap::new();
+        for ((path, folder), content) in labelled.iter().zip(&contents) {
+            let entry = stats.entry(folder.clone()).or_insert_with(|| FolderStats {
+                folder: folder.clone(),
+                files: 0,
+                total: 0.0,
+                weights: HashMap::new(),
+            });
+            entry.files += 1;
+            for (word, weight) in model.features(path, content.as_deref()) {
+                entry.total += weight;
+                *entry.weights.entry(word).or_insert(0.0) += weight;
+            }
+        }
+        model.vocabulary = stats
+            .values()
+            .flat_map(|s| s.weights.keys())
+            .collect::<HashSet<_>>()
+            .len();
+        model.folders = stats.into_values().collect();
+        Ok(model)
+    }
+
+    /// Learned folders with the number of files each was learned from
+    pub fn folders(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.folders.iter().map(|s| (s.folder.as_str(), s.files))
+    }
+
+    /// `n:` name tokens and `e:` the extension count once each, `c:` content words share
+    /// `CONTENT_WEIGHT` by their TF-IDF
+    fn features(&self, path: &Path, content: Option<&str>) -> Vec<(String, f64)> {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut features: Vec<(String, f64)> = tokenize_filename(&name, &self.stop_words)
+            .into_iter()
+            .map(|token| (format!("n:{}", token), 1.0))
+            .collect();
+        if let Some(ext) = path.extension() {
+            features.push((format!("e:{}", ext.to_string_lossy().to_lowercase()), 1.0));
+        }
+
+        if let Some(text) = content {
+            let mut tfidf: HashMap<String, f64> = HashMap::new();
+            for word in tokenize::tokenize(text, &self.stop_words) {
+                if let Some(idf) = self.idf.get(&word) {
+                    *tfidf.entry(word).or_insert(0.0) += idf;
+                }
+            }
+            let total: f64 = tfidf.values().sum();
+            if total > 0.0 {
+                features.extend(
+                    tfidf
+                        .into_iter()
+                        .map(|(word, w)| (format!("c:{}", word), w / total * CONTENT_WEIGHT)),
+                );
+            }
+        }
+        features
+    }
+
+    /// Most likely folder for `file`, with its probability
+    pub fn predict(&self, file: &Path) -> Prediction {
+        let content = read_text(file, self.max_lines_to_read);
+        let features = self.features(file, content.as_deref());
+        let all_files: usize = self.folders.iter().map(|s| s.files).sum();
+        let vocabulary = self.vocabulary.max(1) as f64;
+
+        let scores: Vec<f64> = self
+            .folders
+            .iter()
+            .map(|stats| {
+                let prior = (stats.files as f64 / all_files as f64).ln();
+                let denominator = stats.total + SMOOTHING * vocabulary;
+                features
+                    .iter()
+                    .map(|(word, weight)| {
+                        let seen = stats.weights.get(word).copied().unwrap_or(0.0);
+                        weight * ((seen + SMOOTHING) / denominator).ln()
+                    })
+                    .sum::<f64>()
+                    + prior
+            })
+            .collect();
+
+        // Softmax, shifted by the best score so the exponentials do not underflow
+        let best = scores.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let sum: f64 = scores.iter().map(|s| (s - best).exp()).sum();
+        let (index, _) = scores
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap_or((0, &0.0));
+
+        Prediction {
+            file: file.to_path_buf(),
+            folder: self
+                .folders
+                .get(index)
+                .map(|s| s.folder.clone())
+                .unwrap_or_default(),
+            confidence: if sum > 0.0 { 1.0 / sum } else { 0.0 },
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Corrupt model {}: {}", path.display(), e),
+            )
+        })
+    }
+}
+
+/// Content of text files, `None` for everything else
+fn read_text(path: &Path, max_lines: usize) -> Option<String> {
+    if is_text_file(path) {
+        read_file_lines(path, max_lines).ok()
+    } else {
+        None
+    }
+}
+
+/// IDF of the words found in at least two documents; rarer words say little about a folder
+fn content_idf(texts: &[String], stop_words: &[String]) -> HashMap<String, f64> {
+    if texts.len() < 2 {
+        return HashMap::new();
+    }
+    let model = build_tfidf_model(texts, stop_words);
+    let max_idf = (texts.len() as f64 / 2.0).ln() + 1.0 + 1e-9;
+    model
+        .vocabulary
+        .into_iter()
+        .zip(model.idf)
+        .filter(|(_, idf)| *idf <= max_idf)
+        .collect()
+}
+
+/// Files below `dir` with their folder relative to `root`, cut to `max_depth` levels
+fn collect_files(
+    root: &Path,
+    dir: &Path,
+    config: &ClassifierConfig,
+    ignore: &mut IgnoreMatcher,
+    files: &mut Vec<(PathBuf, String)>,
+) -> io::Result<()> {
+    let review = Path::new(&config.review_folder);
+    for entry in fs::read_dir(dir)?.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        let relative = path.strip_prefix(root).unwrap_or(&path);
+        if hidden || relative.starts_with(review) {
+            continue;
+        }
+
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if ignore.is_ignored(&path, file_type.is_dir()) {
+            continue;
+        }
+        if file_type.is_dir() {
+            ignore.enter(&path)?;
+            collect_files(root, &path, config, ignore, files)?;
+        } else if file_type.is_file() && dir != root {
+            let folder: Vec<String> = dir
+                .strip_prefix(root)
+                .unwrap_or(dir)
+                .components()
+                .filter_map(|c| match c {
+                    Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+                    _ => None,
+                })
+                .take(config.max_depth)
+                .collect();
+            files.push((path, folder.join("/")));
+        }
+    }
+    Ok(())
+}
+
+/// Sends files to the folder a trained `Model` predicts (`tyr classify`)
+///
+/// Folders are relative to the trained root, so plans should use it as their destination.
+pub struct ClassifierOrganizer {
+    model: Model,
+    config: ClassifierConfig,
+}
+
+impl ClassifierOrganizer {
+    pub fn new(model: Model, config: ClassifierConfig) -> Self {
+        Self { model, config }
+    }
+
+    pub fn model(&self) -> &Model {
+        &self.model
+    }
+
+    pub fn predict(&self, files: &[PathBuf]) -> Vec<Prediction> {
+        files.par_iter().map(|f| self.model.predict(f)).collect()
+    }
+
+    /// The predicted folder, or the review folder when the model is not sure enough
+    pub fn target(&self, prediction: &Prediction) -> Target {
+        if prediction.confidence >= self.config.min_confidence {
+            Target::Folder(prediction.folder.clone())
+        } else {
+            Target::Folder(self.config.review_folder.clone())
+        }
+    }
+
+    pub fn assignments(&self, predictions: &[Prediction]) -> Assignments {
+        let mut assignments: Assignments = predictions
+            .iter()
+            .map(|prediction| Assignment {
+                file: prediction.file.clone(),
+                target: self.target(prediction),
+            })
+            .collect();
+        assignments.sort();
+        assignments
+    }
+}
+
+impl Organizer for ClassifierOrganizer {
+    fn name(&self) -> &'static str {
+        "Trained classifier"
+    }
+
+    fn config_schema(&self) -> ConfigSchema {
+        ConfigSchema::of("classifier_config", &self.config)
+    }
+
+    fn analyze(&self, files: &[PathBuf]) -> io::Result<Assignments> {
+        Ok(self.assignments(&self.predict(files)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trained_model_predicts_folders_of_new_files() {
+        let archive = tempfile::tempdir().unwrap();
+        let root = archive.path();
+        let files = [
+            ("Finance/Invoices/invoice_acme_2023_01.pdf", ""),
+            ("Finance/Invoices/invoice_globex_2023_02.pdf", ""),
+            ("Finance/Invoices/2024/invoice_acme_2024_03.pdf", ""),
+            (
+                "Notes/meeting_notes_monday.txt",
+                "agenda budget roadmap action items",
+            ),
+            (
+                "Notes/standup.txt",
+                "agenda blockers action items yesterday",
+            ),
+            (".git/config", ""),
+            ("loose.txt", ""),
+        ];
+        for (path, content) in files {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        let config = ClassifierConfig::default();
+        let model = Model::train(root, &config, &[]).unwrap();
+        assert_eq!(model.files, 5);
+        let folders: Vec<(&str, usize)> = model.folders().collect();
+        assert_eq!(folders, [("Finance/Invoices", 3), ("Notes", 2)]);
+
+        let inbox = tempfile::tempdir().unwrap();
+        let invoice = inbox.path().join("invoice_initech_2025_07.pdf");
+        let retro = inbox.path().join("retro.txt");
+        fs::write(&invoice, "").unwrap();
+        fs::write(&retro, "agenda action items for the retro").unwrap();
+
+        let organizer = ClassifierOrganizer::new(model, config);
+        let predictions = organizer.predict(&[invoice, retro]);
+        assert_eq!(predictions[0].folder, "Finance/Invoices");
+        assert!(predictions[0].confidence > 0.6);
+        assert_eq!(predictions[1].folder, "Notes");
+    }
+}
@@ -0,0 +1,353 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for remembering how the user corrected earlier groups for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::journal::{EntryKind, Journal};
+use super::tokenize;
+
+/// Links recorded for one file at most, so a big folder does not flood the store
+const MAX_LINKS_PER_FILE: usize = 10;
+
+/// Folders with more files than this are not learned from; they are not groups
+const MAX_GROUP_SIZE: usize = 50;
+
+/// Corrections the user made to tyr's groups, kept in `feedback.toml` between runs
+///
+/// Files are remembered by `name_key`, so `report_week12.pdf` and `report_week13.pdf`
+/// count as the same file, which is what makes a weekly mistake a one-time one.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Feedback {
+    /// Name keys the user put into the same folder
+    pub must_link: BTreeSet<(String, String)>,
+    /// Name keys tyr grouped and the user pulled apart
+    pub cannot_link: BTreeSet<(String, String)>,
+    /// Folder names the user gave groups, newest last
+    pub folder_names: Vec<ChosenName>,
+}
+
+/// A folder name the user picked, with the words its files had in common
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ChosenName {
+    pub words: Vec<String>,
+    pub name: String,
+}
+
+/// `Report_Week12 (1).pdf` -> `report_week# (#).pdf`
+pub fn name_key(file_name: &str) -> String {
+    let mut key = String::with_capacity(file_name.len());
+    for c in file_name.to_lowercase().chars() {
+        if !c.is_ascii_digit() {
+            key.push(c);
+        } else if !key.ends_with('#') {
+            key.push('#');
+        }
+    }
+    key
+}
+
+/// The two keys in a fixed order; `None` for the same key, which cannot be told apart
+fn pair(a: &str, b: &str) -> Option<(String, String)> {
+    let (a, b) = (name_key(a), name_key(b));
+    match a.cmp(&b) {
+        std::cmp::Ordering::Less => Some((a, b)),
+        std::cmp::Ordering::Greater => Some((b, a)),
+        std::cmp::Ordering::Equal => None,
+    }
+}
+
+/// Words at least half of `files` share, sorted
+fn signature(files: &[String]) -> Vec<String> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for file in files {
+        let stem = Path::new(file)
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let words: BTreeSet<String> = tokenize::split_words(&stem)
+            .into_iter()
+            .filter(|w| w.chars().all(char::is_alphabetic) && tokenize::is_meaningful(w))
+            .collect();
+        for word in words {
+            *counts.entry(word).or_insert(0) += 1;
+        }
+    }
+    counts
+        .into_iter()
+        .filter(|(_, count)| count * 2 >= files.len())
+        .map(|(word, _)| word)
+        .collect()
+}
+
+fn file_name(path: &Path) -> Option<String> {
+    Some(path.file_name()?.to_string_lossy().into_owned())
+}
+
+impl Feedback {
+    /// The store at `path`; a missing file is an empty store
+    pub fn load(path: &Path) -> io::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)?;
+        toml::from_str(&content).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Corrupt feedback {}: {}", path.display(), e.message()),
+            )
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = toml::to_string(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        fs::write(path, content)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.must_link.is_empty() && self.cannot_link.is_empty() && self.folder_names.is_empty()
+    }
+
+    /// Remembers that files named like `a` and `b` belong together
+    pub fn link(&mut self, a: &str, b: &str) {
+        if let Some(pair) = pair(a, b) {
+            self.cannot_link.remove(&pair);
+            self.must_link.insert(pair);
+        }
+    }
+
+    /// Remembers that files named like `a` and `b` do not belong together
+    pub fn separate(&mut self, a: &str, b: &str) {
+        if let Some(pair) = pair(a, b) {
+            self.must_link.remove(&pair);
+            self.cannot_link.insert(pair);
+        }
+    }
+
+    /// Links each of `files` with the first few of `mates`
+    pub fn link_all(&mut self, files: &[String], mates: &[String]) {
+        for file in files {
+            for mate in mates.iter().filter(|m| *m != file).take(MAX_LINKS_PER_FILE) {
+                self.link(file, mate);
+            }
+        }
+    }
+
+    /// Separates each of `files` from the first few of `mates`
+    pub fn separate_all(&mut self, files: &[String], mates: &[String]) {
+        for file in files {
+            for mate in mates.iter().filter(|m| *m != file).take(MAX_LINKS_PER_FILE) {
+                self.separate(file, mate);
+            }
+        }
+    }
+
+    pub fn must_link(&self, a: &str, b: &str) -> bool {
+        pair(a, b).is_some_and(|pair| self.must_link.contains(&pair))
+    }
+
+    pub fn cannot_link(&self, a: &str, b: &str) -> bool {
+        pair(a, b).is_some_and(|pair| self.cannot_link.contains(&pair))
+    }
+
+    /// Remembers `name` for groups of files like `files`, replacing an older choice
+    pub fn name_folder(&mut self, files: &[String], name: &str) {
+        let words = signature(files);
+        if words.is_empty() {
+            return;
+        }
+        self.folder_names.retain(|chosen| chosen.words != words);
+        self.folder_names.push(ChosenName {
+            words,
+            name: name.to_string(),
+        });
+    }
+
+    /// A name the user chose before for a group sharing most words with `files`
+    ///
+    /// Compares word sets (Jaccard >= 0.5); the newest choice wins a tie.
+    pub fn preferred_name(&self, files: &[String]) -> Option<&str> {
+        if self.folder_names.is_empty() {
+            return None;
+        }
+        let words: HashSet<String> = signature(files).into_iter().collect();
+        if words.is_empty() {
+            return None;
+        }
+
+        let mut best: Option<(f64, &str)> = None;
+        for chosen in &self.folder_names {
+            let shared = chosen.words.iter().filter(|w| words.contains(*w)).count();
+            let union = words.len() + chosen.words.len() - shared;
+            let score = shared as f64 / union as f64;
+            if score >= 0.5 && best.is_none_or(|(best, _)| score >= best) {
+                best = Some((score, &chosen.name));
+            }
+        }
+        best.map(|(_, name)| name)
+    }
+
+    pub fn merge(&mut self, other: Feedback) {
+        for (a, b) in other.must_link {
+            self.cannot_link.remove(&(a.clone(), b.clone()));
+            self.must_link.insert((a, b));
+        }
+        for (a, b) in other.cannot_link {
+            self.must_link.remove(&(a.clone(), b.clone()));
+            self.cannot_link.insert((a, b));
+        }
+        for chosen in other.folder_names {
+            self.folder_names.retain(|c| c.words != chosen.words);
+            self.folder_names.push(chosen);
+        }
+    }
+
+    /// Learns from what happened to a run's folders since (see `Journal::learn`)
+    ///
+    /// A file moved back out of its folder is separated from the files that stayed, one
+    /// moved into another folder is linked with the files there, and a folder renamed as
+    /// a whole gives its name to groups like it. Returns true when anything was learned.
+    pub fn learn_from_journal(&mut self, journal: &Journal) -> bool {
+        if !journal.learn {
+            return false;
+        }
+        let before = self.clone();
+
+        let mut folders: BTreeMap<&Path, Vec<(String, &Path, &Path)>> = BTreeMap::new();
+        for entry in journal.moves.iter().filter(|e| e.kind == EntryKind::Move) {
+            if let (Some(folder), Some(name)) = (entry.to.parent(), file_name(&entry.to)) {
+                folders
+                    .entry(folder)
+                    .or_default()
+                    .push((name, &entry.from, &entry.to));
+            }
+        }
+
+        for (folder, entries) in &folders {
+            if entries.len() > MAX_GROUP_SIZE {
+                continue;
+            }
+            let mut stayed = Vec::new();
+            // With the folder the file went to; `None` when it went back where it was
+            let mut left: Vec<(&String, Option<PathBuf>)> = Vec::new();
+            for (name, from, to) in entries {
+                if to.exists() {
+                    stayed.push(name.clone());
+                } else if from.exists() {
+                    left.push((name, None));
+                } else if let Some(dir) = find_in_siblings(folder, name) {
+                    left.push((name, Some(dir)));
+                }
+            }
+
+            // Every file ended up in one folder tyr did not make: the folder was renamed
+            let renamed = match left.first() {
+                Some((_, Some(dir))) if !folder.exists() && left.len() == entries.len() => left
+                    .iter()
+                    .all(|(_, d)| d.as_ref() == Some(dir))
+                    .then(|| dir.clone())
+                    .filter(|dir| !folders.contains_key(dir.as_path())),
+                _ => None,
+            };
+            if let Some(name) = renamed.as_deref().and_then(file_name) {
+                let files: Vec<String> = entries.iter().map(|(n, _, _)| n.clone()).collect();
+                self.name_folder(&files, &name);
+                continue;
+            }
+
+            for (name, moved_to) in left {
+                let name = [name.clone()];
+                self.separate_all(&name, &stayed);
+                let Some(Ok(entries)) = moved_to.map(fs::read_dir) else {
+                    continue;
+                };
+                let mates: Vec<String> = entries
+                    .filter_map(|e| e.ok())
+                    .filter(|e| e.path().is_file())
+                    .filter_map(|e| file_name(&e.path()))
+                    .take(MAX_LINKS_PER_FILE + 1)
+                    .collect();
+                self.link_all(&name, &mates);
+            }
+        }
+
+        *self != before
+    }
+}
+
+/// Another folder next to `folder` holding a file called `name`
+fn find_in_siblings(folder: &Path, name: &str) -> Option<PathBuf> {
+    fs::read_dir(folder.parent()?)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|dir| dir.is_dir() && dir != folder)
+        .find(|dir| dir.join(name).is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::organizer::transfer::Output;
+
+    fn journal(base: &Path, moves: &[(&str, &str)]) -> Journal {
+        let mut journal = Journal::new(base, &Output::default());
+        journal.learn = true;
+        for (name, folder) in moves {
+            let from = base.join(name);
+            let to = base.join(folder).join(name);
+            fs::create_dir_all(to.parent().unwrap()).unwrap();
+            fs::write(&to, name).unwrap();
+            journal.record_kind(&from, &to, EntryKind::Move);
+        }
+        journal
+    }
+
+    #[test]
+    fn test_learns_from_files_moved_back_and_renamed_folders() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        let run = journal(
+            base,
+            &[
+                ("report_week12.pdf", "Report"),
+                ("report_week13.pdf", "Report"),
+                ("report_card_2024.pdf", "Report"),
+                ("IMG_0001.jpg", "Img"),
+                ("IMG_0002.jpg", "Img"),
+            ],
+        );
+        // The report card goes back, the images get a better folder name
+        fs::rename(
+            base.join("Report/report_card_2024.pdf"),
+            base.join("report_card_2024.pdf"),
+        )
+        .unwrap();
+        fs::rename(base.join("Img"), base.join("Holiday Photos")).unwrap();
+
+        let mut feedback = Feedback::default();
+        assert!(feedback.learn_from_journal(&run));
+        assert!(feedback.cannot_link("report_card_2025.pdf", "report_week40.pdf"));
+        assert!(!feedback.cannot_link("report_week1.pdf", "report_week2.pdf"));
+        assert_eq!(
+            feedback.preferred_name(&["IMG_0107.jpg".to_string(), "IMG_0108.jpg".to_string()]),
+            Some("Holiday Photos")
+        );
+        // Learning again from the same journal changes nothing
+        assert!(!feedback.learn_from_journal(&run));
+    }
+}
@@ -8,39 +8,30 @@
  * tyr(https:github.com/Nurysso/tyr) project
 */
 
-use std::collections::HashSet;
-use std::fs;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::io;
-use std::path::{Path, PathBuf};
-
-/// Checks if a file should be skipped based on common system file patterns
-pub fn should_skip_file(filename: &str) -> bool {
-    let skip_patterns = [
-        ".DS_Store",
-        "Thumbs.db",
-        ".git",
-        ".gitignore",
-        "desktop.ini",
-        ".localized",
-        "~$",
-    ];
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::Arc;
 
-    skip_patterns
-        .iter()
-        .any(|pattern| filename.contains(pattern))
-}
+use super::feedback::Feedback;
+use super::strategy::{Assignment, Assignments, ConfigSchema, Organizer, Target};
+use super::tokenize;
 
 // ML-Based Similarity Detection
 
 /// Configuration for similarity detection
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
 pub struct SimilarityConfig {
-    #[allow(dead_code)]
-    /// Threshold for Levenshtein similarity (0.0 to 1.0)
-    pub levenshtein_threshold: f64,
-    #[allow(dead_code)]
-    /// Threshold for Jaccard similarity (0.0 to 1.0)
-    pub jaccard_threshold: f64,
+    /// Deprecated, never used; still accepted so old tyr.toml files load without warnings
+    #[serde(default, skip_serializing)]
+    pub levenshtein_threshold: Option<f64>,
+    /// Deprecated, never used; still accepted so old tyr.toml files load without warnings
+    #[serde(default, skip_serializing)]
+    pub jaccard_threshold: Option<f64>,
 
     /// Weight for Levenshtein distance (0.0 to 1.0)
     pub levenshtein_weight: f64,
@@ -50,16 +41,20 @@ pub struct SimilarityConfig {
 
     /// Minimum similarity score to consider files related (0.0 to 1.0)
     pub min_similarity_score: f64,
+
+    /// Languages whose stop words are ignored when comparing tokens (e.g. "en", "de")
+    pub stop_words: Vec<String>,
 }
 
 impl Default for SimilarityConfig {
     fn default() -> Self {
         Self {
-            levenshtein_threshold: 0.7,
-            jaccard_threshold: 0.5,
+            levenshtein_threshold: None,
+            jaccard_threshold: None,
             levenshtein_weight: 0.6,
             jaccard_weight: 0.4,
             min_similarity_score: 0.65,
+            stop_words: Vec::new(),
         }
     }
 }
@@ -72,30 +67,6 @@ pub struct FileGroup {
     pub avg_similarity: f64,
 }
 
-/// Result of organizing files by similarity
-#[derive(Debug)]
-pub struct OrganizeResult {
-    pub files_moved: usize,
-    pub folders_created: usize,
-    pub files_skipped: usize,
-    pub skipped_details: Vec<SkippedFile>,
-    pub errors: Vec<String>,
-}
-
-/// Information about a skipped file
-#[derive(Debug, Clone)]
-pub struct SkippedFile {
-    pub filename: String,
-    pub reason: SkipReason,
-}
-
-#[derive(Debug, Clone)]
-pub enum SkipReason {
-    SingleFile,       // Only one file in its group
-    SystemFile,       // System file pattern detected
-    AlreadyOrganized, // Already in a subfolder
-}
-
 /// Calculates Levenshtein distance between two strings
 /// This measures the minimum number of single-character edits needed
 pub fn levenshtein_distance(s1: &str, s2: &str) -> usize {
@@ -112,11 +83,11 @@ pub fn levenshtein_distance(s1: &str, s2: &str) -> usize {
     let mut matrix = vec![vec![0; len2 + 1]; len1 + 1];
 
     // Initialize first row and column
-    for i in 0..=len1 {
-        matrix[i][0] = i;
+    for (i, row) in matrix.iter_mut().enumerate() {
+        row[0] = i;
     }
-    for j in 0..=len2 {
-        matrix[0][j] = j;
+    for (j, cell) in matrix[0].iter_mut().enumerate() {
+        *cell = j;
     }
 
     let s1_chars: Vec<char> = s1.chars().collect();
@@ -149,7 +120,8 @@ pub fn levenshtein_distance(s1: &str, s2: &str) -> usize {
 /// Higher values mean more similar
 pub fn levenshtein_similarity(s1: &str, s2: &str) -> f64 {
     let distance = levenshtein_distance(s1, s2);
-    let max_len = s1.len().max(s2.len()) as f64;
+    // Count characters, not bytes, so non-ASCII names are not scored as more similar
+    let max_len = s1.chars().count().max(s2.chars().count()) as f64;
 
     if max_len == 0.0 {
         return 1.0;
@@ -158,9 +130,10 @@ pub fn levenshtein_similarity(s1: &str, s2: &str) -> f64 {
     1.0 - (distance as f64 / max_len)
 }
 
-/// Tokenizes a filename into meaningful parts - IMPROVED VERSION
-/// Now preserves meaningful phrases and handles common patterns better
-fn tokenize_filename(filename: &str) -> HashSet<String> {
+/// Tokenizes a filename into meaningful parts
+/// Splits on delimiters, camelCase and Unicode word boundaries, folds diacritics and
+/// drops stop words of the configured languages
+pub(crate) fn tokenize_filename(filename: &str, stop_words: &[String]) -> HashSet<String> {
     let mut tokens = HashSet::new();
 
     // Remove extension if present
@@ -168,7 +141,7 @@ fn tokenize_filename(filename: &str) -> HashSet<String> {
         .rsplit_once('.')
         .map(|(n, _)| n)
         .unwrap_or(filename);
-    let name_lower = name.to_lowercase();
+    let name_lower = tokenize::fold_diacritics(&name.to_lowercase());
 
     // First, add the full name without delimiters as a token (helps with phrases)
     let clean_full = name_lower
@@ -202,46 +175,18 @@ fn tokenize_filename(filename: &str) -> HashSet<String> {
     }
 
     // Now tokenize individual words
-    let delimiters = ['-', '_', '.', '(', ')', '[', ']', '{', '}'];
-    let mut current_token = String::new();
-
-    for c in name.chars() {
-        if delimiters.contains(&c) {
-            if !current_token.is_empty() {
-                let token_lower = current_token.to_lowercase();
-                if token_lower.len() > 1 && !token_lower.chars().all(|c| c.is_numeric()) {
-                    tokens.insert(token_lower);
-                }
-                current_token.clear();
-            }
-        } else if c == ' ' {
-            // For spaces, we want to preserve word boundaries but also create word tokens
-            if !current_token.is_empty() {
-                let token_lower = current_token.to_lowercase();
-                if token_lower.len() > 1 && !token_lower.chars().all(|c| c.is_numeric()) {
-                    tokens.insert(token_lower);
-                }
-                current_token.clear();
-            }
-        } else {
-            current_token.push(c);
-        }
-    }
+    let words: Vec<String> = tokenize::split_words(name)
+        .into_iter()
+        .filter(|w| !tokenize::is_stop_word(w, stop_words))
+        .collect();
 
-    if !current_token.is_empty() {
-        let token_lower = current_token.to_lowercase();
-        if token_lower.len() > 1 && !token_lower.chars().all(|c| c.is_numeric()) {
-            tokens.insert(token_lower);
+    for word in &words {
+        if tokenize::is_meaningful(word) {
+            tokens.insert(word.clone());
         }
     }
 
     // Also extract bigrams (two-word combinations) for better matching
-    let words: Vec<String> = name
-        .split(|c: char| !c.is_alphanumeric() && c != ' ')
-        .filter(|s| !s.trim().is_empty())
-        .map(|s| s.to_lowercase())
-        .collect();
-
     for window in words.windows(2) {
         let bigram = format!("{} {}", window[0], window[1]);
         tokens.insert(bigram);
@@ -252,38 +197,118 @@ fn tokenize_filename(filename: &str) -> HashSet<String> {
 
 /// Calculates Jaccard similarity between two strings based on token sets
 /// This measures overlap of words/tokens in the filenames
-pub fn jaccard_similarity(s1: &str, s2: &str) -> f64 {
-    let tokens1 = tokenize_filename(s1);
-    let tokens2 = tokenize_filename(s2);
+pub fn jaccard_similarity(s1: &str, s2: &str, stop_words: &[String]) -> f64 {
+    weighted_jaccard(s1, s2, stop_words, &HashMap::new())
+}
+
+/// Jaccard similarity where each token counts with its weight (1.0 when not listed)
+fn weighted_jaccard(
+    s1: &str,
+    s2: &str,
+    stop_words: &[String],
+    weights: &HashMap<String, f64>,
+) -> f64 {
+    let tokens1 = tokenize_filename(s1, stop_words);
+    let tokens2 = tokenize_filename(s2, stop_words);
 
     if tokens1.is_empty() && tokens2.is_empty() {
         return 1.0;
     }
 
-    let intersection: HashSet<_> = tokens1.intersection(&tokens2).collect();
-    let union: HashSet<_> = tokens1.union(&tokens2).collect();
+    let weight = |token: &String| weights.get(token).copied().unwrap_or(1.0);
+    let intersection: f64 = tokens1.intersection(&tokens2).map(weight).sum();
+    let union: f64 = tokens1.union(&tokens2).map(weight).sum();
 
-    if union.is_empty() {
+    if union == 0.0 {
         return 0.0;
     }
 
-    intersection.len() as f64 / union.len() as f64
+    intersection / union
 }
 
 /// Calculates combined similarity score using both metrics
 pub fn combined_similarity(s1: &str, s2: &str, config: &SimilarityConfig) -> f64 {
+    weighted_similarity(s1, s2, config, &HashMap::new())
+}
+
+fn weighted_similarity(
+    s1: &str,
+    s2: &str,
+    config: &SimilarityConfig,
+    weights: &HashMap<String, f64>,
+) -> f64 {
     let lev_sim = levenshtein_similarity(s1, s2);
-    let jac_sim = jaccard_similarity(s1, s2);
+    let jac_sim = weighted_jaccard(s1, s2, &config.stop_words, weights);
 
     (lev_sim * config.levenshtein_weight) + (jac_sim * config.jaccard_weight)
 }
 
+/// The parts of a similarity score, for explaining a group
+struct Breakdown {
+    levenshtein: f64,
+    jaccard: f64,
+    score: f64,
+    /// Tokens both names have, sorted
+    shared: Vec<String>,
+}
+
+fn breakdown(
+    s1: &str,
+    s2: &str,
+    config: &SimilarityConfig,
+    weights: &HashMap<String, f64>,
+) -> Breakdown {
+    let levenshtein = levenshtein_similarity(s1, s2);
+    let jaccard = weighted_jaccard(s1, s2, &config.stop_words, weights);
+    let tokens = tokenize_filename(s2, &config.stop_words);
+    let mut shared: Vec<String> = tokenize_filename(s1, &config.stop_words)
+        .intersection(&tokens)
+        .cloned()
+        .collect();
+    shared.sort();
+
+    Breakdown {
+        levenshtein,
+        jaccard,
+        score: levenshtein * config.levenshtein_weight + jaccard * config.jaccard_weight,
+        shared,
+    }
+}
+
+/// Token weights learned from corrections: tokens shared by names the user pulled apart
+/// count half as much, tokens shared by names the user put together half again more
+fn token_weights(feedback: &Feedback, stop_words: &[String]) -> HashMap<String, f64> {
+    let mut weights: HashMap<String, f64> = HashMap::new();
+    let links = feedback
+        .cannot_link
+        .iter()
+        .map(|pair| (pair, 0.5))
+        .chain(feedback.must_link.iter().map(|pair| (pair, 1.5)));
+
+    for ((a, b), factor) in links {
+        let tokens_a = tokenize_filename(a, stop_words);
+        for token in tokens_a.intersection(&tokenize_filename(b, stop_words)) {
+            let weight = weights.entry(token.clone()).or_insert(1.0);
+            *weight = (*weight * factor).clamp(0.1, 4.0);
+        }
+    }
+    weights
+}
+
 /// Groups similar files together using clustering
-pub fn group_similar_files(filenames: &[String], config: &SimilarityConfig) -> Vec<FileGroup> {
+///
+/// Corrections in `feedback` win over the scores: a file never joins a group holding a
+/// file it was pulled apart from, and always joins one whose first file it was put with.
+pub fn group_similar_files(
+    filenames: &[String],
+    config: &SimilarityConfig,
+    feedback: &Feedback,
+) -> Vec<FileGroup> {
     if filenames.is_empty() {
         return Vec::new();
     }
 
+    let weights = token_weights(feedback, &config.stop_words);
     let mut groups: Vec<FileGroup> = Vec::new();
     let mut assigned: HashSet<usize> = HashSet::new();
 
@@ -301,8 +326,18 @@ pub fn group_similar_files(filenames: &[String], config: &SimilarityConfig) -> V
             if assigned.contains(&j) {
                 continue;
             }
+            if group_files
+                .iter()
+                .any(|member| feedback.cannot_link(member, &filenames[j]))
+            {
+                continue;
+            }
 
-            let similarity = combined_similarity(&filenames[i], &filenames[j], config);
+            let similarity = if feedback.must_link(&filenames[i], &filenames[j]) {
+                1.0
+            } else {
+                weighted_similarity(&filenames[i], &filenames[j], config, &weights)
+            };
 
             if similarity >= config.min_similarity_score {
                 group_files.push(filenames[j].clone());
@@ -357,7 +392,13 @@ fn extract_common_prefix(filenames: &[String]) -> String {
 }
 
 /// Suggests a smart, memorable folder name for a group of similar files
-pub fn suggest_folder_name(group: &FileGroup) -> String {
+///
+/// A name the user gave a group like this one before wins over the naming rules.
+pub fn suggest_folder_name(group: &FileGroup, feedback: &Feedback) -> String {
+    if let Some(name) = feedback.preferred_name(&group.files) {
+        return name.to_string();
+    }
+
     let name = if group.representative_name.is_empty() {
         "SimilarFiles"
     } else {
@@ -451,25 +492,62 @@ fn smart_folder_naming(name: &str) -> String {
         .to_string()
 }
 
-fn remove_date_patterns(s: &str) -> String {
-    let mut cleaned = String::new();
-    let chars: Vec<char> = s.chars().collect();
-    let mut i = 0;
-
-    while i < chars.len() {
-        // Check for date-like patterns (4 digits followed by more digits)
-        if i + 7 < chars.len() && chars[i..i + 4].iter().all(|c| c.is_numeric()) {
-            // Skip potential date
-            i += 8;
-            while i < chars.len() && (chars[i].is_numeric() || chars[i] == '-' || chars[i] == '_') {
-                i += 1;
-            }
-        } else {
-            cleaned.push(chars[i]);
-            i += 1;
+/// Finds a calendar date in a filename: `20240131`, `2024-01-31`, `2024_01_31` or
+/// `2024.01.31`, as camera, phone and WhatsApp names use (`IMG-20240131-WA0001`)
+///
+/// Returns the date and its byte range in `s`. Digit runs that are not a real date
+/// between 1970 and 2099 are skipped.
+pub fn detect_date(s: &str) -> Option<(NaiveDate, Range<usize>)> {
+    let bytes = s.as_bytes();
+    let digits = |from: usize, count: usize| -> Option<u32> {
+        let part = s.get(from..from + count)?;
+        part.bytes()
+            .all(|b| b.is_ascii_digit())
+            .then(|| part.parse().ok())?
+    };
+
+    for start in 0..bytes.len() {
+        // A year starts a digit run, so "120240131" is not read as 2024-01-31 + junk
+        if start > 0 && bytes[start - 1].is_ascii_digit() {
+            continue;
+        }
+        let Some(year) = digits(start, 4).filter(|y| (1970..=2099).contains(y)) else {
+            continue;
+        };
+
+        let separator = bytes
+            .get(start + 4)
+            .copied()
+            .filter(|b| matches!(b, b'-' | b'_' | b'.' | b' '));
+        let (month_at, day_at, end) = match separator {
+            Some(sep) if bytes.get(start + 7) == Some(&sep) => (start + 5, start + 8, start + 10),
+            Some(_) => continue,
+            None => (start + 4, start + 6, start + 8),
+        };
+
+        let date = digits(month_at, 2)
+            .zip(digits(day_at, 2))
+            .and_then(|(month, day)| NaiveDate::from_ymd_opt(year as i32, month, day));
+        // A separated date must end there; a compact one may run on into a time
+        let ends_cleanly = separator.is_none() || !bytes.get(end).is_some_and(u8::is_ascii_digit);
+        if let Some(date) = date.filter(|_| ends_cleanly) {
+            return Some((date, start..end));
         }
     }
+    None
+}
 
+/// Strips dates found by `detect_date` (and a time glued to a compact one)
+fn remove_date_patterns(s: &str) -> String {
+    let mut cleaned = s.to_string();
+    while let Some((_, range)) = detect_date(&cleaned) {
+        let end = range.end
+            + cleaned[range.end..]
+                .bytes()
+                .take_while(|b| b.is_ascii_digit() || *b == b'-' || *b == b'_')
+                .count();
+        cleaned.replace_range(range.start..end, "");
+    }
     cleaned
 }
 
@@ -508,906 +586,135 @@ fn remove_number_suffixes(s: &str) -> String {
     result
 }
 
-/// Organizes files by similarity, moving them into appropriate folders
-pub fn organize_by_similarity(
-    base_path: &Path,
-    config: &SimilarityConfig,
-    move_skipped: bool,
-    logger: &mut dyn FnMut(&str),
-) -> io::Result<OrganizeResult> {
-    logger(&format!(
-        "Starting organization in: {}",
-        base_path.display()
-    ));
-
-    // Read all files
-    let entries: Vec<_> = fs::read_dir(base_path)?
-        .filter_map(|e| e.ok())
-        .filter(|e| e.path().is_file())
-        .collect();
-
-    logger(&format!("Found {} files to process", entries.len()));
-
-    let filenames: Vec<String> = entries
-        .iter()
-        .filter_map(|e| {
-            e.path()
-                .file_name()
-                .and_then(|n| n.to_str())
-                .map(|s| s.to_string())
-        })
-        .collect();
-
-    // Group files
-    logger("Analyzing file similarities...");
-    let groups = group_similar_files(&filenames, config);
-    logger(&format!("Identified {} file groups", groups.len()));
-
-    let mut files_moved = 0;
-    let mut folders_created = 0;
-    let mut files_skipped = 0;
-    let mut skipped_details = Vec::new();
-    let mut errors = Vec::new();
-
-    // Prepare skip folder if needed
-    let skip_folder = if move_skipped {
-        let skip_dir = base_path.join("tyr-skip");
-        if !skip_dir.exists() {
-            match fs::create_dir(&skip_dir) {
-                Ok(_) => {
-                    logger(&format!("Created skip folder: {}", skip_dir.display()));
-                    Some(skip_dir)
-                }
-                Err(e) => {
-                    let err_msg = format!("Failed to create skip folder: {}", e);
-                    logger(&err_msg);
-                    errors.push(err_msg);
-                    None
-                }
-            }
-        } else {
-            Some(skip_dir)
-        }
-    } else {
-        None
-    };
-
-    // Process each group
-    for group in groups {
-        // Handle single files
-        if group.files.len() < 2 {
-            for filename in &group.files {
-                // Check if it's a system file
-                if should_skip_file(filename) {
-                    skipped_details.push(SkippedFile {
-                        filename: filename.clone(),
-                        reason: SkipReason::SystemFile,
-                    });
-                    logger(&format!("Skipped system file: {}", filename));
-                } else {
-                    skipped_details.push(SkippedFile {
-                        filename: filename.clone(),
-                        reason: SkipReason::SingleFile,
-                    });
-                    logger(&format!("Skipped single file: {}", filename));
-                }
-                files_skipped += 1;
-
-                // Move to skip folder if enabled
-                if let Some(ref skip_dir) = skip_folder {
-                    let source = base_path.join(filename);
-                    let dest = skip_dir.join(filename);
-
-                    if let Err(e) = fs::rename(&source, &dest) {
-                        let err_msg =
-                            format!("Failed to move '{}' to skip folder: {}", filename, e);
-                        logger(&err_msg);
-                        errors.push(err_msg);
-                    } else {
-                        logger(&format!("Moved to skip folder: {}", filename));
-                    }
-                }
-            }
-            continue;
-        }
-
-        let folder_name = suggest_folder_name(&group);
-        let target_dir = base_path.join(&folder_name);
-
-        // Create folder if it doesn't exist
-        if !target_dir.exists() {
-            match fs::create_dir(&target_dir) {
-                Ok(_) => {
-                    folders_created += 1;
-                    logger(&format!("Created folder: {}", folder_name));
-                }
-                Err(e) => {
-                    let err_msg = format!("Failed to create folder '{}': {}", folder_name, e);
-                    logger(&err_msg);
-                    errors.push(err_msg);
-                    continue;
-                }
-            }
-        }
-
-        // Move files into the folder
-        for filename in &group.files {
-            let source = base_path.join(filename);
-            let dest = target_dir.join(filename);
-
-            // Handle naming conflicts
-            let final_dest = if dest.exists() {
-                match handle_naming_conflict(&dest) {
-                    Ok(path) => path,
-                    Err(e) => {
-                        let err_msg = format!("Naming conflict for '{}': {}", filename, e);
-                        logger(&err_msg);
-                        errors.push(err_msg);
-                        continue;
-                    }
-                }
-            } else {
-                dest
-            };
-
-            match fs::rename(&source, &final_dest) {
-                Ok(_) => {
-                    files_moved += 1;
-                    logger(&format!("Moved: {} -> {}", filename, folder_name));
-                }
-                Err(e) => {
-                    let err_msg = format!("Failed to move '{}': {}", filename, e);
-                    logger(&err_msg);
-                    errors.push(err_msg);
-                }
-            }
-        }
-    }
-
-    logger(&format!(
-        "Organization complete: {} files moved, {} folders created, {} files skipped",
-        files_moved, folders_created, files_skipped
-    ));
-
-    Ok(OrganizeResult {
-        files_moved,
-        folders_created,
-        files_skipped,
-        skipped_details,
-        errors,
-    })
-}
-
-/// Handles naming conflicts by appending a number
-fn handle_naming_conflict(path: &Path) -> io::Result<PathBuf> {
-    let parent = path.parent().unwrap();
-    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("file");
-    let extension = path
-        .extension()
-        .and_then(|e| e.to_str())
-        .map(|e| format!(".{}", e))
-        .unwrap_or_default();
-
-    for i in 1..1000 {
-        let new_name = format!("{}_{}{}", stem, i, extension);
-        let new_path = parent.join(new_name);
-        if !new_path.exists() {
-            return Ok(new_path);
-        }
-    }
-
-    Err(io::Error::new(
-        io::ErrorKind::AlreadyExists,
-        "Could not find available filename",
-    ))
-}
-
-// TUI Implementation for Filename Organization
-
-use std::sync::{Arc, Mutex};
-use std::time::Duration;
-
-/// TUI App for filename-based organization
-pub struct FilenameTuiApp {
-    base_path: PathBuf,
+/// Gives files with look-alike names a shared folder (`tyr -f`); the rest stay
+pub struct SimilarityOrganizer {
     config: SimilarityConfig,
-    state: FilenameAppState,
-    move_skipped_to_folder: bool,
-    groups: Vec<FileGroup>,
-    scroll_offset: usize,
-    log_messages: Arc<Mutex<Vec<String>>>,
-}
-
-enum FilenameAppState {
-    Ready,
-    Analyzing,
-    ReviewGroups,
-    Organizing,
-    Complete(OrganizeResult),
+    feedback: Arc<Feedback>,
 }
 
-impl FilenameTuiApp {
-    pub fn new(base_path: PathBuf, config: SimilarityConfig) -> Self {
+impl SimilarityOrganizer {
+    pub fn new(config: SimilarityConfig) -> Self {
         Self {
-            base_path,
             config,
-            state: FilenameAppState::Ready,
-            move_skipped_to_folder: false,
-            groups: Vec::new(),
-            scroll_offset: 0,
-            log_messages: Arc::new(Mutex::new(Vec::new())),
+            feedback: Arc::default(),
         }
     }
 
-    fn log(&self, message: &str) {
-        if let Ok(mut logs) = self.log_messages.lock() {
-            logs.push(message.to_string());
-        }
+    /// Groups and names with the user's earlier corrections in mind
+    pub fn with_feedback(mut self, feedback: Arc<Feedback>) -> Self {
+        self.feedback = feedback;
+        self
     }
+}
 
-    pub fn run(&mut self) -> io::Result<()> {
-        // Import crossterm types
-        use crossterm::{
-            event::{DisableMouseCapture, EnableMouseCapture},
-            execute,
-            terminal::{
-                disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
-            },
-        };
-        use std::io::stdout;
-
-        enable_raw_mode()?;
-        let mut stdout = stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-
-        // Import ratatui types
-        use ratatui::{backend::CrosstermBackend, Terminal};
-
-        let backend = CrosstermBackend::new(stdout);
-        let mut terminal = Terminal::new(backend)?;
-
-        let result = self.run_app(&mut terminal);
-
-        disable_raw_mode()?;
-        execute!(
-            terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture
-        )?;
-        terminal.show_cursor()?;
-
-        result
+impl Organizer for SimilarityOrganizer {
+    fn name(&self) -> &'static str {
+        "Filename similarity"
     }
 
-    fn run_app(
-        &mut self,
-        terminal: &mut ratatui::Terminal<ratatui::backend::CrosstermBackend<std::io::Stdout>>,
-    ) -> io::Result<()> {
-        use crossterm::event::{self, Event, KeyCode};
-
-        loop {
-            terminal.draw(|f| self.draw_ui(f))?;
-
-            if event::poll(Duration::from_millis(100))? {
-                if let Event::Key(key) = event::read()? {
-                    match key.code {
-                        KeyCode::Char('q') | KeyCode::Esc => {
-                            // Allow quitting from any state
-                            if matches!(self.state, FilenameAppState::Organizing) {
-                                // Don't quit while organizing
-                                continue;
-                            }
-                            self.log("User requested quit");
-                            break;
-                        }
-                        KeyCode::Char('a') => {
-                            if matches!(self.state, FilenameAppState::Ready) {
-                                self.analyze_files()?;
-                            }
-                        }
-                        KeyCode::Char('s') => {
-                            if matches!(self.state, FilenameAppState::ReviewGroups) {
-                                self.start_organization()?;
-                            }
-                        }
-                        KeyCode::Char('k') => {
-                            if matches!(self.state, FilenameAppState::ReviewGroups) {
-                                self.move_skipped_to_folder = !self.move_skipped_to_folder;
-                                self.log(&format!(
-                                    "Toggle skip folder: {}",
-                                    self.move_skipped_to_folder
-                                ));
-                            }
-                        }
-                        KeyCode::Char('r') => {
-                            if matches!(self.state, FilenameAppState::Complete(_)) {
-                                self.state = FilenameAppState::Ready;
-                                self.groups.clear();
-                                self.scroll_offset = 0;
-                                self.log("Reset to ready state");
-                            }
-                        }
-                        KeyCode::Up => {
-                            if self.scroll_offset > 0 {
-                                self.scroll_offset -= 1;
-                            }
-                        }
-                        KeyCode::Down => {
-                            self.scroll_offset += 1;
-                        }
-                        KeyCode::PageUp => {
-                            self.scroll_offset = self.scroll_offset.saturating_sub(10);
-                        }
-                        KeyCode::PageDown => {
-                            self.scroll_offset += 10;
-                        }
-                        _ => {}
-                    }
-                }
-            }
-        }
-        Ok(())
+    fn config_schema(&self) -> ConfigSchema {
+        ConfigSchema::of("similarity_config", &self.config)
     }
 
-    fn analyze_files(&mut self) -> io::Result<()> {
-        self.state = FilenameAppState::Analyzing;
-        self.log("Starting file analysis");
-
-        // Read directory
-        let entries: Vec<_> = fs::read_dir(&self.base_path)?
-            .filter_map(|e| e.ok())
-            .filter(|e| e.path().is_file())
-            .collect();
-
-        self.log(&format!("Found {} files", entries.len()));
+    fn learns(&self) -> bool {
+        true
+    }
 
-        let filenames: Vec<String> = entries
+    /// The score of every file against the group's first file, which it was compared with
+    fn explain(&self, files: &[PathBuf]) -> Vec<String> {
+        let mut names: Vec<String> = files
             .iter()
-            .filter_map(|e| {
-                e.path()
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .map(|s| s.to_string())
-            })
+            .filter_map(|f| Some(f.file_name()?.to_string_lossy().into_owned()))
             .collect();
-
-        self.groups = group_similar_files(&filenames, &self.config);
-        self.log(&format!("Grouped into {} clusters", self.groups.len()));
-        self.state = FilenameAppState::ReviewGroups;
-        self.scroll_offset = 0;
-        Ok(())
-    }
-
-    fn start_organization(&mut self) -> io::Result<()> {
-        self.state = FilenameAppState::Organizing;
-        self.log("Starting organization");
-
-        let log_messages = Arc::clone(&self.log_messages);
-        let mut logger = |msg: &str| {
-            if let Ok(mut logs) = log_messages.lock() {
-                logs.push(msg.to_string());
-            }
-        };
-
-        // Perform organization
-        let result = organize_by_similarity(
-            &self.base_path,
-            &self.config,
-            self.move_skipped_to_folder,
-            &mut logger,
-        )?;
-
-        self.state = FilenameAppState::Complete(result);
-        self.scroll_offset = 0;
-        Ok(())
-    }
-
-    fn draw_ui(&self, f: &mut ratatui::Frame) {
-        use ratatui::{
-            layout::{Constraint, Direction, Layout},
-            style::{Color, Modifier, Style},
-            widgets::{Block, Borders, Paragraph},
-        };
-
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3),
-                Constraint::Min(10),
-                Constraint::Length(3),
-            ])
-            .split(f.size());
-
-        // Title
-        let title = Paragraph::new(" Tyr - Filename Similarity Organizer")
-            .style(
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            )
-            .block(Block::default().borders(Borders::ALL));
-        f.render_widget(title, chunks[0]);
-
-        // Main content
-        match &self.state {
-            FilenameAppState::Ready => self.draw_ready_state(f, chunks[1]),
-            FilenameAppState::Analyzing => self.draw_analyzing_state(f, chunks[1]),
-            FilenameAppState::ReviewGroups => self.draw_review_state(f, chunks[1]),
-            FilenameAppState::Organizing => self.draw_organizing_state(f, chunks[1]),
-            FilenameAppState::Complete(result) => self.draw_complete_state(f, chunks[1], result),
-        }
-
-        // Controls
-        self.draw_controls(f, chunks[2]);
-    }
-
-    fn draw_ready_state(&self, f: &mut ratatui::Frame, area: ratatui::layout::Rect) {
-        use ratatui::{
-            style::{Color, Modifier, Style},
-            text::{Line, Span},
-            widgets::{Block, Borders, Paragraph},
-        };
-
-        let text = vec![
-            Line::from(""),
-            Line::from(Span::styled(
-                " Ready to Analyze Files",
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            )),
-            Line::from(""),
-            Line::from(vec![
-                Span::raw("Directory: "),
-                Span::styled(
-                    self.base_path.display().to_string(),
-                    Style::default().fg(Color::Yellow),
-                ),
-            ]),
-            Line::from(""),
-            Line::from(Span::styled(
-                "How it works:",
-                Style::default().fg(Color::Cyan),
-            )),
-            Line::from("  • Analyzes filename patterns using ML algorithms"),
-            Line::from("  • Groups similar files (e.g., IMG_001.jpg, IMG_002.jpg)"),
-            Line::from("  • Creates organized folders automatically"),
-            Line::from(""),
-            Line::from(Span::styled(
-                " Press 'a' to begin analysis",
-                Style::default().fg(Color::Green),
-            )),
-        ];
-
-        let widget =
-            Paragraph::new(text).block(Block::default().borders(Borders::ALL).title(" Status "));
-        f.render_widget(widget, area);
-    }
-
-    fn draw_analyzing_state(&self, f: &mut ratatui::Frame, area: ratatui::layout::Rect) {
-        use ratatui::{
-            style::{Color, Style},
-            widgets::{Block, Borders, Gauge},
-        };
-
-        let gauge = Gauge::default()
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title(" Analyzing Files "),
-            )
-            .gauge_style(Style::default().fg(Color::Yellow))
-            .label(" Scanning and grouping files...")
-            .percent(50);
-        f.render_widget(gauge, area);
-    }
-
-    fn draw_review_state(&self, f: &mut ratatui::Frame, area: ratatui::layout::Rect) {
-        use ratatui::{
-            style::{Color, Modifier, Style},
-            text::{Line, Span},
-            widgets::{Block, Borders, Paragraph, Wrap},
+        names.sort();
+        let Some((seed, others)) = names.split_first() else {
+            return Vec::new();
         };
 
+        let config = &self.config;
+        let weights = token_weights(&self.feedback, &config.stop_words);
         let mut lines = vec![
-            Line::from(Span::styled(
-                format!(
-                    "✓ Analysis Complete - Found {} file groups",
-                    self.groups.len()
-                ),
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            )),
-            Line::from(""),
-        ];
-
-        let grouped_count = self.groups.iter().filter(|g| g.files.len() > 1).count();
-        let single_count = self.groups.iter().filter(|g| g.files.len() == 1).count();
-
-        lines.push(Line::from(vec![
-            Span::raw(" Groups with 2+ files: "),
-            Span::styled(
-                grouped_count.to_string(),
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
+            format!("Every file was compared with {}:", seed),
+            format!(
+                "score = {:.2} × levenshtein + {:.2} × jaccard, grouped from {:.2}",
+                config.levenshtein_weight, config.jaccard_weight, config.min_similarity_score
             ),
-        ]));
-        lines.push(Line::from(vec![
-            Span::raw(" Single files: "),
-            Span::styled(single_count.to_string(), Style::default().fg(Color::Yellow)),
-        ]));
-        lines.push(Line::from(""));
-        lines.push(Line::from(Span::styled(
-            "Preview of groups:",
-            Style::default().fg(Color::Cyan),
-        )));
-        lines.push(Line::from(""));
-
-        // Show groups with scroll support
-        let multi_file_groups: Vec<_> = self.groups.iter().filter(|g| g.files.len() > 1).collect();
-        let visible_groups = multi_file_groups.iter().skip(self.scroll_offset).take(8);
-
-        for (i, group) in visible_groups.enumerate() {
-            let folder_name = suggest_folder_name(group);
-            lines.push(Line::from(vec![
-                Span::styled(
-                    format!("{}. ", i + 1 + self.scroll_offset),
-                    Style::default().fg(Color::Gray),
-                ),
-                Span::styled(
-                    folder_name,
-                    Style::default()
-                        .fg(Color::Green)
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Span::raw(format!(" ({} files, similarity: ", group.files.len())),
-                Span::styled(
-                    format!("{:.0}%", group.avg_similarity * 100.0),
-                    Style::default().fg(Color::Yellow),
-                ),
-                Span::raw(")"),
-            ]));
-
-            // Show first 2 files as examples
-            for (j, file) in group.files.iter().take(2).enumerate() {
-                lines.push(Line::from(format!(
-                    "   {} {}",
-                    if j == 0 { "├─" } else { "└─" },
-                    file
-                )));
-            }
-            if group.files.len() > 2 {
-                lines.push(Line::from(format!(
-                    "   ... and {} more",
-                    group.files.len() - 2
-                )));
-            }
-            lines.push(Line::from(""));
-        }
-
-        if multi_file_groups.len() > 8 + self.scroll_offset {
-            lines.push(Line::from(Span::styled(
-                format!(
-                    "▼ {} more groups (use ↑↓ to scroll)",
-                    multi_file_groups.len() - 8 - self.scroll_offset
-                ),
-                Style::default().fg(Color::Gray),
-            )));
-        }
-
-        lines.push(Line::from(""));
-        lines.push(Line::from(vec![
-            Span::raw(" Move skipped files to 'tyr-skip' folder: "),
-            Span::styled(
-                if self.move_skipped_to_folder {
-                    "YES ✓"
-                } else {
-                    "NO"
-                },
-                if self.move_skipped_to_folder {
-                    Style::default()
-                        .fg(Color::Green)
-                        .add_modifier(Modifier::BOLD)
-                } else {
-                    Style::default().fg(Color::Red)
-                },
-            ),
-        ]));
-
-        let widget = Paragraph::new(lines)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title(" Review Groups "),
-            )
-            .wrap(Wrap { trim: false });
-        f.render_widget(widget, area);
-    }
-
-    fn draw_organizing_state(&self, f: &mut ratatui::Frame, area: ratatui::layout::Rect) {
-        use ratatui::{
-            style::{Color, Style},
-            widgets::{Block, Borders, Gauge},
-        };
-
-        let gauge = Gauge::default()
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title(" Organizing Files "),
-            )
-            .gauge_style(Style::default().fg(Color::Cyan))
-            .label(" Moving files into folders...")
-            .percent(75);
-        f.render_widget(gauge, area);
-    }
-
-    fn draw_complete_state(
-        &self,
-        f: &mut ratatui::Frame,
-        area: ratatui::layout::Rect,
-        result: &OrganizeResult,
-    ) {
-        use ratatui::{
-            style::{Color, Modifier, Style},
-            text::{Line, Span},
-            widgets::{Block, Borders, Paragraph, Wrap},
-        };
-
-        let mut lines = vec![
-            Line::from(Span::styled(
-                "✦ Organization Complete!",
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            )),
-            Line::from(""),
-            Line::from(vec![
-                Span::raw(" Folders created: "),
-                Span::styled(
-                    result.folders_created.to_string(),
-                    Style::default()
-                        .fg(Color::Green)
-                        .add_modifier(Modifier::BOLD),
-                ),
-            ]),
-            Line::from(vec![
-                Span::raw(" Files moved: "),
-                Span::styled(
-                    result.files_moved.to_string(),
-                    Style::default()
-                        .fg(Color::Cyan)
-                        .add_modifier(Modifier::BOLD),
-                ),
-            ]),
-            Line::from(vec![
-                Span::raw(" Files skipped: "),
-                Span::styled(
-                    result.files_skipped.to_string(),
-                    Style::default().fg(Color::Yellow),
-                ),
-            ]),
-            Line::from(""),
         ];
-
-        if !result.skipped_details.is_empty() {
-            lines.push(Line::from(Span::styled(
-                "Skipped Files:",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            )));
-            lines.push(Line::from(""));
-
-            let visible_skipped = result
-                .skipped_details
-                .iter()
-                .skip(self.scroll_offset)
-                .take(8);
-
-            for skip in visible_skipped {
-                let (icon, reason_text) = match skip.reason {
-                    SkipReason::SingleFile => ("", "No similar matches found"),
-                    SkipReason::SystemFile => ("", "System file"),
-                    SkipReason::AlreadyOrganized => ("✓", "Already organized"),
-                };
-                lines.push(Line::from(vec![
-                    Span::raw(format!("  {} ", icon)),
-                    Span::styled(&skip.filename, Style::default().fg(Color::Gray)),
-                    Span::raw(" - "),
-                    Span::styled(reason_text, Style::default().fg(Color::Yellow)),
-                ]));
-            }
-
-            if result.skipped_details.len() > 8 + self.scroll_offset {
-                lines.push(Line::from(Span::styled(
-                    format!(
-                        "▼ {} more (use ↑↓ to scroll)",
-                        result.skipped_details.len() - 8 - self.scroll_offset
-                    ),
-                    Style::default().fg(Color::Gray),
-                )));
-            }
-            lines.push(Line::from(""));
+        if !weights.is_empty() {
+            lines.push(format!(
+                "jaccard weighs {} tokens differently after your corrections",
+                weights.len()
+            ));
         }
-
-        if !result.errors.is_empty() {
-            lines.push(Line::from(Span::styled(
-                "! Errors:",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-            )));
-            for error in result.errors.iter().take(5) {
-                lines.push(Line::from(format!("  • {}", error)));
+        for name in others {
+            if self.feedback.must_link(seed, name) {
+                lines.push(format!(
+                    "  {:30} kept with it by an earlier correction",
+                    name
+                ));
+                continue;
             }
-            if result.errors.len() > 5 {
-                lines.push(Line::from(format!(
-                    "  ... and {} more errors",
-                    result.errors.len() - 5
-                )));
+            let parts = breakdown(seed, name, config, &weights);
+            let verdict = if parts.score >= config.min_similarity_score {
+                "≥"
+            } else {
+                "<"
+            };
+            lines.push(format!(
+                "  {:30} lev {:.2}  jac {:.2}  → {:.2} {} {:.2}",
+                name,
+                parts.levenshtein,
+                parts.jaccard,
+                parts.score,
+                verdict,
+                config.min_similarity_score
+            ));
+            if !parts.shared.is_empty() {
+                lines.push(format!("  {:30} shared: {}", "", parts.shared.join(", ")));
             }
         }
 
-        let widget = Paragraph::new(lines)
-            .block(Block::default().borders(Borders::ALL).title(" Results "))
-            .wrap(Wrap { trim: false });
-        f.render_widget(widget, area);
-    }
-
-    fn draw_controls(&self, f: &mut ratatui::Frame, area: ratatui::layout::Rect) {
-        use ratatui::{
-            style::{Color, Style},
-            widgets::{Block, Borders, Paragraph},
+        let group = FileGroup {
+            representative_name: extract_common_prefix(&names),
+            files: names.clone(),
+            avg_similarity: 0.0,
         };
-
-        let controls = match &self.state {
-            FilenameAppState::Ready => "'a' Analyze | 'q' Quit",
-            FilenameAppState::ReviewGroups => {
-                "'s' Start Organization | 'k' Toggle Skip Folder | ↑↓ Scroll | 'q' Quit"
-            }
-            FilenameAppState::Complete(_) => "'r' Reset | ↑↓ Scroll | 'q' Quit",
-            FilenameAppState::Organizing => " Organizing... Please wait",
-            _ => " Please wait...",
-        };
-
-        let widget = Paragraph::new(controls)
-            .style(Style::default().fg(Color::Yellow))
-            .block(Block::default().borders(Borders::ALL).title(" Controls "));
-        f.render_widget(widget, area);
-    }
-
-    pub fn get_logs(&self) -> Vec<String> {
-        if let Ok(logs) = self.log_messages.lock() {
-            logs.clone()
-        } else {
-            Vec::new()
-        }
+        lines.push(match self.feedback.preferred_name(&names) {
+            Some(name) => format!("Named {} after a folder you named before", name),
+            None => format!(
+                "Named {} from the common prefix '{}'",
+                suggest_folder_name(&group, &self.feedback),
+                group.representative_name
+            ),
+        });
+        lines
     }
-    /// Auto-organize files without UI interaction
-    /// Automatically analyzes (press 'a') then organizes (press 's')
-    pub fn auto_organize(&mut self) -> io::Result<()> {
-        // println!("Analyzing files...");
-
-        // Step 1: Analyze files (equivalent to pressing 'a')
-        self.analyze_files()?;
-
-        // Display analysis results
-        if matches!(self.state, FilenameAppState::ReviewGroups) {
-            let grouped_count = self.groups.iter().filter(|g| g.files.len() > 1).count();
-            let single_count = self.groups.iter().filter(|g| g.files.len() == 1).count();
-            let total_files: usize = self.groups.iter().map(|g| g.files.len()).sum();
-
-            println!("\n✓ Analysis Complete!");
-            println!("   • Total files:        {}", total_files);
-            println!("   • Groups with 2+ files: {}", grouped_count);
-            println!("   • Single files:       {}", single_count);
-
-            // Show preview of groups
-            // if grouped_count > 0 {
-            //     println!("\Preview of file groups:");
-            //     let multi_file_groups: Vec<_> = self.groups.iter()
-            //         .filter(|g| g.files.len() > 1)
-            //         .collect();
-
-            //     for (i, group) in multi_file_groups.iter().take(5).enumerate() {
-            //         let folder_name = suggest_folder_name(group);
-            //         println!("   {}. {} ({} files, similarity: {:.0}%)",
-            //             i + 1,
-            //             folder_name,
-            //             group.files.len(),
-            //             group.avg_similarity * 100.0
-            //         );
-
-            //         // Show first 2 files
-            //         for (j, file) in group.files.iter().take(2).enumerate() {
-            //             println!("      {} {}",
-            //                 if j == 0 { "├─" } else { "└─" },
-            //                 file
-            //             );
-            //         }
-            //         if group.files.len() > 2 {
-            //             println!("      ... and {} more", group.files.len() - 2);
-            //         }
-            //     }
-
-            //     if multi_file_groups.len() > 5 {
-            //         println!("   ... and {} more groups", multi_file_groups.len() - 5);
-            //     }
-            // }
-
-            // println!("\nStarting organization...");
-        }
 
-        // Step 2: Start organization (equivalent to pressing 's')
-        self.start_organization()?;
-
-        // Display organization results
-        if let FilenameAppState::Complete(result) = &self.state {
-            println!("\n✦ Organization Complete!\n");
-            println!("Summary:");
-            println!("   • Folders created: {}", result.folders_created);
-            println!("   • Files moved:     {}", result.files_moved);
-            println!("   • Files skipped:   {}", result.files_skipped);
-
-            // Show skipped files summary
-            if !result.skipped_details.is_empty() {
-                println!("\n[!]  Skipped Files:");
-
-                let single_files = result
-                    .skipped_details
-                    .iter()
-                    .filter(|s| matches!(s.reason, SkipReason::SingleFile))
-                    .count();
-                let system_files = result
-                    .skipped_details
-                    .iter()
-                    .filter(|s| matches!(s.reason, SkipReason::SystemFile))
-                    .count();
-                let already_organized = result
-                    .skipped_details
-                    .iter()
-                    .filter(|s| matches!(s.reason, SkipReason::AlreadyOrganized))
-                    .count();
-
-                if single_files > 0 {
-                    println!("   • No similar matches: {}", single_files);
-                }
-                if system_files > 0 {
-                    println!("   • System files:       {}", system_files);
-                }
-                if already_organized > 0 {
-                    println!("   • Already organized:  {}", already_organized);
-                }
-
-                // Show first few skipped files
-                if result.skipped_details.len() <= 5 {
-                    println!("\n   Files:");
-                    for skip in &result.skipped_details {
-                        let reason_text = match skip.reason {
-                            SkipReason::SingleFile => "no matches",
-                            SkipReason::SystemFile => "system file",
-                            SkipReason::AlreadyOrganized => "already organized",
-                        };
-                        println!("   • {} ({})", skip.filename, reason_text);
-                    }
-                } else {
-                    println!("   (Use logs for full details)");
-                }
-            }
+    fn analyze(&self, files: &[PathBuf]) -> io::Result<Assignments> {
+        let by_name: HashMap<&str, &PathBuf> = files
+            .iter()
+            .filter_map(|f| Some((f.file_name()?.to_str()?, f)))
+            .collect();
+        let mut filenames: Vec<String> = by_name.keys().map(|n| n.to_string()).collect();
+        filenames.sort();
 
-            // Show errors if any
-            if !result.errors.is_empty() {
-                println!("\nx Errors:");
-                for (i, error) in result.errors.iter().enumerate().take(3) {
-                    println!("   {}. {}", i + 1, error);
-                }
-                if result.errors.len() > 3 {
-                    println!("   ... and {} more errors", result.errors.len() - 3);
+        let mut assignments = Assignments::new();
+        for group in group_similar_files(&filenames, &self.config, &self.feedback) {
+            let target = if group.files.len() >= 2 {
+                Target::Folder(suggest_folder_name(&group, &self.feedback))
+            } else {
+                Target::Keep
+            };
+            for name in &group.files {
+                if let Some(file) = by_name.get(name.as_str()) {
+                    assignments.push(Assignment {
+                        file: (*file).clone(),
+                        target: target.clone(),
+                    });
                 }
             }
         }
-
-        Ok(())
+        assignments.sort();
+        Ok(assignments)
     }
 }
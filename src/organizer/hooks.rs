@@ -0,0 +1,321 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for the commands run before, during and after organizing for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::path::Path;
+use std::process::Command;
+use std::sync::LazyLock;
+
+use crate::error::TyrError;
+
+/// Shell commands run around a run (`[hooks]`); empty ones are skipped
+///
+/// Every value is also in the hook's environment (`TYR_SRC`, `TYR_DST`, `TYR_DIR`,
+/// `TYR_MOVED`, `TYR_FAILED`). `{variables}` in the command never splice a path into it as
+/// text the shell parses: `sh` gets it single-quoted, `cmd` a `"!TYR_SRC!"` reference that
+/// is expanded after parsing. Hooks are only run for runs that have something to do.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct HooksConfig {
+    /// Before the first file moves, with `{dir}`; the run is aborted when it fails
+    pub pre_run: String,
+    /// After every file moved or copied, with `{src}`, `{dst}` and `{dir}`
+    pub post_move: String,
+    /// After the run, with `{dir}`, `{moved}` and `{failed}`
+    pub post_run: String,
+}
+
+const PRE_RUN: [&str; 1] = ["dir"];
+const POST_MOVE: [&str; 3] = ["src", "dst", "dir"];
+const POST_RUN: [&str; 3] = ["dir", "moved", "failed"];
+
+impl HooksConfig {
+    /// `{words}` that are not variables of their hook, as messages for `tyr config check`
+    ///
+    /// Other braces are left to the shell (`${HOME}`, `awk '{print $1}'`).
+    pub fn validate(&self) -> Vec<String> {
+        static PLACEHOLDER: LazyLock<Regex> =
+            LazyLock::new(|| Regex::new(r"(?:^|[^$])\{([a-z_]+)\}").unwrap());
+
+        let hooks = [
+            ("pre_run", &self.pre_run, &PRE_RUN[..]),
+            ("post_move", &self.post_move, &POST_MOVE[..]),
+            ("post_run", &self.post_run, &POST_RUN[..]),
+        ];
+        let mut problems = Vec::new();
+        for (name, command, variables) in hooks {
+            for caps in PLACEHOLDER.captures_iter(command) {
+                if !variables.contains(&&caps[1]) {
+                    problems.push(format!(
+                        "{}: unknown variable {{{}}} (available: {})",
+                        name,
+                        &caps[1],
+                        variables
+                            .iter()
+                            .map(|v| format!("{{{}}}", v))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ));
+                }
+            }
+        }
+        problems
+    }
+
+    /// Takes the hooks left empty here from `fallback`
+    pub fn fill_from(&mut self, fallback: &HooksConfig) {
+        let hooks = [
+            (&mut self.pre_run, &fallback.pre_run),
+            (&mut self.post_move, &fallback.post_move),
+            (&mut self.post_run, &fallback.post_run),
+        ];
+        for (command, fallback) in hooks {
+            if command.is_empty() {
+                command.clone_from(fallback);
+            }
+        }
+    }
+
+    pub fn pre_run(&self, dir: &Path) -> Result<(), TyrError> {
+        run("pre_run", &self.pre_run, &[("dir", Value::Path(dir))])
+    }
+
+    pub fn post_move(&self, dir: &Path, src: &Path, dst: &Path) -> Result<(), TyrError> {
+        run(
+            "post_move",
+            &self.post_move,
+            &[
+                ("src", Value::Path(src)),
+                ("dst", Value::Path(dst)),
+                ("dir", Value::Path(dir)),
+            ],
+        )
+    }
+
+    pub fn post_run(&self, dir: &Path, moved: usize, failed: usize) -> Result<(), TyrError> {
+        run(
+            "post_run",
+            &self.post_run,
+            &[
+                ("dir", Value::Path(dir)),
+                ("moved", Value::Count(moved)),
+                ("failed", Value::Count(failed)),
+            ],
+        )
+    }
+}
+
+/// What a hook variable stands for
+#[derive(Clone, Copy)]
+enum Value<'a> {
+    Path(&'a Path),
+    Count(usize),
+}
+
+impl Value<'_> {
+    fn as_os_string(self) -> OsString {
+        match self {
+            Value::Path(path) => path.as_os_str().to_os_string(),
+            Value::Count(count) => count.to_string().into(),
+        }
+    }
+
+    /// What `{name}` becomes in the command text
+    fn placeholder(self, name: &str) -> String {
+        match self {
+            Value::Path(path) => quote_path(name, path),
+            Value::Count(count) => count.to_string(),
+        }
+    }
+}
+
+/// Runs one hook through the shell and waits for it; its output is captured, not shown,
+/// so it cannot scribble over the TUI
+fn run(hook: &'static str, command: &str, variables: &[(&str, Value)]) -> Result<(), TyrError> {
+    if command.trim().is_empty() {
+        return Ok(());
+    }
+    let failed = |message: String| TyrError::HookFailed { hook, message };
+    let placeholders: Vec<(&str, String)> = variables
+        .iter()
+        .map(|&(name, value)| (name, value.placeholder(name)))
+        .collect();
+    let expanded = expand(command, &placeholders);
+
+    let mut shell = shell(&expanded);
+    for &(name, value) in variables {
+        shell.env(env_name(name), value.as_os_string());
+    }
+    let output = shell
+        .output()
+        .map_err(|e| failed(format!("could not start the shell: {}", e)))?;
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let status = match output.status.code() {
+        Some(code) => format!("exit code {}", code),
+        None => "killed by a signal".to_string(),
+    };
+    Err(failed(
+        match stderr.lines().rev().find(|l| !l.trim().is_empty()) {
+            Some(last) => format!("{}: {}", status, last.trim()),
+            None => status,
+        },
+    ))
+}
+
+/// Replaces the `{variables}` of `command` in one pass, so a value is never scanned again
+/// (a file named `{dst}.pdf` stays that name); other braces are left to the shell
+fn expand(command: &str, variables: &[(&str, String)]) -> String {
+    let mut expanded = String::with_capacity(command.len());
+    let mut rest = command;
+    while let Some(open) = rest.find('{') {
+        expanded.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let value = after.find('}').and_then(|close| {
+            let name = &after[..close];
+            let quoted = !expanded.ends_with('$');
+            variables
+                .iter()
+                .find(|(variable, _)| quoted && *variable == name)
+                .map(|(_, value)| (value, close))
+        });
+        match value {
+            Some((value, close)) => {
+                expanded.push_str(value);
+                rest = &after[close + 1..];
+            }
+            None => {
+                expanded.push('{');
+                rest = after;
+            }
+        }
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+/// Delayed expansion (`/V:ON`) lets `!TYR_SRC!` stand in for a path without cmd parsing it;
+/// the command is passed as is, since cmd does not follow the usual argument quoting
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    use std::os::windows::process::CommandExt;
+
+    let mut shell = Command::new("cmd");
+    shell
+        .args(["/D", "/V:ON", "/S", "/C"])
+        .raw_arg(format!("\"{}\"", command));
+    shell
+}
+
+/// `src` -> `TYR_SRC`
+fn env_name(name: &str) -> String {
+    format!("TYR_{}", name.to_uppercase())
+}
+
+/// `it's here.pdf` -> `'it'\''s here.pdf'`
+#[cfg(unix)]
+fn quote_path(_name: &str, path: &Path) -> String {
+    format!("'{}'", path.to_string_lossy().replace('\'', r"'\''"))
+}
+
+/// `{src}` -> `"!TYR_SRC!"`; the path itself (which may hold `%`, `&` or `^`) never
+/// reaches cmd's parser
+#[cfg(windows)]
+fn quote_path(name: &str, _path: &Path) -> String {
+    format!("\"!{}!\"", env_name(name))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_hooks_quote_paths_and_report_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("log.txt");
+        let hooks = HooksConfig {
+            pre_run: "echo \"${TYR_UNSET:-index} busy\" >&2; exit 4".to_string(),
+            post_move: format!(
+                "printf '%s -> %s\\n' {{src}} {{dst}} >> '{}'",
+                log.display()
+            ),
+            post_run: String::new(),
+        };
+        assert!(hooks.validate().is_empty());
+
+        let src = dir.path().join("it's here.pdf");
+        let dst = dir.path().join("Documents/it's here.pdf");
+        hooks.post_move(dir.path(), &src, &dst).unwrap();
+        assert_eq!(
+            fs::read_to_string(&log).unwrap(),
+            format!("{} -> {}\n", src.display(), dst.display())
+        );
+
+        let err = hooks.pre_run(dir.path()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "pre_run hook failed: exit code 4: index busy"
+        );
+        assert!(hooks.post_run(dir.path(), 1, 0).is_ok());
+    }
+
+    #[test]
+    fn test_hook_values_are_not_expanded_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("log.txt");
+        let marker = dir.path().join("injected");
+        let hooks = HooksConfig {
+            post_move: format!("echo {{src}} {{dst}} ${{HOME:+x}} >> '{}'", log.display()),
+            ..HooksConfig::default()
+        };
+
+        let name = format!("$(touch '{}'){{dst}}.pdf", marker.display());
+        let src = dir.path().join(&name);
+        let dst = dir.path().join("Documents").join(&name);
+        hooks.post_move(dir.path(), &src, &dst).unwrap();
+        assert!(!marker.exists());
+        assert_eq!(
+            fs::read_to_string(&log).unwrap(),
+            format!("{} {} x\n", src.display(), dst.display())
+        );
+    }
+
+    #[test]
+    fn test_hook_values_are_in_the_environment() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("log.txt");
+        let hooks = HooksConfig {
+            post_run: format!(
+                "printf '%s %s %s' \"$TYR_DIR\" \"$TYR_MOVED\" \"$TYR_FAILED\" > '{}'",
+                log.display()
+            ),
+            ..HooksConfig::default()
+        };
+
+        let target = dir.path().join("100% & done");
+        hooks.post_run(&target, 3, 1).unwrap();
+        assert_eq!(
+            fs::read_to_string(&log).unwrap(),
+            format!("{} 3 1", target.display())
+        );
+    }
+}
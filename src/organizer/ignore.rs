@@ -0,0 +1,320 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for gitignore-style skip patterns and .tyrignore files for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use regex::Regex;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Name of the ignore file read from the target directory, its ancestors and, in recursive
+/// walks, its subfolders
+pub const IGNORE_FILE_NAME: &str = ".tyrignore";
+
+/// Files tyr keeps next to the organized files; they are never moved
+const ALWAYS_IGNORED: [&str; 2] = [IGNORE_FILE_NAME, crate::config::PROJECT_CONFIG_NAME];
+
+/// One compiled gitignore line
+#[derive(Debug)]
+struct IgnoreRule {
+    /// Directory paths are matched relative to
+    base: PathBuf,
+    /// Path from the .tyrignore's directory down to `base` ("" when they are the same)
+    prefix: String,
+    regex: Regex,
+    negated: bool,
+    dir_only: bool,
+}
+
+/// Decides which files are skipped, using gitignore semantics
+///
+/// Supported: `*`, `?`, `[abc]`, `**`, `!negation`, `/anchored` patterns and `dir/` patterns
+/// that only match directories. As in git, the last matching rule wins and a file inside an
+/// ignored directory cannot be re-included.
+#[derive(Debug, Default)]
+pub struct IgnoreMatcher {
+    rules: Vec<IgnoreRule>,
+}
+
+impl IgnoreMatcher {
+    /// Compiles patterns relative to `base`; blank lines and `#` comments are skipped
+    pub fn from_patterns<S: AsRef<str>>(base: &Path, patterns: &[S]) -> Self {
+        let mut matcher = Self::default();
+        matcher.add_patterns(base, "", patterns);
+        matcher
+    }
+
+    /// Matcher for organizing `dir`: config `skip_patterns` first, then every `.tyrignore`
+    /// from the filesystem root down to `dir`, so the nearest file has the final say
+    pub fn for_directory(dir: &Path, skip_patterns: &[String]) -> io::Result<Self> {
+        let mut matcher = Self::from_patterns(dir, skip_patterns);
+
+        let canonical = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+        let mut ignore_dirs: Vec<&Path> = canonical.ancestors().collect();
+        ignore_dirs.reverse();
+
+        for ignore_dir in ignore_dirs {
+            let ignore_file = ignore_dir.join(IGNORE_FILE_NAME);
+            if !ignore_file.is_file() {
+                continue;
+            }
+            let content = fs::read_to_string(&ignore_file)?;
+            let lines: Vec<&str> = content.lines().collect();
+
+            // Paths are always given under `dir` (as the caller spells it), so an ancestor's
+            // patterns see them with the folders in between put in front
+            let prefix = canonical
+                .strip_prefix(ignore_dir)
+                .map(slash_path)
+                .unwrap_or_default();
+            matcher.add_patterns(dir, &prefix, &lines);
+        }
+
+        Ok(matcher)
+    }
+
+    /// Adds the `.tyrignore` of `dir`, a subfolder a recursive walk has just reached
+    ///
+    /// As in git, its rules only apply below `dir` and win over those of the folders above.
+    pub fn enter(&mut self, dir: &Path) -> io::Result<()> {
+        let ignore_file = dir.join(IGNORE_FILE_NAME);
+        if ignore_file.is_file() {
+            let content = fs::read_to_string(&ignore_file)?;
+            let lines: Vec<&str> = content.lines().collect();
+            self.add_patterns(dir, "", &lines);
+        }
+        Ok(())
+    }
+
+    fn add_patterns<S: AsRef<str>>(&mut self, base: &Path, prefix: &str, patterns: &[S]) {
+        self.rules.extend(
+            patterns
+                .iter()
+                .filter_map(|p| compile_pattern(base, prefix, p.as_ref())),
+        );
+    }
+
+    /// Returns true if `path` should be left alone
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if ALWAYS_IGNORED.contains(&filename) {
+            return true;
+        }
+
+        // A parent directory that is ignored hides everything below it
+        let mut parent = path.parent();
+        while let Some(dir) = parent {
+            if self.matches(dir, true) == Some(true) {
+                return true;
+            }
+            parent = dir.parent();
+        }
+
+        self.matches(path, is_dir).unwrap_or(false)
+    }
+
+    /// Some(ignored) from the last rule that matches, None if no rule does
+    fn matches(&self, path: &Path, is_dir: bool) -> Option<bool> {
+        self.rules
+            .iter()
+            .rev()
+            .filter(|rule| is_dir || !rule.dir_only)
+            .find(|rule| relative_path(rule, path).is_some_and(|rel| rule.regex.is_match(&rel)))
+            .map(|rule| !rule.negated)
+    }
+}
+
+/// `path` as the rule's .tyrignore sees it, or None if it is outside the rule's base
+fn relative_path(rule: &IgnoreRule, path: &Path) -> Option<String> {
+    let rel = path.strip_prefix(&rule.base).ok()?;
+    if rel.as_os_str().is_empty() {
+        return None;
+    }
+
+    if rule.prefix.is_empty() {
+        Some(slash_path(rel))
+    } else {
+        Some(format!("{}/{}", rule.prefix, slash_path(rel)))
+    }
+}
+
+/// Joins path components with `/` on every platform
+fn slash_path(path: &Path) -> String {
+    let parts: Vec<String> = path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    parts.join("/")
+}
+
+fn compile_pattern(base: &Path, prefix: &str, line: &str) -> Option<IgnoreRule> {
+    let mut pattern = line.trim_end();
+    if pattern.is_empty() || pattern.starts_with('#') {
+        return None;
+    }
+
+    // `!` negates; `\!` and `\#` are a literal leading `!` or `#`
+    let negated = pattern.starts_with('!');
+    let escaped = pattern.starts_with("\\!") || pattern.starts_with("\\#");
+    if negated || escaped {
+        pattern = &pattern[1..];
+    }
+
+    let dir_only = pattern.ends_with('/');
+    let pattern = pattern.trim_end_matches('/');
+    if pattern.is_empty() {
+        return None;
+    }
+
+    // A slash anywhere but the end ties the pattern to `base`; otherwise it matches a name
+    // at any depth
+    let anchored = pattern.contains('/');
+    let pattern = pattern.trim_start_matches('/');
+
+    let body = glob_to_regex(pattern);
+    let regex = if anchored {
+        format!("^{}$", body)
+    } else {
+        format!("^(?:.*/)?{}$", body)
+    };
+
+    Some(IgnoreRule {
+        base: base.to_path_buf(),
+        prefix: prefix.to_string(),
+        regex: Regex::new(&regex).ok()?,
+        negated,
+        dir_only,
+    })
+}
+
+fn glob_to_regex(glob: &str) -> String {
+    let chars: Vec<char> = glob.chars().collect();
+    let mut regex = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                let at_start = i == 0 || chars[i - 1] == '/';
+                if at_start && chars.get(i + 2) == Some(&'/') {
+                    // `**/` matches zero or more directories
+                    regex.push_str("(?:.*/)?");
+                    i += 3;
+                } else {
+                    regex.push_str(".*");
+                    i += 2;
+                }
+                continue;
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            '[' => match chars[i + 1..].iter().position(|&c| c == ']') {
+                Some(len) => {
+                    let class: String = chars[i + 1..i + 1 + len].iter().collect();
+                    let class = match class.strip_prefix('!') {
+                        Some(rest) => format!("^{}", rest),
+                        None => class,
+                    };
+                    regex.push('[');
+                    regex.push_str(&class.replace('\\', "\\\\"));
+                    regex.push(']');
+                    i += len + 2;
+                    continue;
+                }
+                None => regex.push_str("\\["),
+            },
+            '\\' if i + 1 < chars.len() => {
+                regex.push_str(&regex::escape(&chars[i + 1].to_string()));
+                i += 2;
+                continue;
+            }
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+        i += 1;
+    }
+
+    regex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ignored(matcher: &IgnoreMatcher, base: &Path, rel: &str, is_dir: bool) -> bool {
+        matcher.is_ignored(&base.join(rel), is_dir)
+    }
+
+    #[test]
+    fn test_globs_match_whole_names_only() {
+        let base = Path::new("/data");
+        let matcher = IgnoreMatcher::from_patterns(base, &[".git", "*.tmp", "~$*"]);
+
+        assert!(ignored(&matcher, base, ".git", false));
+        assert!(!ignored(&matcher, base, "my.github-notes.txt", false));
+        assert!(ignored(&matcher, base, "scratch.tmp", false));
+        assert!(!ignored(&matcher, base, "scratch.tmp.txt", false));
+        assert!(ignored(&matcher, base, "~$report.docx", false));
+        assert!(ignored(&matcher, base, ".tyrignore", false));
+    }
+
+    #[test]
+    fn test_negation_anchoring_and_directories() {
+        let base = Path::new("/data");
+        let matcher = IgnoreMatcher::from_patterns(
+            base,
+            &["*.log", "!keep.log", "/build", "cache/", "docs/**/*.bak"],
+        );
+
+        assert!(ignored(&matcher, base, "app.log", false));
+        assert!(!ignored(&matcher, base, "keep.log", false));
+        assert!(ignored(&matcher, base, "build", false));
+        assert!(!ignored(&matcher, base, "src/build", false));
+        assert!(!ignored(&matcher, base, "cache", false));
+        assert!(ignored(&matcher, base, "cache", true));
+        assert!(ignored(&matcher, base, "cache/file.txt", false));
+        assert!(ignored(&matcher, base, "docs/a/b/old.bak", false));
+        assert!(ignored(&matcher, base, "docs/old.bak", false));
+    }
+
+    #[test]
+    fn test_tyrignore_files_are_loaded_from_ancestors() {
+        let root = tempfile::tempdir().unwrap();
+        let nested = root.path().join("inbox");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(root.path().join(IGNORE_FILE_NAME), "*.iso\n").unwrap();
+        fs::write(nested.join(IGNORE_FILE_NAME), "!ubuntu.iso\n").unwrap();
+
+        let matcher = IgnoreMatcher::for_directory(&nested, &["*.part".to_string()]).unwrap();
+
+        assert!(matcher.is_ignored(&nested.join("windows.iso"), false));
+        assert!(!matcher.is_ignored(&nested.join("ubuntu.iso"), false));
+        assert!(matcher.is_ignored(&nested.join("movie.mkv.part"), false));
+    }
+
+    #[test]
+    fn test_tyrignore_files_in_subfolders_apply_below_them() {
+        let root = tempfile::tempdir().unwrap();
+        let base = root.path();
+        let photos = base.join("photos");
+        let raw = photos.join("raw");
+        fs::create_dir_all(&raw).unwrap();
+        fs::write(base.join(IGNORE_FILE_NAME), "*.tmp\n").unwrap();
+        fs::write(photos.join(IGNORE_FILE_NAME), "*.jpg\n!keep.tmp\n/raw/\n").unwrap();
+
+        let mut matcher = IgnoreMatcher::for_directory(base, &[]).unwrap();
+        matcher.enter(&photos).unwrap();
+
+        assert!(matcher.is_ignored(&photos.join("beach.jpg"), false));
+        assert!(!matcher.is_ignored(&base.join("beach.jpg"), false));
+        assert!(!matcher.is_ignored(&base.join("other/beach.jpg"), false));
+        assert!(matcher.is_ignored(&photos.join("scratch.tmp"), false));
+        assert!(!matcher.is_ignored(&photos.join("keep.tmp"), false));
+        assert!(matcher.is_ignored(&raw, true));
+        assert!(matcher.is_ignored(&raw.join("a.cr2"), false));
+    }
+}
@@ -7,37 +7,34 @@
  * This file is responsible for executing intelligent file organazing for
  * tyr(https:github.com/Nurysso/tyr) project
 */
-use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
-use ratatui::{
-    backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
-    text::{Line, Span},
-    widgets::{Block, Borders, Gauge, List, ListItem, Paragraph},
-    Terminal,
-};
 use rayon::prelude::*;
-use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::io::{self, stdout};
+use std::io;
 use std::path::{Path, PathBuf};
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+
+use super::feedback::Feedback;
+use super::sniff;
+use super::strategy::{Assignment, Assignments, ConfigSchema, Organizer, Target};
+use super::tokenize;
 
 /// Configuration for intelligent grouping
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
 pub struct IntelligentConfig {
     pub max_lines_to_read: usize,
     pub min_cluster_size: usize,
     pub max_clusters: usize,
     pub filename_similarity_weight: f64,
     pub content_similarity_weight: f64,
-    pub similarity_threshold: f64,
+    /// Deprecated, never used; still accepted so old tyr.toml files load without warnings
+    #[serde(default, skip_serializing)]
+    pub similarity_threshold: Option<f64>,
     pub max_iterations: usize,
+    /// Languages whose stop words are dropped before building TF-IDF vectors
+    pub stop_words: Vec<String>,
 }
 
 impl Default for IntelligentConfig {
@@ -48,8 +45,9 @@ impl Default for IntelligentConfig {
             max_clusters: 20,
             filename_similarity_weight: 0.3,
             content_similarity_weight: 0.7,
-            similarity_threshold: 0.65,
+            similarity_threshold: None,
             max_iterations: 100,
+            stop_words: Vec::new(),
         }
     }
 }
@@ -60,13 +58,14 @@ pub struct FileFeatures {
     pub path: PathBuf,
     pub filename_vector: Vec<f64>,
     pub content_vector: Option<Vec<f64>>,
-    pub is_text: bool,
 }
 
 /// Result of clustering operation
 #[derive(Debug, Clone)]
 pub struct ClusterResult {
     pub groups: Vec<FileGroup>,
+    /// Clusters k-means was asked for; smaller ones than `min_cluster_size` are dropped
+    pub k: usize,
 }
 
 /// A group of similar files
@@ -74,89 +73,76 @@ pub struct ClusterResult {
 pub struct FileGroup {
     pub files: Vec<PathBuf>,
     pub suggested_name: String,
-    pub centroid: Vec<f64>,
+    /// Heaviest words of the members' mean TF-IDF vector, for explaining the group
+    pub top_terms: Vec<(String, f64)>,
+    /// Members whose content was read
+    pub text_files: usize,
+    pub named_from: NameSource,
+}
+
+/// Where a cluster's name came from, for `--explain`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameSource {
+    /// A folder the user named before (see `Feedback::preferred_name`)
+    Feedback,
+    /// The cluster's top TF-IDF terms, blended with words of the file names
+    Content,
+    /// The file names alone: a common prefix, a common word or the extension
+    Filenames,
 }
 
 /// TF-IDF vocabulary and document vectors
 #[derive(Debug)]
-struct TfIdfModel {
-    vocabulary: Vec<String>,
-    idf: Vec<f64>,
+pub(crate) struct TfIdfModel {
+    pub(crate) vocabulary: Vec<String>,
+    pub(crate) idf: Vec<f64>,
+    stop_words: Vec<String>,
 }
 
-/// Progress callback type
-type ProgressCallback = Box<dyn Fn(String) + Send>;
-
-/// Main entry point for intelligent file organization
-pub fn organize_files_intelligently(
-    directory: &Path,
+/// Clusters an explicit list of files (used when grouping inside a category folder)
+///
+/// Corrections in `feedback` constrain the clustering and name the groups.
+pub fn cluster_files(
+    files: &[PathBuf],
     config: &IntelligentConfig,
-    progress_callback: Option<ProgressCallback>,
+    feedback: &Feedback,
+    send_progress: &dyn Fn(String),
 ) -> Result<ClusterResult, io::Error> {
-    let send_progress = |msg: String| {
-        if let Some(ref callback) = progress_callback {
-            callback(msg);
-        }
-    };
-
-    send_progress("Scanning directory for files...".to_string());
-
-    // Step 1: Traverse directory and collect files
-    let files = collect_files(directory)?;
-
     if files.is_empty() {
-        return Ok(ClusterResult { groups: Vec::new() });
+        return Ok(ClusterResult {
+            groups: Vec::new(),
+            k: 0,
+        });
     }
 
-    send_progress(format!("Found {} files to analyze", files.len()));
-
     // Step 2: Extract features from each file
     send_progress("Extracting features...".to_string());
-    let file_features = extract_features(&files, config)?;
+    let (file_features, tfidf_model) = extract_features(files, config)?;
 
     // Step 3: Perform clustering
     send_progress("Clustering files...".to_string());
-    let clusters = perform_clustering(&file_features, config)?;
+    let (clusters, k) = perform_clustering(&file_features, config, feedback)?;
 
     // Step 4: Generate group names
     send_progress("Generating group names...".to_string());
-    let groups = generate_group_names(clusters, &file_features);
+    let groups = generate_group_names(
+        clusters,
+        &file_features,
+        tfidf_model.as_ref(),
+        config,
+        feedback,
+    );
 
     send_progress(format!("✓ Created {} groups", groups.len()));
 
-    Ok(ClusterResult { groups })
+    Ok(ClusterResult { groups, k })
 }
 
-/// Recursively collect all files from directory (excluding hidden files and directories)
-fn collect_files(directory: &Path) -> Result<Vec<PathBuf>, io::Error> {
-    let mut files = Vec::new();
-
-    if directory.is_dir() {
-        for entry in fs::read_dir(directory)? {
-            let entry = entry?;
-            let path = entry.path();
-
-            // Skip hidden files/directories
-            if let Some(name) = path.file_name() {
-                if name.to_string_lossy().starts_with('.') {
-                    continue;
-                }
-            }
-
-            if path.is_file() {
-                files.push(path);
-            }
-        }
-    }
-
-    Ok(files)
-}
-
-/// Extract features from files
+/// Extract features from files, with the TF-IDF model of their contents if any had text
 fn extract_features(
     files: &[PathBuf],
     config: &IntelligentConfig,
-) -> Result<Vec<FileFeatures>, io::Error> {
+) -> Result<(Vec<FileFeatures>, Option<TfIdfModel>), io::Error> {
     // First, identify text files and read their content
     let file_contents: Vec<(PathBuf, Option<String>)> = files
         .par_iter()
@@ -177,7 +163,7 @@ fn extract_features(
         .collect();
 
     let tfidf_model = if !text_contents.is_empty() {
-        Some(build_tfidf_model(&text_contents))
+        Some(build_tfidf_model(&text_contents, &config.stop_words))
     } else {
         None
     };
@@ -198,16 +184,15 @@ fn extract_features(
                 path,
                 filename_vector,
                 content_vector,
-                is_text: content.is_some(),
             }
         })
         .collect();
 
-    Ok(features)
+    Ok((features, tfidf_model))
 }
 
-/// Check if file is likely a text file based on extension
-fn is_text_file(path: &Path) -> bool {
+/// Check if file is likely a text file: known text extension, or text-looking content
+pub(crate) fn is_text_file(path: &Path) -> bool {
     let text_extensions = [
         "txt", "md", "rs", "py", "js", "ts", "jsx", "tsx", "html", "css", "json", "xml", "yaml",
         "yml", "toml", "ini", "cfg", "conf", "c", "cpp", "h", "hpp", "java", "go", "php", "rb",
@@ -217,14 +202,19 @@ fn is_text_file(path: &Path) -> bool {
 
     if let Some(ext) = path.extension() {
         let ext_str = ext.to_string_lossy().to_lowercase();
-        return text_extensions.contains(&ext_str.as_str());
+        if text_extensions.contains(&ext_str.as_str()) {
+            return true;
+        }
     }
 
-    false
+    sniff::sniff_file(path)
+        .ok()
+        .flatten()
+        .is_some_and(|s| s.is_text())
 }
 
 /// Read first N lines from a file
-fn read_file_lines(path: &Path, max_lines: usize) -> Result<String, io::Error> {
+pub(crate) fn read_file_lines(path: &Path, max_lines: usize) -> Result<String, io::Error> {
     let content = fs::read_to_string(path)?;
     let lines: Vec<&str> = content.lines().take(max_lines).collect();
     Ok(lines.join("\n"))
@@ -277,12 +267,12 @@ fn extract_filename_features(path: &Path) -> Vec<f64> {
 }
 
 /// Build TF-IDF model from documents
-fn build_tfidf_model(documents: &[String]) -> TfIdfModel {
+pub(crate) fn build_tfidf_model(documents: &[String], stop_words: &[String]) -> TfIdfModel {
     // Build vocabulary
     let mut word_set = HashSet::new();
 
     for doc in documents {
-        let words = preprocess_text(doc);
+        let words = preprocess_text(doc, stop_words);
         word_set.extend(words);
     }
 
@@ -297,7 +287,7 @@ fn build_tfidf_model(documents: &[String]) -> TfIdfModel {
     let mut doc_freq = vec![0; vocabulary.len()];
 
     for doc in documents {
-        let words = preprocess_text(doc);
+        let words = preprocess_text(doc, stop_words);
         let mut seen = HashSet::new();
 
         for word in words {
@@ -321,22 +311,21 @@ fn build_tfidf_model(documents: &[String]) -> TfIdfModel {
         })
         .collect();
 
-    TfIdfModel { vocabulary, idf }
+    TfIdfModel {
+        vocabulary,
+        idf,
+        stop_words: stop_words.to_vec(),
+    }
 }
 
-/// Preprocess text into tokens
-fn preprocess_text(text: &str) -> Vec<String> {
-    let re = Regex::new(r"[a-zA-Z0-9]+").unwrap();
-
-    re.find_iter(&text.to_lowercase())
-        .map(|m| m.as_str().to_string())
-        .filter(|w| w.len() >= 2) // Filter very short words
-        .collect()
+/// Preprocess text into tokens (Unicode words, camelCase split, stop words removed)
+pub(crate) fn preprocess_text(text: &str, stop_words: &[String]) -> Vec<String> {
+    tokenize::tokenize(text, stop_words)
 }
 
 /// Compute TF-IDF vector for a document
 fn compute_tfidf_vector(text: &str, model: &TfIdfModel) -> Vec<f64> {
-    let words = preprocess_text(text);
+    let words = preprocess_text(text, &model.stop_words);
     let vocab_map: HashMap<&str, usize> = model
         .vocabulary
         .iter()
@@ -367,29 +356,43 @@ fn compute_tfidf_vector(text: &str, model: &TfIdfModel) -> Vec<f64> {
 }
 
 /// Perform K-means clustering
+/// Clusters of file indices, and the k they came from
 fn perform_clustering(
     features: &[FileFeatures],
     config: &IntelligentConfig,
-) -> Result<Vec<Vec<usize>>, io::Error> {
+    feedback: &Feedback,
+) -> Result<(Vec<Vec<usize>>, usize), io::Error> {
     if features.is_empty() {
-        return Ok(Vec::new());
+        return Ok((Vec::new(), 0));
     }
 
+    // Files without text get zeros over the whole vocabulary
+    let content_len = features
+        .iter()
+        .filter_map(|f| f.content_vector.as_ref())
+        .map(Vec::len)
+        .max()
+        .unwrap_or(0);
+
     // Combine filename and content features
     let combined_vectors: Vec<Vec<f64>> = features
         .iter()
-        .map(|f| combine_feature_vectors(f, config))
+        .map(|f| combine_feature_vectors(f, content_len, config))
         .collect();
 
     // Determine optimal number of clusters
     let k = determine_k(&combined_vectors, config);
 
     if k == 0 {
-        return Ok(vec![features.iter().enumerate().map(|(i, _)| i).collect()]);
+        return Ok((
+            vec![features.iter().enumerate().map(|(i, _)| i).collect()],
+            1,
+        ));
     }
 
     // Run K-means
-    let assignments = kmeans(&combined_vectors, k, config.max_iterations);
+    let links = Links::from_feedback(features, feedback);
+    let assignments = kmeans(&combined_vectors, k, config.max_iterations, &links);
 
     // Group indices by cluster
     let mut clusters: Vec<Vec<usize>> = vec![Vec::new(); k];
@@ -400,11 +403,15 @@ fn perform_clustering(
     // Filter out small clusters
     clusters.retain(|c| c.len() >= config.min_cluster_size);
 
-    Ok(clusters)
+    Ok((clusters, k))
 }
 
-/// Combine filename and content feature vectors
-fn combine_feature_vectors(features: &FileFeatures, config: &IntelligentConfig) -> Vec<f64> {
+/// Combine filename and content feature vectors, `content_len` being the vocabulary size
+fn combine_feature_vectors(
+    features: &FileFeatures,
+    content_len: usize,
+    config: &IntelligentConfig,
+) -> Vec<f64> {
     let mut combined = features.filename_vector.clone();
 
     // Scale filename features
@@ -421,7 +428,7 @@ fn combine_feature_vectors(features: &FileFeatures, config: &IntelligentConfig)
         combined.extend(scaled_content);
     } else {
         // Pad with zeros if no content
-        combined.extend(vec![0.0; 100]); // Arbitrary padding
+        combined.resize(combined.len() + content_len, 0.0);
     }
 
     combined
@@ -432,18 +439,61 @@ fn determine_k(vectors: &[Vec<f64>], config: &IntelligentConfig) -> usize {
     let n = vectors.len();
 
     // Simple heuristic: sqrt(n/2)
-    let k = ((n as f64 / 2.0).sqrt().ceil() as usize)
+    ((n as f64 / 2.0).sqrt().ceil() as usize)
         .max(2)
         .min(config.max_clusters)
-        .min(n);
+        .min(n)
+}
+
+/// For each file, the files before it that it must or must not share a cluster with
+struct Links {
+    must: Vec<Vec<usize>>,
+    cannot: Vec<Vec<usize>>,
+}
+
+impl Links {
+    fn none(n: usize) -> Self {
+        Self {
+            must: vec![Vec::new(); n],
+            cannot: vec![Vec::new(); n],
+        }
+    }
+
+    fn from_feedback(features: &[FileFeatures], feedback: &Feedback) -> Self {
+        let mut links = Self::none(features.len());
+        if feedback.must_link.is_empty() && feedback.cannot_link.is_empty() {
+            return links;
+        }
 
-    k
+        let names: Vec<String> = features
+            .iter()
+            .map(|f| {
+                f.path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default()
+            })
+            .collect();
+        for i in 0..names.len() {
+            for j in 0..i {
+                if feedback.must_link(&names[i], &names[j]) {
+                    links.must[i].push(j);
+                } else if feedback.cannot_link(&names[i], &names[j]) {
+                    links.cannot[i].push(j);
+                }
+            }
+        }
+        links
+    }
 }
 
 /// K-means clustering algorithm
 /// refer this page to learn more about K-means:
 ///  https://www.ibm.com/think/topics/k-means-clustering
-fn kmeans(vectors: &[Vec<f64>], k: usize, max_iterations: usize) -> Vec<usize> {
+///
+/// Constrained like COP-k-means: a point joins the cluster of a point it must link with,
+/// and skips clusters holding a point it cannot link with while one is left.
+fn kmeans(vectors: &[Vec<f64>], k: usize, max_iterations: usize, links: &Links) -> Vec<usize> {
     let n = vectors.len();
     if n == 0 || k == 0 {
         return Vec::new();
@@ -468,13 +518,25 @@ fn kmeans(vectors: &[Vec<f64>], k: usize, max_iterations: usize) -> Vec<usize> {
 
         // Assignment step
         for (i, vector) in vectors.iter().enumerate() {
+            // Earlier points already have this pass's cluster
+            let forbidden: Vec<usize> = links.cannot[i].iter().map(|&p| assignments[p]).collect();
+            let nearest = |allowed: &dyn Fn(usize) -> bool| {
+                centroids
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| allowed(*j))
+                    .map(|(j, c)| (j, euclidean_distance(vector, c)))
+                    .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+                    .map(|(j, _)| j)
+            };
+
             // let mut min_dist = f64::MAX;
-            let mut best_cluster = centroids
+            let best_cluster = links.must[i]
                 .iter()
-                .enumerate()
-                .map(|(j, c)| (j, euclidean_distance(vector, c)))
-                .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
-                .map(|(j, _)| j)
+                .map(|&p| assignments[p])
+                .find(|c| !forbidden.contains(c))
+                .or_else(|| nearest(&|j| !forbidden.contains(&j)))
+                .or_else(|| nearest(&|_| true))
                 .unwrap_or(0);
 
             // for (j, centroid) in centroids.iter().enumerate() {
@@ -537,9 +599,18 @@ fn euclidean_distance(a: &[f64], b: &[f64]) -> f64 {
         .sqrt()
 }
 
-/// Generate meaningful names for file groups
-fn generate_group_names(clusters: Vec<Vec<usize>>, features: &[FileFeatures]) -> Vec<FileGroup> {
-    clusters
+/// Names every cluster, with no two alike
+///
+/// Bigger clusters pick first, so a smaller one that would get the same name falls back
+/// to its next candidate, or a number.
+fn generate_group_names(
+    clusters: Vec<Vec<usize>>,
+    features: &[FileFeatures],
+    tfidf_model: Option<&TfIdfModel>,
+    config: &IntelligentConfig,
+    feedback: &Feedback,
+) -> Vec<FileGroup> {
+    let mut named: Vec<(FileGroup, Vec<String>)> = clusters
         .into_iter()
         .map(|cluster_indices| {
             let files: Vec<PathBuf> = cluster_indices
@@ -547,62 +618,172 @@ fn generate_group_names(clusters: Vec<Vec<usize>>, features: &[FileFeatures]) ->
                 .map(|&i| features[i].path.clone())
                 .collect();
 
-            // Generate name from common filename patterns
-            let suggested_name = generate_name_from_files(&files);
+            let contents: Vec<&Vec<f64>> = cluster_indices
+                .iter()
+                .filter_map(|&i| features[i].content_vector.as_ref())
+                .collect();
+            let top_terms = tfidf_model
+                .map(|model| top_terms(&contents, model, 5))
+                .unwrap_or_default();
 
-            // Compute centroid
-            let centroid = compute_centroid(&cluster_indices, features);
+            // A name the user chose for files like these before, else the terms and names
+            let names: Vec<String> = files
+                .iter()
+                .filter_map(|p| Some(p.file_name()?.to_string_lossy().into_owned()))
+                .collect();
+            let (candidates, named_from) = match feedback.preferred_name(&names) {
+                Some(name) => (vec![name.to_string()], NameSource::Feedback),
+                None => {
+                    let coverage = contents.len() as f64 / files.len() as f64;
+                    name_candidates(&files, &top_terms, coverage, config)
+                }
+            };
 
-            FileGroup {
+            let group = FileGroup {
                 files,
-                suggested_name,
-                centroid,
-            }
+                suggested_name: String::new(),
+                top_terms,
+                text_files: contents.len(),
+                named_from,
+            };
+            (group, candidates)
         })
-        .collect()
+        .collect();
+
+    let mut order: Vec<usize> = (0..named.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(named[i].0.files.len()));
+    let mut taken = HashSet::new();
+    for i in order {
+        let (group, candidates) = &mut named[i];
+        group.suggested_name = unique_name(candidates, &mut taken);
+    }
+
+    named.into_iter().map(|(group, _)| group).collect()
 }
 
-/// Generate a meaningful name from a group of files
-fn generate_name_from_files(files: &[PathBuf]) -> String {
-    if files.is_empty() {
-        return "Group".to_string();
+/// The first of `candidates` no other folder has, else the first one numbered
+fn unique_name(candidates: &[String], taken: &mut HashSet<String>) -> String {
+    let key = |name: &str| sanitize_dirname(name).to_lowercase();
+    let base = candidates.first().map_or("Group", String::as_str);
+    let name = candidates
+        .iter()
+        .find(|c| !key(c).is_empty() && !taken.contains(&key(c)))
+        .cloned()
+        .unwrap_or_else(|| {
+            (2..)
+                .map(|n| format!("{}_{}", base, n))
+                .find(|c| !taken.contains(&key(c)))
+                .unwrap_or_default()
+        });
+    taken.insert(key(&name));
+    name
+}
+
+/// The `count` heaviest words of the mean of `vectors`
+fn top_terms(vectors: &[&Vec<f64>], model: &TfIdfModel, count: usize) -> Vec<(String, f64)> {
+    if vectors.is_empty() {
+        return Vec::new();
+    }
+    let mut mean = vec![0.0; model.vocabulary.len()];
+    for vector in vectors {
+        for (sum, value) in mean.iter_mut().zip(vector.iter()) {
+            *sum += value / vectors.len() as f64;
+        }
     }
 
-    // Extract all filenames
-    let filenames: Vec<String> = files
+    let mut terms: Vec<(String, f64)> = model
+        .vocabulary
         .iter()
-        .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().to_lowercase()))
+        .cloned()
+        .zip(mean)
+        .filter(|(_, weight)| *weight > 0.0)
         .collect();
+    terms.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    terms.truncate(count);
+    terms
+}
+
+/// Names for a cluster, best first
+///
+/// Each word scores its centroid weight (relative to the top term, times the share of
+/// files that had text) times `content_similarity_weight`, plus the share of file names
+/// holding it times `filename_similarity_weight`, the same mix the clustering used. Two
+/// words close at the top make a name together (`Budget_Forecast`). Without terms the
+/// file names decide alone.
+fn name_candidates(
+    files: &[PathBuf],
+    terms: &[(String, f64)],
+    coverage: f64,
+    config: &IntelligentConfig,
+) -> (Vec<String>, NameSource) {
+    let stems: Vec<String> = files
+        .iter()
+        .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+        .collect();
+    let shares = word_shares(&stems);
+    let from_names = filename_candidates(files, &stems, &shares);
+    let Some((_, top)) = terms.first() else {
+        return (from_names, NameSource::Filenames);
+    };
 
-    // Find common prefix
-    let common_prefix = find_common_prefix(&filenames);
-
-    if !common_prefix.is_empty() && common_prefix.len() >= 3 {
-        // Capitalize first letter
-        let mut chars = common_prefix.chars();
-        return chars
-            .next()
-            .unwrap()
-            .to_uppercase()
-            .chain(chars)
-            .collect::<String>()
-            .trim_end_matches(|c: char| !c.is_alphanumeric())
-            .to_string();
+    let mut scores: HashMap<&str, f64> = HashMap::new();
+    for (term, weight) in terms {
+        *scores.entry(term).or_insert(0.0) +=
+            config.content_similarity_weight * coverage * weight / top;
     }
+    for (word, share) in shares.iter().filter(|(_, share)| **share >= 0.5) {
+        *scores.entry(word).or_insert(0.0) += config.filename_similarity_weight * share;
+    }
+    let mut words: Vec<(&str, f64)> = scores.into_iter().collect();
+    words.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
 
-    // Find most common words
-    let words = extract_common_words(&filenames);
-    if !words.is_empty() {
-        let mut name = words[0].clone();
-        name.get_mut(0..1).map(|s| s.make_ascii_uppercase());
-        return name;
+    let mut candidates = Vec::new();
+    if let [(first, best), (second, score), ..] = words[..] {
+        if score >= 0.75 * best {
+            candidates.push(format!("{}_{}", capitalize(first), capitalize(second)));
+        }
     }
+    candidates.extend(words.iter().map(|(word, _)| capitalize(word)));
+    candidates.extend(from_names);
+    (candidates, NameSource::Content)
+}
 
-    // Fallback: use extension or "Mixed"
-    if let Some(ext) = files[0].extension() {
-        format!("{}_Files", ext.to_string_lossy().to_uppercase())
-    } else {
-        "Mixed_Files".to_string()
+/// A common prefix, the most common word, then the extension (`PDF_Files`)
+fn filename_candidates(
+    files: &[PathBuf],
+    stems: &[String],
+    shares: &HashMap<String, f64>,
+) -> Vec<String> {
+    let mut candidates = Vec::new();
+
+    let lowered: Vec<String> = stems.iter().map(|s| s.to_lowercase()).collect();
+    let common_prefix = find_common_prefix(&lowered);
+    if common_prefix.len() >= 3 {
+        let prefix = capitalize(&common_prefix);
+        let prefix = prefix.trim_end_matches(|c: char| !c.is_alphanumeric());
+        if !prefix.is_empty() {
+            candidates.push(prefix.to_string());
+        }
+    }
+
+    let mut words: Vec<(&String, &f64)> = shares.iter().collect();
+    words.sort_by(|a, b| b.1.total_cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    if let Some((word, _)) = words.first() {
+        candidates.push(capitalize(word));
+    }
+
+    candidates.push(match files.first().and_then(|f| f.extension()) {
+        Some(ext) => format!("{}_Files", ext.to_string_lossy().to_uppercase()),
+        None => "Mixed_Files".to_string(),
+    });
+    candidates
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
     }
 }
 
@@ -626,105 +807,33 @@ fn find_common_prefix(strings: &[String]) -> String {
     prefix
 }
 
-/// Extract most common words from filenames
-fn extract_common_words(filenames: &[String]) -> Vec<String> {
+/// Share of `filenames` holding each word (camelCase and separators split)
+fn word_shares(filenames: &[String]) -> HashMap<String, f64> {
     let mut word_counts: HashMap<String, usize> = HashMap::new();
-    let re = Regex::new(r"[a-zA-Z]{3,}").unwrap();
 
     for filename in filenames {
-        for word in re.find_iter(filename) {
-            let word_str = word.as_str().to_lowercase();
-            if word_str.len() >= 3 {
-                *word_counts.entry(word_str).or_insert(0) += 1;
-            }
-        }
-    }
-
-    let mut words: Vec<(String, usize)> = word_counts.into_iter().collect();
-    words.sort_by(|a, b| b.1.cmp(&a.1));
-
-    words.into_iter().map(|(w, _)| w).take(1).collect()
-}
-
-/// Compute centroid of a cluster
-fn compute_centroid(indices: &[usize], features: &[FileFeatures]) -> Vec<f64> {
-    if indices.is_empty() {
-        return Vec::new();
-    }
-
-    let first_vec = &features[indices[0]].filename_vector;
-    let dim = first_vec.len();
-    let mut centroid = vec![0.0; dim];
-
-    for &idx in indices {
-        for (i, &val) in features[idx].filename_vector.iter().enumerate() {
-            centroid[i] += val;
-        }
-    }
-
-    for val in &mut centroid {
-        *val /= indices.len() as f64;
-    }
-
-    centroid
-}
-
-/// Move files into their organized groups
-pub fn move_files_to_groups(
-    base_path: &Path,
-    result: &ClusterResult,
-    dry_run: bool,
-) -> Result<Vec<String>, io::Error> {
-    let mut log = Vec::new();
-
-    for (_i, group) in result.groups.iter().enumerate() {
-        // Sanitize group name for directory
-        let dir_name = sanitize_dirname(&group.suggested_name);
-        let group_dir = base_path.join(&dir_name);
-
-        if !dry_run {
-            // Create directory if it doesn't exist
-            fs::create_dir_all(&group_dir)?;
-            log.push(format!("Created directory: {}", group_dir.display()));
-        } else {
-            log.push(format!("[DRY RUN] Would create: {}", group_dir.display()));
-        }
-
-        // Move each file to the group directory
-        for file_path in &group.files {
-            if let Some(filename) = file_path.file_name() {
-                let dest_path = group_dir.join(filename);
-
-                // Skip if source and dest are the same
-                if file_path == &dest_path {
-                    continue;
-                }
-
-                if !dry_run {
-                    // Handle file name conflicts
-                    let final_dest = handle_conflict(&dest_path)?;
-                    fs::rename(file_path, &final_dest)?;
-                    log.push(format!(
-                        "  Moved: {} → {}",
-                        file_path.display(),
-                        final_dest.display()
-                    ));
-                } else {
-                    log.push(format!(
-                        "  [DRY RUN] Would move: {} → {}",
-                        file_path.display(),
-                        dest_path.display()
-                    ));
-                }
-            }
+        let words: HashSet<String> = tokenize::split_words(filename)
+            .into_iter()
+            .filter(|word| {
+                let alphabetic = word.chars().all(|c| c.is_alphabetic());
+                let long_enough =
+                    word.chars().count() >= 3 || word.chars().all(tokenize::is_ideographic);
+                alphabetic && long_enough
+            })
+            .collect();
+        for word in words {
+            *word_counts.entry(word).or_insert(0) += 1;
         }
     }
 
-    Ok(log)
+    word_counts
+        .into_iter()
+        .map(|(word, count)| (word, count as f64 / filenames.len() as f64))
+        .collect()
 }
 
 /// Sanitize directory name
-fn sanitize_dirname(name: &str) -> String {
+pub fn sanitize_dirname(name: &str) -> String {
     name.chars()
         .map(|c| {
             if c.is_alphanumeric() || c == '_' || c == '-' {
@@ -738,711 +847,222 @@ fn sanitize_dirname(name: &str) -> String {
         .to_string()
 }
 
-/// Handle file name conflicts by appending numbers
-fn handle_conflict(path: &Path) -> Result<PathBuf, io::Error> {
-    if !path.exists() {
-        return Ok(path.to_path_buf());
-    }
-
-    let parent = path.parent().unwrap_or(Path::new("."));
-    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
-    let ext = path
-        .extension()
-        .map(|e| format!(".{}", e.to_string_lossy()))
-        .unwrap_or_default();
-
-    for i in 1..1000 {
-        let new_name = format!("{}_{}{}", stem, i, ext);
-        let new_path = parent.join(new_name);
-        if !new_path.exists() {
-            return Ok(new_path);
-        }
-    }
-
-    Err(io::Error::new(
-        io::ErrorKind::AlreadyExists,
-        "Too many file conflicts",
-    ))
-}
-
-// TUI
-#[derive(Debug)]
-enum AppState {
-    Ready,
-    Analyzing,
-    Complete(ClusterResult),
-    Moving,
-    Moved(Vec<String>),
+/// One folder per k-means cluster of names and contents (`tyr -i`)
+///
+/// Hidden files are left where they are.
+pub struct ClusterOrganizer {
+    config: IntelligentConfig,
+    feedback: Arc<Feedback>,
+    /// Why the clusters of the last `analyze` formed, for `explain`
+    explanations: Mutex<Explanations>,
 }
 
-pub struct IntelligentTuiApp {
-    config: IntelligentConfig,
-    base_path: PathBuf,
-    state: AppState,
-    progress_message: String,
-    log_messages: Vec<String>,
+#[derive(Default)]
+struct Explanations {
+    cluster_of: HashMap<PathBuf, usize>,
+    lines: Vec<Vec<String>>,
 }
 
-impl IntelligentTuiApp {
-    pub fn new(config: IntelligentConfig, base_path: PathBuf) -> Self {
+impl ClusterOrganizer {
+    pub fn new(config: IntelligentConfig) -> Self {
         Self {
             config,
-            base_path,
-            state: AppState::Ready,
-            progress_message: String::new(),
-            log_messages: Vec::new(),
+            feedback: Arc::default(),
+            explanations: Mutex::default(),
         }
     }
 
-    pub fn run(&mut self) -> io::Result<()> {
-        enable_raw_mode()?;
-        let mut stdout = stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-
-        let backend = CrosstermBackend::new(stdout);
-        let mut terminal = Terminal::new(backend)?;
-
-        let result = self.run_app(&mut terminal);
-
-        disable_raw_mode()?;
-        execute!(
-            terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture
-        )?;
-        terminal.show_cursor()?;
-
-        result
-    }
-
-    fn run_app(
-        &mut self,
-        terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
-    ) -> io::Result<()> {
-        loop {
-            terminal.draw(|f| self.draw_ui(f))?;
-
-            if event::poll(Duration::from_millis(100))? {
-                if let Event::Key(key) = event::read()? {
-                    match key.code {
-                        KeyCode::Char('q') | KeyCode::Esc => break,
-                        KeyCode::Char('s') => {
-                            if matches!(self.state, AppState::Ready) {
-                                self.start_analysis()?;
-                            }
-                        }
-                        KeyCode::Char('m') => {
-                            if let AppState::Complete(result) = &self.state {
-                                let result_clone = result.clone();
-                                self.move_files(&result_clone)?;
-                            }
-                        }
-                        KeyCode::Char('d') => {
-                            if let AppState::Complete(result) = &self.state {
-                                let result_clone = result.clone();
-                                self.dry_run_move(&result_clone)?;
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-            }
+    fn explain_group(
+        &self,
+        group: &FileGroup,
+        name: &str,
+        k: usize,
+        clustered: usize,
+    ) -> Vec<String> {
+        let config = &self.config;
+        let mut lines = vec![
+            format!(
+                "One of {} k-means clusters over {} files (k = √(n/2), at most max_clusters = {})",
+                k, clustered, config.max_clusters
+            ),
+            format!(
+                "Distance = filename characters × {:.2} + TF-IDF content × {:.2}",
+                config.filename_similarity_weight, config.content_similarity_weight
+            ),
+        ];
+        if group.top_terms.is_empty() {
+            lines.push("No readable text, grouped by the file names only".to_string());
+        } else {
+            lines.push(format!(
+                "{} of {} files had text; top TF-IDF terms of the centroid:",
+                group.text_files,
+                group.files.len()
+            ));
+            lines.push(format!(
+                "  {}",
+                group
+                    .top_terms
+                    .iter()
+                    .map(|(term, weight)| format!("{} {:.3}", term, weight))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
         }
-        Ok(())
-    }
 
-    fn start_analysis(&mut self) -> io::Result<()> {
-        self.state = AppState::Analyzing;
-        self.progress_message = "Initializing ML clustering...".to_string();
-        self.log_messages.clear();
-
-        match organize_files_intelligently(&self.base_path, &self.config, None) {
-            Ok(result) => {
-                self.state = AppState::Complete(result);
-                Ok(())
-            }
-            Err(e) => {
-                self.state = AppState::Ready;
-                self.progress_message = format!("Error: {}", e);
-                Err(e)
-            }
-        }
+        lines.push(match group.named_from {
+            NameSource::Feedback => format!("Named {} after a folder you named before", name),
+            NameSource::Content => format!("Named {} from the top terms and the file names", name),
+            NameSource::Filenames => format!("Named {} from the file names", name),
+        });
+        lines
     }
 
-    fn dry_run_move(&mut self, result: &ClusterResult) -> io::Result<()> {
-        self.log_messages.clear();
-        match move_files_to_groups(&self.base_path, result, true) {
-            Ok(log) => {
-                self.log_messages = log;
-            }
-            Err(e) => {
-                self.log_messages.push(format!("Error: {}", e));
-            }
-        }
-        Ok(())
+    /// Clusters and names with the user's earlier corrections in mind
+    pub fn with_feedback(mut self, feedback: Arc<Feedback>) -> Self {
+        self.feedback = feedback;
+        self
     }
+}
 
-    fn move_files(&mut self, result: &ClusterResult) -> io::Result<()> {
-        self.state = AppState::Moving;
-        self.log_messages.clear();
-
-        match move_files_to_groups(&self.base_path, result, false) {
-            Ok(log) => {
-                self.state = AppState::Moved(log.clone());
-                self.log_messages = log;
-                Ok(())
-            }
-            Err(e) => {
-                self.state = AppState::Complete(ClusterResult {
-                    groups: result.groups.clone(),
-                });
-                self.log_messages.push(format!("Error: {}", e));
-                Err(e)
-            }
-        }
-    }
-
-    fn draw_ui(&self, f: &mut ratatui::Frame) {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3),
-                Constraint::Min(10),
-                Constraint::Length(5),
-                Constraint::Length(3),
-            ])
-            .split(f.size());
-
-        // Title
-        let title = Paragraph::new(" Tyr - Intelligent ML-Based File Organizer")
-            .style(
-                Style::default()
-                    .fg(Color::Magenta)
-                    .add_modifier(Modifier::BOLD),
-            )
-            .block(Block::default().borders(Borders::ALL));
-        f.render_widget(title, chunks[0]);
-
-        // Main content
-        match &self.state {
-            AppState::Ready => self.draw_ready_state(f, chunks[1]),
-            AppState::Analyzing => self.draw_analyzing_state(f, chunks[1]),
-            AppState::Complete(result) => self.draw_complete_state(f, chunks[1], result),
-            AppState::Moving => self.draw_moving_state(f, chunks[1]),
-            AppState::Moved(_) => self.draw_moved_state(f, chunks[1]),
-        }
-
-        // Info panel
-        self.draw_info_panel(f, chunks[2]);
-
-        // Controls
-        self.draw_controls(f, chunks[3]);
+impl Organizer for ClusterOrganizer {
+    fn name(&self) -> &'static str {
+        "Intelligent clustering"
     }
 
-    fn draw_ready_state(&self, f: &mut ratatui::Frame, area: Rect) {
-        let text = vec![
-            Line::from(""),
-            Line::from(Span::styled(
-                " Ready to Analyze Files with ML",
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            )),
-            Line::from(""),
-            Line::from(vec![
-                Span::raw("Directory: "),
-                Span::styled(
-                    self.base_path.display().to_string(),
-                    Style::default().fg(Color::Yellow),
-                ),
-            ]),
-            Line::from(""),
-            Line::from(vec![Span::styled(
-                "How Intelligent Grouping Works:",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            )]),
-            Line::from(""),
-            Line::from(" Text Files:"),
-            Line::from(format!(
-                "     • Reads first {} lines of content",
-                self.config.max_lines_to_read
-            )),
-            Line::from("     • Builds TF-IDF vectors for semantic analysis"),
-            Line::from("     • Combines with filename similarity"),
-            Line::from(""),
-            Line::from(" All Files:"),
-            Line::from("     • Extracts filename patterns and features"),
-            Line::from("     • Uses K-means clustering algorithm"),
-            Line::from(format!(
-                "     • Creates up to {} intelligent groups",
-                self.config.max_clusters
-            )),
-            Line::from("     • Generates meaningful group names"),
-            Line::from(""),
-            Line::from(vec![
-                Span::raw("  Weights: Filename "),
-                Span::styled(
-                    format!(
-                        "{}%",
-                        (self.config.filename_similarity_weight * 100.0) as u8
-                    ),
-                    Style::default().fg(Color::Cyan),
-                ),
-                Span::raw(" | Content "),
-                Span::styled(
-                    format!("{}%", (self.config.content_similarity_weight * 100.0) as u8),
-                    Style::default().fg(Color::Magenta),
-                ),
-            ]),
-            Line::from(""),
-            Line::from(Span::styled(
-                " Press 's' to start intelligent analysis",
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            )),
-        ];
-
-        let widget =
-            Paragraph::new(text).block(Block::default().borders(Borders::ALL).title(" Status "));
-        f.render_widget(widget, area);
+    fn config_schema(&self) -> ConfigSchema {
+        ConfigSchema::of("intelligent_config", &self.config)
     }
 
-    fn draw_analyzing_state(&self, f: &mut ratatui::Frame, area: Rect) {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Min(1),
-            ])
-            .split(area);
-
-        // Progress gauge
-        let gauge = Gauge::default()
-            .block(Block::default().borders(Borders::ALL).title(" Progress "))
-            .gauge_style(Style::default().fg(Color::Magenta))
-            .label(" Running ML clustering algorithm...")
-            .percent(50);
-        f.render_widget(gauge, chunks[0]);
-
-        // Current step
-        let step_text = vec![Line::from(vec![
-            Span::styled("", Style::default().fg(Color::Yellow)),
-            Span::raw(&self.progress_message),
-        ])];
-        let step_widget = Paragraph::new(step_text).block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(" Current Step "),
-        );
-        f.render_widget(step_widget, chunks[1]);
-
-        // Processing info
-        let info_text = vec![
-            Line::from(""),
-            Line::from(" This may take a moment depending on:"),
-            Line::from("   • Number of files to analyze"),
-            Line::from("   • Size of text files"),
-            Line::from("   • Complexity of filename patterns"),
-        ];
-        let info_widget = Paragraph::new(info_text).block(Block::default().borders(Borders::ALL));
-        f.render_widget(info_widget, chunks[2]);
+    fn learns(&self) -> bool {
+        true
     }
 
-    fn draw_complete_state(&self, f: &mut ratatui::Frame, area: Rect, result: &ClusterResult) {
-        let total_files: usize = result.groups.iter().map(|g| g.files.len()).sum();
-
-        let mut lines = vec![
-            Line::from(Span::styled(
-                "✦ Analysis Complete!",
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            )),
-            Line::from(""),
-            Line::from(vec![
-                Span::raw(" Total files analyzed: "),
-                Span::styled(
-                    total_files.to_string(),
-                    Style::default()
-                        .fg(Color::Green)
-                        .add_modifier(Modifier::BOLD),
-                ),
-            ]),
-            Line::from(vec![
-                Span::raw(" Groups created: "),
-                Span::styled(
-                    result.groups.len().to_string(),
-                    Style::default()
-                        .fg(Color::Magenta)
-                        .add_modifier(Modifier::BOLD),
-                ),
-            ]),
-            Line::from(""),
-            Line::from(Span::styled(
-                " Discovered Groups:",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            )),
-            Line::from(""),
-        ];
-
-        // Sort groups by file count
-        let mut sorted_groups: Vec<_> = result.groups.iter().collect();
-        sorted_groups.sort_by(|a, b| b.files.len().cmp(&a.files.len()));
-
-        for (_i, group) in sorted_groups.iter().enumerate().take(12) {
-            // let icon = get_group_icon(&group.suggested_name);
+    fn analyze(&self, files: &[PathBuf]) -> io::Result<Assignments> {
+        let (hidden, visible): (Vec<PathBuf>, Vec<PathBuf>) =
+            files.iter().cloned().partition(|f| {
+                f.file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+            });
+
+        let mut assignments: Assignments = hidden
+            .into_iter()
+            .map(|file| Assignment {
+                file,
+                target: Target::Keep,
+            })
+            .collect();
+        let result = cluster_files(&visible, &self.config, &self.feedback, &|_| {})?;
+        let mut explanations = Explanations::default();
+        for group in result.groups {
+            let name = sanitize_dirname(&group.suggested_name);
+            explanations
+                .lines
+                .push(self.explain_group(&group, &name, result.k, visible.len()));
+            for file in &group.files {
+                explanations
+                    .cluster_of
+                    .insert(file.clone(), explanations.lines.len() - 1);
+            }
 
-            // Truncate long names
-            let name = if group.suggested_name.len() > 25 {
-                format!("{}...", &group.suggested_name[..22])
+            let target = if name.is_empty() {
+                Target::Keep
             } else {
-                group.suggested_name.clone()
+                Target::Folder(name)
             };
-
-            lines.push(Line::from(vec![
-                // Span::raw(format!("  {} ", icon)),
-                Span::styled(format!("{:28}", name), Style::default().fg(Color::Cyan)),
-                Span::raw(" → "),
-                Span::styled(
-                    format!("{} files", group.files.len()),
-                    Style::default()
-                        .fg(Color::Green)
-                        .add_modifier(Modifier::BOLD),
-                ),
-            ]));
+            assignments.extend(group.files.into_iter().map(|file| Assignment {
+                file,
+                target: target.clone(),
+            }));
         }
-
-        if sorted_groups.len() > 12 {
-            lines.push(Line::from(""));
-            lines.push(Line::from(Span::styled(
-                format!("... and {} more groups", sorted_groups.len() - 12),
-                Style::default().fg(Color::Gray),
-            )));
+        assignments.sort();
+        if let Ok(mut last) = self.explanations.lock() {
+            *last = explanations;
         }
-
-        lines.push(Line::from(""));
-        lines.push(Line::from(Span::styled(
-            " Press 'm' to move files | 'd' for dry run preview",
-            Style::default().fg(Color::Yellow),
-        )));
-
-        // Show dry run logs if available
-        if !self.log_messages.is_empty() {
-            lines.push(Line::from(""));
-            lines.push(Line::from(Span::styled(
-                " Preview:",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            )));
-            for msg in self.log_messages.iter().take(5) {
-                lines.push(Line::from(format!("   {}", msg)));
-            }
-            if self.log_messages.len() > 5 {
-                lines.push(Line::from(format!(
-                    "   ... and {} more operations",
-                    self.log_messages.len() - 5
-                )));
-            }
-        }
-
-        let widget =
-            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(" Results "));
-        f.render_widget(widget, area);
+        Ok(assignments)
     }
 
-    fn draw_moving_state(&self, f: &mut ratatui::Frame, area: Rect) {
-        let text = vec![
-            Line::from(""),
-            Line::from(Span::styled(
-                " Moving Files to Groups...",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            )),
-            Line::from(""),
-            Line::from(" Please wait while files are being organized..."),
-            Line::from(""),
-            Line::from(" This process will:"),
-            Line::from("   • Create group directories"),
-            Line::from("   • Move files to respective groups"),
-            Line::from("   • Handle naming conflicts automatically"),
-        ];
-
-        let widget =
-            Paragraph::new(text).block(Block::default().borders(Borders::ALL).title(" Status "));
-        f.render_widget(widget, area);
-    }
-
-    fn draw_moved_state(&self, f: &mut ratatui::Frame, area: Rect) {
-        let mut lines = vec![
-            Line::from(Span::styled(
-                "✓ Files Successfully Moved! ",
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            )),
-            Line::from(""),
-            Line::from(vec![
-                Span::raw(" Operations completed: "),
-                Span::styled(
-                    self.log_messages.len().to_string(),
-                    Style::default()
-                        .fg(Color::Green)
-                        .add_modifier(Modifier::BOLD),
-                ),
-            ]),
-            Line::from(""),
-            Line::from(Span::styled(
-                " Recent Operations:",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            )),
-            Line::from(""),
-        ];
-
-        // Show last 15 operations
-        for msg in self.log_messages.iter().rev().take(15) {
-            lines.push(Line::from(format!("   {}", msg)));
-        }
+    /// The reasons recorded for each cluster the files came from
+    fn explain(&self, files: &[PathBuf]) -> Vec<String> {
+        let Ok(explanations) = self.explanations.lock() else {
+            return Vec::new();
+        };
+        let mut clusters: Vec<usize> = files
+            .iter()
+            .filter_map(|f| explanations.cluster_of.get(f).copied())
+            .collect();
+        clusters.sort();
+        clusters.dedup();
 
-        if self.log_messages.len() > 15 {
-            lines.push(Line::from(""));
-            lines.push(Line::from(Span::styled(
-                format!("... and {} more operations", self.log_messages.len() - 15),
-                Style::default().fg(Color::Gray),
-            )));
+        let mut lines = Vec::new();
+        for cluster in clusters {
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+            lines.extend(explanations.lines[cluster].iter().cloned());
         }
-
-        let widget =
-            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(" Complete "));
-        f.render_widget(widget, area);
+        lines
     }
+}
 
-    fn draw_info_panel(&self, f: &mut ratatui::Frame, area: Rect) {
-        let info = match &self.state {
-            AppState::Ready => vec![
-                ListItem::new(Line::from(vec![
-                    Span::styled("", Style::default().fg(Color::Yellow)),
-                    Span::raw("Intelligent grouping uses ML to find patterns"),
-                ])),
-                ListItem::new(Line::from(vec![
-                    Span::styled("", Style::default().fg(Color::Green)),
-                    Span::raw("Faster and smarter than manual organization"),
-                ])),
-                ListItem::new(Line::from(vec![
-                    Span::styled("", Style::default().fg(Color::Cyan)),
-                    Span::raw("Groups files by both name and content similarity"),
-                ])),
-            ],
-            AppState::Analyzing => vec![
-                ListItem::new(Line::from(vec![
-                    Span::styled("", Style::default().fg(Color::Magenta)),
-                    Span::raw("Building TF-IDF vectors from file content..."),
-                ])),
-                ListItem::new(Line::from(vec![
-                    Span::styled("", Style::default().fg(Color::Cyan)),
-                    Span::raw("Extracting filename features and patterns..."),
-                ])),
-                ListItem::new(Line::from(vec![
-                    Span::styled("", Style::default().fg(Color::Green)),
-                    Span::raw("Running K-means clustering algorithm..."),
-                ])),
-            ],
-            AppState::Complete(result) => {
-                let total_files: usize = result.groups.iter().map(|g| g.files.len()).sum();
-                let avg_group_size = if !result.groups.is_empty() {
-                    total_files / result.groups.len()
-                } else {
-                    0
-                };
-
-                vec![
-                    ListItem::new(Line::from(vec![
-                        Span::styled("✓ ", Style::default().fg(Color::Green)),
-                        Span::raw(format!(
-                            "Successfully grouped {} files into {} categories",
-                            total_files,
-                            result.groups.len()
-                        )),
-                    ])),
-                    ListItem::new(Line::from(vec![
-                        Span::styled("", Style::default().fg(Color::Cyan)),
-                        Span::raw(format!("Average group size: {} files", avg_group_size)),
-                    ])),
-                    ListItem::new(Line::from(vec![
-                        Span::styled("", Style::default().fg(Color::Magenta)),
-                        Span::raw("Smart group names generated from file patterns"),
-                    ])),
-                ]
-            }
-            AppState::Moving => vec![
-                ListItem::new(Line::from(vec![
-                    Span::styled("", Style::default().fg(Color::Yellow)),
-                    Span::raw("Creating directories for each group..."),
-                ])),
-                ListItem::new(Line::from(vec![
-                    Span::styled("", Style::default().fg(Color::Cyan)),
-                    Span::raw("Moving files to their designated groups..."),
-                ])),
-                ListItem::new(Line::from(vec![
-                    Span::styled("", Style::default().fg(Color::Green)),
-                    Span::raw("Handling conflicts and organizing structure..."),
-                ])),
-            ],
-            AppState::Moved(log) => {
-                vec![
-                    ListItem::new(Line::from(vec![
-                        Span::styled("", Style::default().fg(Color::Green)),
-                        Span::raw(format!(
-                            "All {} operations completed successfully",
-                            log.len()
-                        )),
-                    ])),
-                    ListItem::new(Line::from(vec![
-                        Span::styled("", Style::default().fg(Color::Cyan)),
-                        Span::raw("Files organized into categorized directories"),
-                    ])),
-                    ListItem::new(Line::from(vec![
-                        Span::styled("", Style::default().fg(Color::Magenta)),
-                        Span::raw("Your files are now perfectly organized!"),
-                    ])),
-                ]
-            }
-        };
-
-        let list = List::new(info).block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(" Information "),
-        );
-        f.render_widget(list, area);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kmeans_keeps_cannot_linked_points_apart() {
+        let vectors = vec![vec![0.0], vec![0.1], vec![0.2], vec![5.0], vec![5.1]];
+        let unconstrained = kmeans(&vectors, 2, 10, &Links::none(5));
+        assert_eq!(unconstrained[0], unconstrained[2]);
+
+        // The third point was pulled out of the first one's folder before
+        let mut links = Links::none(5);
+        links.cannot[2].push(0);
+        let constrained = kmeans(&vectors, 2, 10, &links);
+        assert_ne!(constrained[0], constrained[2]);
+        assert_eq!(constrained[3], constrained[4]);
     }
 
-    fn draw_controls(&self, f: &mut ratatui::Frame, area: Rect) {
-        let controls = match &self.state {
-            AppState::Ready => " 's' Start Analysis | 'q' Quit",
-            AppState::Analyzing => " Analyzing... Please wait",
-            AppState::Complete(_) => " 'm' Move Files | 'd' Dry Run | 'q' Quit",
-            AppState::Moving => " Moving files... Please wait",
-            AppState::Moved(_) => " 'q' Quit",
-        };
-
-        let widget = Paragraph::new(controls)
-            .style(Style::default().fg(Color::Yellow))
-            .block(Block::default().borders(Borders::ALL).title(" Controls "));
-        f.render_widget(widget, area);
+    #[test]
+    fn test_cluster_names_blend_terms_with_file_names_and_never_repeat() {
+        let config = IntelligentConfig::default();
+        let files = |names: &[&str]| names.iter().map(PathBuf::from).collect::<Vec<_>>();
+
+        let invoices = files(&["acme_invoice_01.pdf", "acme_invoice_02.pdf"]);
+        let terms = vec![("invoice".to_string(), 0.5), ("total".to_string(), 0.2)];
+        let (names, source) = name_candidates(&invoices, &terms, 1.0, &config);
+        assert_eq!(source, NameSource::Content);
+        assert_eq!(names[0], "Invoice");
+
+        // Nothing to go on but the missing extension, twice
+        let (names, source) = name_candidates(&files(&["a1", "b2"]), &[], 0.0, &config);
+        assert_eq!(source, NameSource::Filenames);
+        let mut taken = HashSet::new();
+        assert_eq!(unique_name(&names, &mut taken), "Mixed_Files");
+        assert_eq!(unique_name(&names, &mut taken), "Mixed_Files_2");
     }
 
-    /// Auto-analyze files without UI interaction
-    pub fn auto_analyze(&mut self) -> io::Result<()> {
-        // Redirect output to log instead of stdout
-        self.log_messages
-            .push("Starting intelligent ML-based analysis...".to_string());
-
-        // Start analysis
-        self.start_analysis()?;
-
-        // Display results
-        if let AppState::Complete(result) = &self.state {
-            let total_files: usize = result.groups.iter().map(|g| g.files.len()).sum();
-
-            self.log_messages
-                .push("\n✦ Analysis Complete! \n".to_string());
-            self.log_messages.push("Summary:".to_string());
-            self.log_messages
-                .push(format!("   • Total files analyzed: {}", total_files));
-            self.log_messages.push(format!(
-                "   • Groups created:       {}",
-                result.groups.len()
-            ));
-
-            if !result.groups.is_empty() {
-                self.log_messages
-                    .push("\n Discovered Groups:\n".to_string());
-
-                // Sort groups by file count
-                let mut sorted_groups: Vec<_> = result.groups.iter().collect();
-                sorted_groups.sort_by(|a, b| b.files.len().cmp(&a.files.len()));
-
-                for (_i, group) in sorted_groups.iter().enumerate() {
-                    // let icon = get_group_icon(&group.suggested_name);
-                    self.log_messages.push(format!(
-                        "  {:30} → {} files",
-                        // icon,
-                        group.suggested_name,
-                        group.files.len()
-                    ));
-                }
-
-                // Calculate statistics
-                let avg_group_size = total_files / result.groups.len();
-                self.log_messages.push("\n Statistics:".to_string());
-                self.log_messages
-                    .push(format!("   • Average group size: {} files", avg_group_size));
-                self.log_messages.push(format!(
-                    "   • Largest group:      {} files ({})",
-                    sorted_groups[0].files.len(),
-                    sorted_groups[0].suggested_name
-                ));
-                self.log_messages.push(format!(
-                    "   • Smallest group:     {} files ({})",
-                    sorted_groups.last().unwrap().files.len(),
-                    sorted_groups.last().unwrap().suggested_name
-                ));
-            }
-
-            // Print all logs to stdout after analysis
-            for msg in &self.log_messages {
-                println!("{}", msg);
-            }
+    #[test]
+    fn test_explain_names_the_top_terms_of_a_cluster() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut files = Vec::new();
+        for i in 0..4 {
+            let finance = dir.path().join(format!("q{}-finance.txt", i));
+            fs::write(&finance, "budget revenue forecast budget\n").unwrap();
+            let recipe = dir.path().join(format!("cake{}.md", i));
+            fs::write(&recipe, "recipe flour sugar oven\n").unwrap();
+            files.extend([finance, recipe]);
         }
 
-        Ok(())
+        let organizer = ClusterOrganizer::new(IntelligentConfig::default());
+        let assignments = organizer.analyze(&files).unwrap();
+        assert_eq!(assignments.len(), 8);
+
+        let lines = organizer.explain(&files[..1]);
+        let terms = lines.iter().find(|l| l.contains("budget")).unwrap();
+        assert!(terms.trim_start().starts_with("budget"), "{}", terms);
+        assert!(!terms.contains("flour"));
+        assert!(organizer
+            .explain(&[dir.path().join("missing.txt")])
+            .is_empty());
     }
 }
-
-// Get an icon for a group based on its name
-// fn get_group_icon(name: &str) -> &'static str {
-//     let name_lower = name.to_lowercase();
-
-//     if name_lower.contains("image") || name_lower.contains("photo") || name_lower.contains("picture") {
-//         "🖼️"
-//     } else if name_lower.contains("video") || name_lower.contains("movie") || name_lower.contains("film") {
-//         "🎬"
-//     } else if name_lower.contains("audio") || name_lower.contains("music") || name_lower.contains("sound") {
-//         "🎵"
-//     } else if name_lower.contains("document") || name_lower.contains("doc") || name_lower.contains("text") {
-//         "📄"
-//     } else if name_lower.contains("code") || name_lower.contains("script") || name_lower.contains("program") {
-//         "💻"
-//     } else if name_lower.contains("archive") || name_lower.contains("zip") || name_lower.contains("compressed") {
-//         "📦"
-//     } else if name_lower.contains("data") || name_lower.contains("database") || name_lower.contains("json") {
-//         "💾"
-//     } else if name_lower.contains("spreadsheet") || name_lower.contains("excel") || name_lower.contains("csv") {
-//         "📊"
-//     } else if name_lower.contains("presentation") || name_lower.contains("slide") {
-//         "📽️"
-//     } else if name_lower.contains("design") || name_lower.contains("graphic") {
-//         "🎨"
-//     } else if name_lower.contains("3d") || name_lower.contains("model") {
-//         "🗿"
-//     } else if name_lower.contains("font") {
-//         "🔤"
-//     } else if name_lower.contains("report") {
-//         "📋"
-//     } else if name_lower.contains("project") {
-//         "📁"
-//     } else {
-//         "📂"
-//     }
-// }
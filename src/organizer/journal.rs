@@ -0,0 +1,331 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for recording finished moves and undoing them for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::transfer::{self, Output, TransferMode};
+use super::trash;
+
+/// One change to the filesystem that actually happened
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct JournalEntry {
+    pub from: PathBuf,
+    pub to: PathBuf,
+    #[serde(default, skip_serializing_if = "EntryKind::is_move")]
+    pub kind: EntryKind,
+}
+
+/// How `to` came about, which decides how it is undone
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryKind {
+    /// Moved from `from`; undo moves it back
+    #[default]
+    Move,
+    /// Copied from `from`; undo deletes the copy
+    Copy,
+    /// `to` is in the desktop trash; undo restores it from there
+    Trash,
+    /// Extracted from the archive `from`; undo deletes it
+    Extract,
+}
+
+impl EntryKind {
+    fn is_move(&self) -> bool {
+        *self == EntryKind::Move
+    }
+}
+
+/// Everything a single run moved, in the order it was moved
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Journal {
+    /// Local time the run started, `%Y-%m-%d %H:%M:%S`
+    pub created: String,
+    /// Directory that was organized
+    pub base_path: PathBuf,
+    /// Where the folders were created when it was not `base_path` (`--dest`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dest_root: Option<PathBuf>,
+    /// The folders are groups tyr made up (`-f`, `-i`), so files the user moves out of
+    /// them afterwards are corrections worth learning from
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub learn: bool,
+    pub moves: Vec<JournalEntry>,
+}
+
+/// Outcome of `tyr undo`
+#[derive(Debug)]
+pub struct UndoResult {
+    pub restored: usize,
+    pub errors: Vec<String>,
+    /// Moves that could not be reverted, kept so the journal can be retried
+    pub remaining: Vec<JournalEntry>,
+}
+
+impl Journal {
+    pub fn new(base_path: &Path, output: &Output) -> Self {
+        Self {
+            created: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            base_path: base_path.to_path_buf(),
+            dest_root: output.dest_root.clone(),
+            learn: false,
+            moves: Vec::new(),
+        }
+    }
+
+    /// Records a transfer made with `output` (a move, or a copy with `--copy`)
+    pub fn record(&mut self, from: &Path, to: &Path, output: &Output) {
+        let kind = match output.mode {
+            TransferMode::Move => EntryKind::Move,
+            TransferMode::Copy => EntryKind::Copy,
+        };
+        self.record_kind(from, to, kind);
+    }
+
+    /// Records any change; for `Trash`, `to` is the file's path inside the trash
+    pub fn record_kind(&mut self, from: &Path, to: &Path, kind: EntryKind) {
+        self.moves.push(JournalEntry {
+            from: from.to_path_buf(),
+            to: to.to_path_buf(),
+            kind,
+        });
+    }
+
+    /// Writes the journal into `dir` as `<timestamp>.toml` and returns its path
+    pub fn save(&self, dir: &Path) -> io::Result<PathBuf> {
+        fs::create_dir_all(dir)?;
+
+        let stamp = Local::now().format("%Y%m%d-%H%M%S").to_string();
+        let mut path = dir.join(format!("{}.toml", stamp));
+        let mut n = 1;
+        while path.exists() {
+            path = dir.join(format!("{}-{}.toml", stamp, n));
+            n += 1;
+        }
+
+        self.write_to(&path)?;
+        Ok(path)
+    }
+
+    /// Overwrites `path` with this journal (used to keep moves a failed undo left behind)
+    pub fn write_to(&self, path: &Path) -> io::Result<()> {
+        let content = toml::to_string(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        fs::write(path, content)
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        toml::from_str(&content).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Corrupt journal {}: {}", path.display(), e.message()),
+            )
+        })
+    }
+
+    /// Finds the most recent journal in `dir`, if any
+    pub fn latest(dir: &Path) -> io::Result<Option<PathBuf>> {
+        Ok(Self::recent(dir, 1)?.pop())
+    }
+
+    /// Up to `count` journals in `dir`, newest first
+    pub fn recent(dir: &Path, count: usize) -> io::Result<Vec<PathBuf>> {
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut journals: Vec<PathBuf> = fs::read_dir(dir)?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        journals.sort_by_cached_key(|p| std::cmp::Reverse(save_order(p)));
+        journals.truncate(count);
+
+        Ok(journals)
+    }
+
+    /// Moves every file back where it came from, newest move first
+    ///
+    /// Copies and extracted files are deleted instead, trashed files come back out of the
+    /// trash. Folders the run created are removed again once they are empty.
+    pub fn undo(&self) -> UndoResult {
+        let mut result = UndoResult {
+            restored: 0,
+            errors: Vec::new(),
+            remaining: Vec::new(),
+        };
+
+        for entry in self.moves.iter().rev() {
+            if !entry.to.exists() {
+                result
+                    .errors
+                    .push(format!("Missing: {}", entry.to.display()));
+                result.remaining.push(entry.clone());
+                continue;
+            }
+            match entry.kind {
+                EntryKind::Copy | EntryKind::Extract => {
+                    self.remove_created(entry, &mut result);
+                    continue;
+                }
+                EntryKind::Move | EntryKind::Trash => {}
+            }
+            if entry.from.exists() {
+                result.errors.push(format!(
+                    "Not restoring {}: {} already exists",
+                    entry.to.display(),
+                    entry.from.display()
+                ));
+                result.remaining.push(entry.clone());
+                continue;
+            }
+
+            let restored = if entry.kind == EntryKind::Trash {
+                trash::restore_to(&entry.to, &entry.from)
+            } else {
+                entry
+                    .from
+                    .parent()
+                    .map_or(Ok(()), fs::create_dir_all)
+                    .and_then(|_| transfer::move_file(&entry.to, &entry.from))
+            };
+
+            match restored {
+                Ok(()) if entry.kind == EntryKind::Trash => result.restored += 1,
+                Ok(()) => {
+                    result.restored += 1;
+                    self.remove_empty_parents(&entry.to);
+                }
+                Err(e) => {
+                    result.errors.push(format!(
+                        "Failed to restore {}: {}",
+                        entry.from.display(),
+                        e
+                    ));
+                    result.remaining.push(entry.clone());
+                }
+            }
+        }
+
+        // Keep the original order for a retry
+        result.remaining.reverse();
+        result
+    }
+
+    /// Deletes a copy or an extracted file, as long as its source is still there
+    fn remove_created(&self, entry: &JournalEntry, result: &mut UndoResult) {
+        if !entry.from.exists() {
+            result.errors.push(format!(
+                "Not deleting {}: the original {} is gone",
+                entry.to.display(),
+                entry.from.display()
+            ));
+            result.remaining.push(entry.clone());
+            return;
+        }
+
+        match fs::remove_file(&entry.to) {
+            Ok(()) => {
+                result.restored += 1;
+                self.remove_empty_parents(&entry.to);
+            }
+            Err(e) => {
+                result
+                    .errors
+                    .push(format!("Failed to delete {}: {}", entry.to.display(), e));
+                result.remaining.push(entry.clone());
+            }
+        }
+    }
+
+    /// Undo never removes folders above the organized (or `--dest`) root
+    fn remove_empty_parents(&self, path: &Path) {
+        let root = self.dest_root.as_deref().unwrap_or(&self.base_path);
+        let mut dir = path.parent();
+        while let Some(current) = dir {
+            if current == root || !current.starts_with(root) {
+                break;
+            }
+            // Fails (and stops) at the first folder that still has files in it
+            if fs::remove_dir(current).is_err() {
+                break;
+            }
+            dir = current.parent();
+        }
+    }
+}
+
+/// `<stamp>.toml` then `<stamp>-1.toml`, `<stamp>-2.toml` for runs saved in the same
+/// second; a plain name comparison would put the `-1` first (`-` sorts before `.`)
+fn save_order(path: &Path) -> (String, u64) {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    match stem.rsplit_once('-') {
+        Some((stamp, n)) if stamp.contains('-') => match n.parse() {
+            Ok(n) => (stamp.to_string(), n),
+            Err(_) => (stem, 0),
+        },
+        _ => (stem, 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undo_restores_files_and_removes_empty_folders() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        let from = base.join("photo.jpg");
+        let to = base.join("Images").join("Holiday").join("photo.jpg");
+
+        fs::create_dir_all(to.parent().unwrap()).unwrap();
+        fs::write(&to, "jpeg").unwrap();
+
+        let mut journal = Journal::new(base, &Output::default());
+        journal.record(&from, &to, &Output::default());
+
+        let saved = journal.save(&base.join("journal")).unwrap();
+        let result = Journal::load(&saved).unwrap().undo();
+
+        assert_eq!(result.restored, 1);
+        assert!(result.remaining.is_empty());
+        assert!(from.exists());
+        assert!(!base.join("Images").exists());
+    }
+
+    #[test]
+    fn test_runs_saved_in_the_same_second_stay_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let journals = dir.path().join("journal");
+        let runs: Vec<PathBuf> = (0..3)
+            .map(|i| {
+                let journal = Journal::new(&dir.path().join(i.to_string()), &Output::default());
+                journal.save(&journals).unwrap()
+            })
+            .collect();
+
+        let newest_first: Vec<PathBuf> = runs.iter().rev().cloned().collect();
+        assert_eq!(Journal::recent(&journals, 10).unwrap(), newest_first);
+        let latest = Journal::latest(&journals).unwrap().unwrap();
+        assert_eq!(
+            Journal::load(&latest).unwrap().base_path,
+            dir.path().join("2")
+        );
+    }
+}
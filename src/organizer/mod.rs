@@ -1,3 +1,24 @@
+pub mod archive;
+pub mod buckets;
 pub mod categorise;
+pub mod classifier;
+pub mod feedback;
 pub mod filename;
+pub mod hooks;
+pub mod ignore;
 pub mod intelligent;
+pub mod journal;
+pub mod naming;
+pub mod pdf;
+pub mod pipeline;
+pub mod plugin;
+pub mod rename;
+pub mod report;
+pub mod similar;
+pub mod sniff;
+pub mod strategy;
+pub mod template;
+pub mod tokenize;
+pub mod transfer;
+pub mod trash;
+pub mod tui;
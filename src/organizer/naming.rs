@@ -0,0 +1,135 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for extensions (including compound ones) and naming conflicts for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Multi-dot extensions that always stay together, on top of the ones categories declare
+const COMPOUND_EXTENSIONS: [&str; 12] = [
+    "tar.gz", "tar.bz2", "tar.xz", "tar.zst", "tar.lz", "tar.lz4", "tar.lzma", "tar.br", "d.ts",
+    "user.js", "min.js", "min.css",
+];
+
+/// Every possible extension of `filename`, lowercased, longest first
+///
+/// `archive.backup.tar.gz` gives `backup.tar.gz`, `tar.gz`, `gz`. A leading dot is part of
+/// the name, not an extension (`.bashrc` has none).
+pub fn extension_candidates(filename: &str) -> Vec<String> {
+    let lower = filename.to_lowercase();
+    lower
+        .char_indices()
+        .filter(|&(i, c)| c == '.' && i > 0 && i + 1 < lower.len())
+        .map(|(i, _)| lower[i + 1..].to_string())
+        .collect()
+}
+
+/// Splits `filename` into stem and extension, keeping compound extensions whole
+///
+/// `extra` lists compound extensions declared in the config (e.g. a category with `d.ts`).
+pub fn split_extension<'a>(filename: &'a str, extra: &[String]) -> (&'a str, Option<&'a str>) {
+    let is_compound =
+        |ext: &str| COMPOUND_EXTENSIONS.contains(&ext) || extra.iter().any(|e| e == ext);
+
+    let dots: Vec<usize> = filename
+        .char_indices()
+        .filter(|&(i, c)| c == '.' && i > 0 && i + 1 < filename.len())
+        .map(|(i, _)| i)
+        .collect();
+
+    // Longest compound match first, then the plain last extension
+    let split_at = dots
+        .iter()
+        .copied()
+        .find(|&i| {
+            let ext = filename[i + 1..].to_lowercase();
+            ext.contains('.') && is_compound(&ext)
+        })
+        .or(dots.last().copied());
+
+    match split_at {
+        Some(i) => (&filename[..i], Some(&filename[i + 1..])),
+        None => (filename, None),
+    }
+}
+
+/// Finds a free name for `path` by appending `_1`, `_2`, ... to the stem
+///
+/// `is_taken` decides whether a candidate can be used (usually `Path::exists`, plus any
+/// names already promised to other files of the same run).
+pub fn resolve_conflict(
+    path: &Path,
+    extra: &[String],
+    mut is_taken: impl FnMut(&Path) -> bool,
+) -> io::Result<PathBuf> {
+    if !is_taken(path) {
+        return Ok(path.to_path_buf());
+    }
+
+    let parent = path.parent().unwrap_or(Path::new(""));
+    let filename = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "file".to_string());
+    let (stem, extension) = split_extension(&filename, extra);
+
+    for i in 1..1000 {
+        let new_name = match extension {
+            Some(ext) => format!("{}_{}.{}", stem, i, ext),
+            None => format!("{}_{}", stem, i),
+        };
+        let candidate = parent.join(new_name);
+        if !is_taken(&candidate) {
+            return Ok(candidate);
+        }
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        "Could not find available filename after 999 attempts",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compound_extensions_are_kept_together() {
+        assert_eq!(
+            split_extension("backup.tar.gz", &[]),
+            ("backup", Some("tar.gz"))
+        );
+        assert_eq!(
+            split_extension("my.notes.txt", &[]),
+            ("my.notes", Some("txt"))
+        );
+        assert_eq!(split_extension(".bashrc", &[]), (".bashrc", None));
+        assert_eq!(
+            split_extension("scene.blend1.bak", &["blend1.bak".to_string()]),
+            ("scene", Some("blend1.bak"))
+        );
+        assert_eq!(
+            extension_candidates("Types.D.TS"),
+            vec!["d.ts".to_string(), "ts".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_conflicts_number_the_stem() {
+        let taken = [
+            PathBuf::from("/x/backup.tar.gz"),
+            PathBuf::from("/x/backup_1.tar.gz"),
+        ];
+        let free = resolve_conflict(Path::new("/x/backup.tar.gz"), &[], |p| {
+            taken.iter().any(|t| t == p)
+        })
+        .unwrap();
+        assert_eq!(free, PathBuf::from("/x/backup_2.tar.gz"));
+    }
+}
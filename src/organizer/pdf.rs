@@ -0,0 +1,294 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for pulling the visible text out of PDF files (for `tyr similar`) for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use flate2::read::ZlibDecoder;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// Streams are cut off at this size once inflated
+const MAX_STREAM_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Stream dictionaries with one of these hold fonts, images or metadata, never page text
+const SKIPPED_STREAMS: [&[u8]; 6] = [
+    b"/Image",
+    b"/FontFile",
+    b"/Length1",
+    b"/XRef",
+    b"/ObjStm",
+    b"/Metadata",
+];
+
+/// The text the pages of `path` draw, one text object per line
+///
+/// Meant for comparing documents, not for reading them: strings shown with `Tj`, `TJ`, `'`
+/// and `"` are collected from plain and FlateDecode streams and read as Latin-1 (UTF-16
+/// with a byte order mark). Fonts with their own encodings give garbled but repeatable
+/// text; encrypted files give none.
+pub fn extract_text(path: &Path) -> io::Result<String> {
+    let data = fs::read(path)?;
+    if !data.starts_with(b"%PDF") {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} is not a PDF", path.display()),
+        ));
+    }
+
+    let mut text = String::new();
+    for content in streams(&data) {
+        show_text(&content, &mut text);
+    }
+    Ok(text)
+}
+
+/// Decoded contents of the streams that may draw text
+fn streams(data: &[u8]) -> Vec<Vec<u8>> {
+    let mut streams = Vec::new();
+    let mut at = 0;
+    while let Some(found) = find(&data[at..], b"stream") {
+        let keyword = at + found;
+        at = keyword + b"stream".len();
+        if data[..keyword].ends_with(b"end") {
+            continue;
+        }
+
+        // The dictionary sits between `N 0 obj` and `stream`, the data after one EOL
+        let dict_start = rfind(&data[..keyword], b"obj").map_or(0, |i| i + 3);
+        let dict = &data[dict_start..keyword];
+        let mut start = at;
+        if data.get(start) == Some(&b'\r') {
+            start += 1;
+        }
+        if data.get(start) == Some(&b'\n') {
+            start += 1;
+        }
+        let Some(length) = find(&data[start..], b"endstream") else {
+            break;
+        };
+        let raw = &data[start..start + length];
+        at = start + length + b"endstream".len();
+
+        if SKIPPED_STREAMS.iter().any(|key| find(dict, key).is_some()) {
+            continue;
+        }
+        let filters = dict.windows(6).filter(|w| w == b"Decode").count();
+        match (filters, find(dict, b"/FlateDecode")) {
+            (0, _) => streams.push(raw.to_vec()),
+            (1, Some(_)) => {
+                // A damaged stream still gives what was inflated before the damage
+                let mut inflated = Vec::new();
+                let _ = ZlibDecoder::new(raw)
+                    .take(MAX_STREAM_BYTES)
+                    .read_to_end(&mut inflated);
+                streams.push(inflated);
+            }
+            _ => {}
+        }
+    }
+    streams
+}
+
+/// Appends the strings a content stream shows to `out`
+fn show_text(content: &[u8], out: &mut String) {
+    let mut shown = String::new();
+    let mut in_array = false;
+    let mut i = 0;
+    while i < content.len() {
+        match content[i] {
+            b'(' => i = literal_string(content, i + 1, &mut shown),
+            b'<' if content.get(i + 1) == Some(&b'<') => i += 2,
+            b'<' => i = hex_string(content, i + 1, &mut shown),
+            b'[' => {
+                in_array = true;
+                i += 1;
+            }
+            b']' => {
+                in_array = false;
+                i += 1;
+            }
+            b'%' => {
+                while i < content.len() && !matches!(content[i], b'\r' | b'\n') {
+                    i += 1;
+                }
+            }
+            b'/' => {
+                i += 1;
+                while i < content.len() && is_regular(content[i]) {
+                    i += 1;
+                }
+            }
+            b'+' | b'-' | b'.' | b'0'..=b'9' => {
+                let start = i;
+                i += 1;
+                while i < content.len() && matches!(content[i], b'.' | b'0'..=b'9') {
+                    i += 1;
+                }
+                // Wide gaps inside a TJ array are the spaces between words
+                let gap = std::str::from_utf8(&content[start..i])
+                    .ok()
+                    .and_then(|n| n.parse::<f64>().ok());
+                if in_array && gap.is_some_and(|gap| gap <= -200.0) {
+                    shown.push(' ');
+                }
+            }
+            c if is_regular(c) => {
+                let start = i;
+                while i < content.len() && is_regular(content[i]) {
+                    i += 1;
+                }
+                match &content[start..i] {
+                    b"Tj" | b"TJ" => out.push_str(&shown),
+                    b"'" | b"\"" => {
+                        out.push('\n');
+                        out.push_str(&shown);
+                    }
+                    b"Td" | b"TD" | b"T*" | b"Tm" => out.push(' '),
+                    b"ET" => out.push('\n'),
+                    // Inline image data may hold anything, up to its `EI`
+                    b"ID" => {
+                        i = find(&content[i..], b"EI").map_or(content.len(), |end| i + end + 2)
+                    }
+                    _ => {}
+                }
+                shown.clear();
+            }
+            _ => i += 1,
+        }
+    }
+}
+
+/// Reads a `(...)` string starting after the parenthesis, returning where it ends
+fn literal_string(content: &[u8], mut i: usize, shown: &mut String) -> usize {
+    let mut bytes = Vec::new();
+    let mut depth = 1;
+    while i < content.len() {
+        let c = content[i];
+        i += 1;
+        match c {
+            b'(' => depth += 1,
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            b'\\' => {
+                let Some(&escaped) = content.get(i) else {
+                    break;
+                };
+                i += 1;
+                let byte = match escaped {
+                    b'n' => b'\n',
+                    b'r' => b'\r',
+                    b't' => b'\t',
+                    b'b' => 0x08,
+                    b'f' => 0x0c,
+                    b'0'..=b'7' => {
+                        let mut value = u32::from(escaped - b'0');
+                        for _ in 0..2 {
+                            match content.get(i) {
+                                Some(&d @ b'0'..=b'7') => {
+                                    value = value * 8 + u32::from(d - b'0');
+                                    i += 1;
+                                }
+                                _ => break,
+                            }
+                        }
+                        value as u8
+                    }
+                    // A backslash before the line break continues the string
+                    b'\r' | b'\n' => {
+                        if escaped == b'\r' && content.get(i) == Some(&b'\n') {
+                            i += 1;
+                        }
+                        continue;
+                    }
+                    other => other,
+                };
+                bytes.push(byte);
+                continue;
+            }
+            _ => {}
+        }
+        bytes.push(c);
+    }
+    shown.push_str(&decode(&bytes));
+    i
+}
+
+/// Reads a `<...>` string starting after the bracket, returning where it ends
+fn hex_string(content: &[u8], mut i: usize, shown: &mut String) -> usize {
+    let mut digits = Vec::new();
+    while i < content.len() && content[i] != b'>' {
+        if let Some(digit) = (content[i] as char).to_digit(16) {
+            digits.push(digit as u8);
+        }
+        i += 1;
+    }
+    if digits.len() % 2 == 1 {
+        digits.push(0);
+    }
+    let bytes: Vec<u8> = digits
+        .chunks(2)
+        .map(|pair| pair[0] << 4 | pair[1])
+        .collect();
+    shown.push_str(&decode(&bytes));
+    i + 1
+}
+
+fn decode(bytes: &[u8]) -> String {
+    match bytes.strip_prefix(&[0xfe, 0xff]) {
+        Some(utf16) => {
+            let units: Vec<u16> = utf16
+                .chunks_exact(2)
+                .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        None => bytes.iter().map(|&b| b as char).collect(),
+    }
+}
+
+/// Not whitespace and not a PDF delimiter
+fn is_regular(c: u8) -> bool {
+    !c.is_ascii_whitespace()
+        && c != 0
+        && !matches!(
+            c,
+            b'(' | b')' | b'<' | b'>' | b'[' | b']' | b'{' | b'}' | b'/' | b'%'
+        )
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+fn rfind(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).rposition(|w| w == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strings_shown_by_text_operators() {
+        let mut text = String::new();
+        show_text(
+            br"BT /F1 12 Tf 72 712 Td (Quarterly \(draft\) budget) Tj 0 -14 Td
+               [(Nor) 20 (thern) -300 (region)] TJ T* <FEFF00E9007400E9> Tj ET
+               BT (caf\351) ' ET",
+            &mut text,
+        );
+        assert_eq!(
+            text,
+            " Quarterly (draft) budget Northern region été\n\ncafé\n"
+        );
+    }
+}
@@ -0,0 +1,565 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for the default pipeline (categorise, then group inside each category) for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::archive::{self, ArchiveConfig, ArchiveKind, ExtractedAction};
+use super::categorise::{FileOrganizer, FileOrganizerConfig};
+use super::feedback::Feedback;
+use super::filename::{SimilarityConfig, SimilarityOrganizer};
+use super::intelligent::{ClusterOrganizer, IntelligentConfig};
+use super::journal::{EntryKind, Journal};
+use super::naming;
+use super::strategy::{Organizer, Target};
+use super::transfer::{Output, TransferMode};
+use crate::error::{FileFailure, TyrError};
+
+/// Grouping applied inside each category folder after categorising
+#[derive(Debug, Clone)]
+pub enum Grouping {
+    /// Categorise only
+    None,
+    /// Filename similarity (`enable_smart_grouping`)
+    Similarity(SimilarityConfig),
+    /// TF-IDF + k-means clustering (`enable_intelligent_grouping`)
+    Intelligent(IntelligentConfig),
+}
+
+impl Grouping {
+    fn organizer(&self, feedback: Arc<Feedback>) -> Option<Box<dyn Organizer>> {
+        match self {
+            Grouping::None => None,
+            Grouping::Similarity(config) => Some(Box::new(
+                SimilarityOrganizer::new(config.clone()).with_feedback(feedback),
+            )),
+            Grouping::Intelligent(config) => Some(Box::new(
+                ClusterOrganizer::new(config.clone()).with_feedback(feedback),
+            )),
+        }
+    }
+}
+
+/// A single file move the pipeline wants to make
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedMove {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+/// Every move of one run, worked out before anything touches the disk
+#[derive(Debug)]
+pub struct Plan {
+    pub base_path: PathBuf,
+    pub output: Output,
+    pub moves: Vec<PlannedMove>,
+    /// Files of `action = "trash"` categories (and extracted archives, if so configured)
+    pub trash: Vec<PathBuf>,
+    /// Archives to extract once moved, by their new path (`archive_config.auto_extract`)
+    pub extract: Vec<PathBuf>,
+    /// Archives whose contents already sit in the folder next to them
+    pub already_extracted: Vec<PathBuf>,
+    /// Files that were planned despite a problem (a failing plugin); they are reported
+    /// with the run's own failures
+    pub failures: Vec<FileFailure>,
+}
+
+/// Outcome of executing a plan
+#[derive(Debug)]
+pub struct ExecuteResult {
+    pub files_moved: usize,
+    pub files_trashed: usize,
+    pub archives_extracted: usize,
+    /// Files that could not be moved, trashed or extracted
+    pub failures: Vec<FileFailure>,
+    /// Moves that succeeded, for `tyr undo`
+    pub journal: Journal,
+    /// The `pre_run` hook failed, so nothing was touched; its error is the only failure
+    pub aborted: bool,
+}
+
+impl ExecuteResult {
+    fn new(journal: Journal, failures: Vec<FileFailure>, archives_extracted: usize) -> Self {
+        let count = |kinds: &[EntryKind]| {
+            journal
+                .moves
+                .iter()
+                .filter(|m| kinds.contains(&m.kind))
+                .count()
+        };
+        Self {
+            files_moved: count(&[EntryKind::Move, EntryKind::Copy]),
+            files_trashed: count(&[EntryKind::Trash]),
+            archives_extracted,
+            failures,
+            journal,
+            aborted: false,
+        }
+    }
+}
+
+pub struct Pipeline {
+    organizer: FileOrganizer,
+    compound_extensions: Vec<String>,
+    archives: ArchiveConfig,
+    grouping: Grouping,
+    /// Runs on the files of each category, from `grouping`
+    grouper: Option<Box<dyn Organizer>>,
+    output: Output,
+}
+
+impl Pipeline {
+    pub fn new(organizer_config: FileOrganizerConfig, grouping: Grouping, output: Output) -> Self {
+        Self {
+            compound_extensions: organizer_config.compound_extensions(),
+            archives: organizer_config.archives.clone(),
+            organizer: FileOrganizer::new(organizer_config),
+            grouper: grouping.organizer(Arc::default()),
+            grouping,
+            output,
+        }
+    }
+
+    /// Groups inside the categories with the user's earlier corrections in mind
+    pub fn with_feedback(mut self, feedback: Arc<Feedback>) -> Self {
+        self.grouper = self.grouping.organizer(feedback);
+        self
+    }
+
+    /// Human readable list of the steps, e.g. "categorise → filename similarity"
+    pub fn describe(&self) -> String {
+        std::iter::once(self.organizer.name())
+            .chain(self.grouper.as_ref().map(|g| g.name()))
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+            .join(" → ")
+    }
+
+    /// Builds the full plan for `base_path` without moving anything
+    pub fn plan(&self, base_path: &Path) -> io::Result<Plan> {
+        // Step 1: category folder per file
+        let mut by_category: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+        let mut trash = Vec::new();
+        let mut to_extract = HashSet::new();
+        let mut already_extracted = Vec::new();
+        let copying = self.output.mode == TransferMode::Copy;
+
+        for assignment in self.organizer.plan_directory(base_path)? {
+            let file = assignment.file;
+            if self.is_extracted(&file) {
+                already_extracted.push(file.clone());
+                if self.archives.extracted_action == ExtractedAction::Trash && !copying {
+                    trash.push(file);
+                    continue;
+                }
+            } else if self.archives.auto_extract && ArchiveKind::from_path(&file).is_some() {
+                to_extract.insert(file.clone());
+            }
+
+            match assignment.target {
+                Target::Folder(folder_name) => {
+                    by_category.entry(folder_name).or_default().push(file)
+                }
+                // Copying never deletes anything, so these stay where they are
+                Target::Trash if copying => {}
+                Target::Trash => trash.push(file),
+                Target::Keep => {}
+            }
+        }
+
+        // Step 2: optional sub-groups inside each category
+        let root = self.output.root(base_path);
+        let mut reserved = HashSet::new();
+        let mut moves = Vec::new();
+        let mut extract = Vec::new();
+
+        for (folder_name, files) in by_category {
+            let category_dir = root.join(&folder_name);
+
+            for (file, sub_folder) in self.group_category(&files)? {
+                let target_dir = match sub_folder {
+                    Some(name) => category_dir.join(name),
+                    None => category_dir.clone(),
+                };
+                let Some(filename) = file.file_name() else {
+                    continue;
+                };
+                // Free on disk and not promised to another file of this plan
+                let to = naming::resolve_conflict(
+                    &target_dir.join(filename),
+                    &self.compound_extensions,
+                    |p| p.exists() || reserved.contains(p),
+                )?;
+                reserved.insert(to.clone());
+                if to_extract.contains(&file) {
+                    extract.push(to.clone());
+                }
+                moves.push(PlannedMove { from: file, to });
+            }
+        }
+
+        Ok(Plan {
+            base_path: base_path.to_path_buf(),
+            output: self.output.clone(),
+            moves,
+            trash,
+            extract,
+            already_extracted,
+            failures: self.take_failures(),
+        })
+    }
+
+    /// Carries out a plan, recording every successful move (or copy) in the journal
+    ///
+    /// Archives are extracted last, once they sit in their new folder, and the extracted
+    /// files are categorised inside the extraction folder.
+    pub fn execute(&self, plan: &Plan) -> ExecuteResult {
+        with_hooks(plan, |journal, failures| {
+            transfer_all(plan, journal, failures);
+
+            let mut archives_extracted = 0;
+            for path in plan.extract.iter().filter(|p| p.exists()) {
+                match self.extract_archive(path, journal) {
+                    Ok(()) => archives_extracted += 1,
+                    Err(source) => failures.push(FileFailure::new(
+                        path,
+                        TyrError::ExtractFailed {
+                            archive: path.clone(),
+                            source,
+                        },
+                    )),
+                }
+                failures.extend(self.organizer.take_failures());
+            }
+            archives_extracted
+        })
+    }
+
+    fn take_failures(&self) -> Vec<FileFailure> {
+        let mut failures = self.organizer.take_failures();
+        if let Some(grouper) = &self.grouper {
+            failures.extend(grouper.take_failures());
+        }
+        failures
+    }
+
+    /// An archive with every entry already in the folder next to it
+    fn is_extracted(&self, path: &Path) -> bool {
+        let settings = &self.archives;
+        let wanted = settings.auto_extract || settings.extracted_action != ExtractedAction::Keep;
+        if !wanted || ArchiveKind::from_path(path).is_none() {
+            return false;
+        }
+        archive::extraction_dir(path).is_some_and(|dir| dir.is_dir())
+            && fs::metadata(path).is_ok_and(|m| m.len() <= settings.max_bytes())
+            && archive::list_entries(path, settings.max_bytes())
+                .is_ok_and(|entries| archive::is_extracted(path, &entries))
+    }
+
+    /// Extracts `path` next to itself and sorts the top-level files it produced into
+    /// category folders inside the extraction folder
+    fn extract_archive(&self, path: &Path, journal: &mut Journal) -> io::Result<()> {
+        let Some(into) = archive::extraction_dir(path) else {
+            return Ok(());
+        };
+        if into.exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} already exists", into.display()),
+            ));
+        }
+
+        let files = match archive::extract(path, &into, self.archives.max_bytes()) {
+            Ok(files) => files,
+            Err(e) => {
+                // The folder did not exist before, so nothing of the user's is lost
+                let _ = fs::remove_dir_all(&into);
+                return Err(e);
+            }
+        };
+        for file in &files {
+            journal.record_kind(path, file, EntryKind::Extract);
+        }
+
+        let in_place = Output::default();
+        for assignment in self.organizer.plan_directory(&into)? {
+            let file = assignment.file;
+            let (Target::Folder(folder), Some(filename)) = (assignment.target, file.file_name())
+            else {
+                continue;
+            };
+            let to = naming::resolve_conflict(
+                &into.join(folder).join(filename),
+                &self.compound_extensions,
+                |p| p.exists(),
+            )?;
+            in_place.transfer(&file, &to)?;
+            journal.record(&file, &to, &in_place);
+        }
+        Ok(())
+    }
+
+    /// Pairs every file of one category with its sub-folder (None = stays in the category)
+    fn group_category(&self, files: &[PathBuf]) -> io::Result<Vec<(PathBuf, Option<String>)>> {
+        let Some(grouper) = &self.grouper else {
+            return Ok(files.iter().map(|f| (f.clone(), None)).collect());
+        };
+
+        let assignments = grouper.analyze(files)?;
+        let mut sizes: HashMap<&str, usize> = HashMap::new();
+        for assignment in &assignments {
+            if let Target::Folder(folder) = &assignment.target {
+                *sizes.entry(folder).or_default() += 1;
+            }
+        }
+
+        let mut assigned: Vec<(PathBuf, Option<String>)> = assignments
+            .iter()
+            .map(|assignment| {
+                let folder = match &assignment.target {
+                    Target::Folder(folder)
+                        if worth_a_folder(sizes[folder.as_str()], files.len()) =>
+                    {
+                        Some(folder.clone())
+                    }
+                    _ => None,
+                };
+                (assignment.file.clone(), folder)
+            })
+            .collect();
+        // Files the grouper left out (e.g. in clusters too small to keep) stay in the category
+        let seen: HashSet<&PathBuf> = assignments.iter().map(|a| &a.file).collect();
+        assigned.extend(
+            files
+                .iter()
+                .filter(|f| !seen.contains(f))
+                .map(|f| (f.clone(), None)),
+        );
+        assigned.sort();
+        Ok(assigned)
+    }
+}
+
+/// Carries out the moves and trashing of a plan, for plans that extract no archives
+pub fn execute_moves(plan: &Plan) -> ExecuteResult {
+    with_hooks(plan, |journal, failures| {
+        transfer_all(plan, journal, failures);
+        0
+    })
+}
+
+/// Runs `execute` between the plan's `pre_run` and `post_run` hooks
+///
+/// A failing `pre_run` stops the run before anything moves and is its only failure.
+/// Plans with nothing to do run no hooks at all.
+fn with_hooks(
+    plan: &Plan,
+    execute: impl FnOnce(&mut Journal, &mut Vec<FileFailure>) -> usize,
+) -> ExecuteResult {
+    let hooks = &plan.output.hooks;
+    let mut journal = Journal::new(&plan.base_path, &plan.output);
+    let mut failures = plan.failures.clone();
+    let busy = !(plan.moves.is_empty() && plan.trash.is_empty());
+
+    if busy {
+        if let Err(e) = hooks.pre_run(&plan.base_path) {
+            failures = vec![FileFailure::new(&plan.base_path, e)];
+            let mut result = ExecuteResult::new(journal, failures, 0);
+            result.aborted = true;
+            return result;
+        }
+    }
+
+    let archives_extracted = execute(&mut journal, &mut failures);
+    let mut result = ExecuteResult::new(journal, failures, archives_extracted);
+
+    if busy {
+        let (moved, failed) = (result.files_moved, result.failures.len());
+        if let Err(e) = hooks.post_run(&plan.base_path, moved, failed) {
+            result.failures.push(FileFailure::new(&plan.base_path, e));
+        }
+    }
+    result
+}
+
+/// Moves (or copies) and trashes the files of a plan, never replacing an existing file
+///
+/// A failing `post_move` hook is recorded as a failure of its file, which stays moved.
+fn transfer_all(plan: &Plan, journal: &mut Journal, failures: &mut Vec<FileFailure>) {
+    for planned in &plan.moves {
+        // Appeared after planning
+        if planned.to.exists() {
+            failures.push(FileFailure::new(
+                &planned.from,
+                TyrError::Conflict(planned.to.clone()),
+            ));
+            continue;
+        }
+
+        match plan.output.transfer(&planned.from, &planned.to) {
+            Ok(()) => {
+                journal.record(&planned.from, &planned.to, &plan.output);
+                let hooked =
+                    plan.output
+                        .hooks
+                        .post_move(&plan.base_path, &planned.from, &planned.to);
+                if let Err(e) = hooked {
+                    failures.push(FileFailure::new(&planned.from, e));
+                }
+            }
+            Err(e) => failures.push(FileFailure::new(
+                &planned.from,
+                TyrError::transfer(&planned.from, &planned.to, e),
+            )),
+        }
+    }
+
+    for file in &plan.trash {
+        match plan.output.trash(file) {
+            Ok(trashed) => journal.record_kind(file, &trashed, EntryKind::Trash),
+            Err(source) => failures.push(FileFailure::new(
+                file,
+                TyrError::TrashFailed {
+                    path: file.clone(),
+                    source,
+                },
+            )),
+        }
+    }
+}
+
+/// A sub-folder needs at least two files, and one holding the whole category adds nothing
+fn worth_a_folder(group_size: usize, category_size: usize) -> bool {
+    group_size >= 2 && group_size < category_size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_similarity_groups_inside_category_folders() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        for name in [
+            "invoice_2024_01.pdf",
+            "invoice_2024_02.pdf",
+            "resume.pdf",
+            "song.mp3",
+        ] {
+            fs::write(base.join(name), "x").unwrap();
+        }
+
+        let pipeline = Pipeline::new(
+            FileOrganizerConfig::default(),
+            Grouping::Similarity(SimilarityConfig::default()),
+            Output::default(),
+        );
+        let plan = pipeline.plan(base).unwrap();
+
+        let target_of = |name: &str| {
+            plan.moves
+                .iter()
+                .find(|m| m.from == base.join(name))
+                .map(|m| m.to.strip_prefix(base).unwrap().to_path_buf())
+                .unwrap()
+        };
+
+        let invoice = target_of("invoice_2024_01.pdf");
+        assert!(invoice.starts_with("Documents"));
+        assert_eq!(invoice.components().count(), 3);
+        assert_eq!(invoice.parent(), target_of("invoice_2024_02.pdf").parent());
+        assert_eq!(target_of("resume.pdf"), Path::new("Documents/resume.pdf"));
+        assert_eq!(target_of("song.mp3"), Path::new("Audio/song.mp3"));
+
+        let result = pipeline.execute(&plan);
+        assert_eq!(result.files_moved, 4);
+        assert_eq!(result.journal.undo().restored, 4);
+        assert!(base.join("song.mp3").exists());
+    }
+
+    #[test]
+    fn test_intelligent_grouping_of_text_and_binary_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        fs::write(base.join("a.txt"), "rent lease tenant landlord deposit").unwrap();
+        fs::write(base.join("b.txt"), "rent lease tenant landlord keys").unwrap();
+        fs::write(base.join("c.pdf"), [0u8, 159, 146, 150]).unwrap();
+        fs::write(base.join("e.pdf"), [0u8, 1, 2, 3]).unwrap();
+
+        let pipeline = Pipeline::new(
+            FileOrganizerConfig::default(),
+            Grouping::Intelligent(IntelligentConfig::default()),
+            Output::default(),
+        );
+        let plan = pipeline.plan(base).unwrap();
+
+        assert_eq!(plan.moves.len(), 4);
+        assert!(plan.moves.iter().all(|m| m
+            .to
+            .strip_prefix(base)
+            .unwrap()
+            .starts_with("Documents")));
+    }
+
+    #[test]
+    fn test_archive_of_pdfs_is_filed_and_extracted() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        let mut zip = zip::ZipWriter::new(fs::File::create(base.join("papers.zip")).unwrap());
+        for name in ["a.pdf", "b.pdf", "notes.txt"] {
+            zip.start_file(name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            zip.write_all(b"x").unwrap();
+        }
+        zip.finish().unwrap();
+
+        let mut config = FileOrganizerConfig::default();
+        config.archives.inspect_contents = true;
+        config.archives.auto_extract = true;
+        let pipeline = Pipeline::new(config, Grouping::None, Output::default());
+        let plan = pipeline.plan(base).unwrap();
+        assert_eq!(plan.extract, vec![base.join("Documents/papers.zip")]);
+
+        let result = pipeline.execute(&plan);
+        assert!(result.failures.is_empty(), "{:?}", result.failures);
+        assert_eq!(result.archives_extracted, 1);
+        assert!(base.join("Documents/papers/Documents/a.pdf").exists());
+        assert!(base.join("Documents/papers/Documents/notes.txt").exists());
+
+        let undo = result.journal.undo();
+        assert!(undo.errors.is_empty(), "{:?}", undo.errors);
+        assert!(base.join("papers.zip").exists());
+        assert!(!base.join("Documents").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_failing_pre_run_hook_aborts_the_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        fs::write(base.join("song.mp3"), "x").unwrap();
+
+        let mut output = Output::default();
+        output.hooks.pre_run = "exit 1".to_string();
+        output.hooks.post_run = format!("touch '{}'", base.join("ran").display());
+        let pipeline = Pipeline::new(FileOrganizerConfig::default(), Grouping::None, output);
+        let result = pipeline.execute(&pipeline.plan(base).unwrap());
+
+        assert!(result.aborted);
+        assert_eq!(result.files_moved, 0);
+        assert!(base.join("song.mp3").exists());
+        assert!(!base.join("ran").exists());
+    }
+}
@@ -0,0 +1,363 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for running user supplied classifier plugins for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use super::strategy::Target;
+use super::template;
+
+/// External classifier settings (`[plugin_config]`)
+///
+/// The plugin is any program that reads one JSON object per line on stdin (a `Request`)
+/// and answers each with one JSON line on stdout (a `Decision`).
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PluginConfig {
+    /// Program and its arguments; empty means no plugin
+    pub command: Vec<String>,
+    pub consult: Consult,
+    /// How long the plugin may take to answer for one file
+    pub timeout_secs: u64,
+}
+
+impl Default for PluginConfig {
+    fn default() -> Self {
+        Self {
+            command: Vec::new(),
+            consult: Consult::After,
+            timeout_secs: 10,
+        }
+    }
+}
+
+/// When the plugin is asked, relative to the built-in categories
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Consult {
+    /// About every file; its answer wins over the categories
+    Before,
+    /// Only about files no category claims (they would go to Extras)
+    After,
+}
+
+impl PluginConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.command.is_empty()
+    }
+
+    /// Problems with the settings, as messages for `tyr config check`
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.command.first().is_some_and(|p| p.trim().is_empty()) {
+            problems.push("command: the program name is empty".to_string());
+        }
+        if self.timeout_secs == 0 {
+            problems.push("timeout_secs must be greater than 0".to_string());
+        }
+        problems
+    }
+
+    /// e.g. "python3 route.py (after the categories)"
+    pub fn describe(&self) -> String {
+        let when = match self.consult {
+            Consult::Before => "before",
+            Consult::After => "after",
+        };
+        format!("{} ({} the categories)", self.command.join(" "), when)
+    }
+}
+
+/// What the plugin is told about a file
+#[derive(Debug, Serialize)]
+pub struct Request {
+    pub path: String,
+    pub name: String,
+    /// Lowercased, without the dot
+    pub extension: Option<String>,
+    pub size: u64,
+    /// Where the built-in categories would put the file, null when none claims it
+    pub builtin: Option<Decision>,
+}
+
+impl Request {
+    pub fn new(path: &Path, builtin: Option<&Target>) -> Self {
+        Self {
+            path: path.to_string_lossy().into_owned(),
+            name: path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            extension: path.extension().map(|e| e.to_string_lossy().to_lowercase()),
+            size: path.metadata().map(|m| m.len()).unwrap_or(0),
+            builtin: builtin.map(Decision::from_target),
+        }
+    }
+}
+
+/// A plugin's answer for one file; `{}` leaves the file to the categories
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct Decision {
+    /// Folder relative to the target, may be nested (`Customers/ACME`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+    /// Takes precedence over `folder`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<Action>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    Trash,
+    Keep,
+}
+
+impl Decision {
+    pub fn from_target(target: &Target) -> Self {
+        match target {
+            Target::Folder(folder) => Decision {
+                folder: Some(folder.clone()),
+                action: None,
+            },
+            Target::Trash => Decision {
+                folder: None,
+                action: Some(Action::Trash),
+            },
+            Target::Keep => Decision {
+                folder: None,
+                action: Some(Action::Keep),
+            },
+        }
+    }
+
+    /// `None` when the plugin has no opinion; folders leaving the target are an error
+    pub fn into_target(self) -> Result<Option<Target>, String> {
+        match (self.action, self.folder) {
+            (Some(Action::Trash), _) => Ok(Some(Target::Trash)),
+            (Some(Action::Keep), _) => Ok(Some(Target::Keep)),
+            (None, None) => Ok(None),
+            (None, Some(folder)) if folder.trim().is_empty() => Ok(None),
+            (None, Some(folder)) if !template::stays_inside(Path::new(&folder)) => Err(format!(
+                "answered folder '{}', which is outside the target folder",
+                folder
+            )),
+            (None, Some(folder)) => Ok(Some(Target::Folder(folder))),
+        }
+    }
+}
+
+/// A running plugin, asked about one file at a time
+///
+/// The process lives for one run; closing its stdin when dropped tells it to exit.
+pub struct Plugin {
+    program: String,
+    child: Child,
+    stdin: Option<ChildStdin>,
+    replies: Receiver<io::Result<String>>,
+    stderr: Option<JoinHandle<String>>,
+    timeout: Duration,
+}
+
+impl Plugin {
+    pub fn spawn(config: &PluginConfig) -> io::Result<Self> {
+        let Some((program, args)) = config.command.split_first() else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "plugin_config.command is empty",
+            ));
+        };
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                io::Error::new(
+                    e.kind(),
+                    format!("could not start plugin '{}': {}", program, e),
+                )
+            })?;
+
+        // Read on threads, so a silent plugin runs into the timeout instead of hanging tyr
+        let (sender, replies) = mpsc::channel();
+        if let Some(stdout) = child.stdout.take() {
+            thread::spawn(move || {
+                for line in BufReader::new(stdout).lines() {
+                    if sender.send(line).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        let stderr = child.stderr.take().map(|mut stderr| {
+            thread::spawn(move || {
+                let mut text = String::new();
+                let _ = stderr.read_to_string(&mut text);
+                text
+            })
+        });
+
+        Ok(Self {
+            program: program.clone(),
+            stdin: child.stdin.take(),
+            child,
+            replies,
+            stderr,
+            timeout: Duration::from_secs(config.timeout_secs),
+        })
+    }
+
+    /// Asks where `request`'s file goes; `None` when the plugin leaves it to the categories
+    pub fn ask(&mut self, request: &Request) -> io::Result<Option<Target>> {
+        let mut line = serde_json::to_string(request)?;
+        line.push('\n');
+        let sent = match self.stdin.as_mut() {
+            Some(stdin) => stdin.write_all(line.as_bytes()).and_then(|_| stdin.flush()),
+            None => Err(io::ErrorKind::BrokenPipe.into()),
+        };
+        if let Err(e) = sent {
+            return Err(self.failed(format_args!("stopped reading ({})", e)));
+        }
+
+        let reply = match self.replies.recv_timeout(self.timeout) {
+            Ok(Ok(reply)) => reply,
+            Ok(Err(e)) => return Err(self.failed(format_args!("could not be read ({})", e))),
+            Err(RecvTimeoutError::Timeout) => {
+                let secs = self.timeout.as_secs();
+                return Err(self.failed(format_args!(
+                    "did not answer within {}s for {}",
+                    secs, request.name
+                )));
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                return Err(self.failed(format_args!("exited before answering")))
+            }
+        };
+
+        let decision: Decision = match serde_json::from_str(&reply) {
+            Ok(decision) => decision,
+            Err(e) => {
+                return Err(self.failed(format_args!("sent '{}' ({})", reply.trim(), e)));
+            }
+        };
+        decision
+            .into_target()
+            .map_err(|problem| self.failed(format_args!("{} for {}", problem, request.name)))
+    }
+
+    /// Stops the plugin and describes what went wrong, with the last line of its stderr
+    /// when it exited on its own
+    fn failed(&mut self, problem: fmt::Arguments) -> io::Error {
+        let exited = self.exits_within(Duration::from_millis(200));
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        // A killed plugin's own children may still hold stderr open, so only wait for
+        // it when the plugin exited by itself
+        let stderr = match self.stderr.take() {
+            Some(handle) if exited => handle.join().unwrap_or_default(),
+            _ => String::new(),
+        };
+
+        let message = format!("plugin '{}' {}", self.program, problem);
+        io::Error::other(match stderr.lines().rev().find(|l| !l.trim().is_empty()) {
+            Some(last) => format!("{}: {}", message, last.trim()),
+            None => message,
+        })
+    }
+
+    /// Whether the plugin exits by itself within `grace`
+    fn exits_within(&mut self, grace: Duration) -> bool {
+        let step = Duration::from_millis(10);
+        let mut waited = Duration::ZERO;
+        loop {
+            if matches!(self.child.try_wait(), Ok(Some(_))) {
+                return true;
+            }
+            if waited >= grace {
+                return false;
+            }
+            thread::sleep(step);
+            waited += step;
+        }
+    }
+}
+
+impl Drop for Plugin {
+    fn drop(&mut self) {
+        // EOF on stdin is the signal to exit; give it a moment before killing it
+        self.stdin.take();
+        if !self.exits_within(Duration::from_millis(200)) {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn shell(script: &str) -> PluginConfig {
+        PluginConfig {
+            command: vec!["sh".to_string(), "-c".to_string(), script.to_string()],
+            timeout_secs: 5,
+            ..PluginConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_plugin_answers_per_line() {
+        let config = shell(
+            r#"while read -r line; do
+                 case "$line" in
+                   *ACME*) echo '{"folder": "Customers/ACME"}' ;;
+                   *junk*) echo '{"action": "trash"}' ;;
+                   *) echo '{}' ;;
+                 esac
+               done"#,
+        );
+        let mut plugin = Plugin::spawn(&config).unwrap();
+        let ask =
+            |plugin: &mut Plugin, name: &str| plugin.ask(&Request::new(Path::new(name), None));
+
+        assert_eq!(
+            ask(&mut plugin, "ACME-1042_invoice.pdf").unwrap(),
+            Some(Target::Folder("Customers/ACME".to_string()))
+        );
+        assert_eq!(ask(&mut plugin, "junk.tmp").unwrap(), Some(Target::Trash));
+        assert_eq!(ask(&mut plugin, "holiday.jpg").unwrap(), None);
+    }
+
+    #[test]
+    fn test_plugin_failures_are_reported() {
+        let mut escaping =
+            Plugin::spawn(&shell(r#"read -r line; echo '{"folder": "../x"}'"#)).unwrap();
+        let err = escaping
+            .ask(&Request::new(Path::new("a.txt"), None))
+            .unwrap_err();
+        assert!(err.to_string().contains("outside the target folder"));
+
+        let mut crashing =
+            Plugin::spawn(&shell("read -r line; echo 'no rules' >&2; exit 3")).unwrap();
+        let err = crashing
+            .ask(&Request::new(Path::new("a.txt"), None))
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("exited before answering: no rules"));
+    }
+}
@@ -0,0 +1,672 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for normalizing and bulk renaming files in place for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use chrono::{DateTime, Local, NaiveDate};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+use super::filename::detect_date;
+use super::ignore::IgnoreMatcher;
+use super::journal::Journal;
+use super::naming;
+use super::template;
+use super::transfer::Output;
+use crate::error::{FileFailure, TyrError};
+
+/// Variables a rename template may use
+pub const VARIABLES: [&str; 4] = ["name", "original", "date", "parent"];
+
+/// Rename settings (`[rename_config]`)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RenameConfig {
+    /// New file name without the extension, e.g. `{date}_{name}`
+    pub template: String,
+    /// Applied to `{name}` (and the extension) in this order
+    pub transforms: Vec<Transform>,
+    /// Where `{date}` comes from, first one that has a date wins
+    pub date_sources: Vec<DateSource>,
+    /// chrono format for `{date}`
+    pub date_format: String,
+}
+
+impl Default for RenameConfig {
+    fn default() -> Self {
+        Self {
+            template: "{date}_{name}".to_string(),
+            transforms: vec![
+                Transform::StripCopyMarkers,
+                Transform::Slugify,
+                Transform::Lowercase,
+            ],
+            date_sources: vec![DateSource::Exif, DateSource::Filename, DateSource::Modified],
+            date_format: "%Y-%m-%d".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Transform {
+    /// Drops `(1)`, ` - Copy` and `Copy of ` left behind by browsers and file managers
+    StripCopyMarkers,
+    /// Accents folded, anything but letters and digits turned into single dashes
+    Slugify,
+    Lowercase,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DateSource {
+    /// DateTimeOriginal of photos (JPEG, TIFF, HEIF, PNG, WebP)
+    Exif,
+    /// A date in the name itself (`IMG-20240131-WA0001`)
+    Filename,
+    /// Last modification time
+    Modified,
+}
+
+impl RenameConfig {
+    /// Problems with the template or date format, as messages for `tyr config check`
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        let mut used = Vec::new();
+        let checked = template::expand(&self.template, |name| {
+            if VARIABLES.contains(&name) {
+                used.push(name.to_string());
+                Ok(String::new())
+            } else {
+                Err(format!(
+                    "unknown variable {{{}}} (available: {{name}}, {{original}}, {{date}}, {{parent}})",
+                    name
+                ))
+            }
+        });
+        match checked {
+            Err(e) => problems.push(format!("template: {}", e)),
+            Ok(text) if text.contains(['/', '\\']) => {
+                problems.push("template: renames stay in their folder, no '/' allowed".to_string())
+            }
+            Ok(_) if !used.iter().any(|v| v == "name" || v == "original") => problems.push(
+                "template: needs {name} or {original}, or every file gets the same name"
+                    .to_string(),
+            ),
+            Ok(_) => {}
+        }
+
+        let items = chrono::format::StrftimeItems::new(&self.date_format);
+        if items
+            .clone()
+            .any(|item| matches!(item, chrono::format::Item::Error))
+        {
+            problems.push(format!(
+                "date_format: '{}' is not a valid date format",
+                self.date_format
+            ));
+        }
+        problems
+    }
+}
+
+/// One file to rename
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rename {
+    pub from: PathBuf,
+    pub to: PathBuf,
+    /// The wanted name was taken, so `to` got a `_N` suffix
+    pub numbered: bool,
+}
+
+/// Outcome of applying renames
+#[derive(Debug)]
+pub struct RenameResult {
+    pub renamed: usize,
+    pub failures: Vec<FileFailure>,
+    /// Renames that happened, so `tyr undo` can reverse them
+    pub journal: Journal,
+}
+
+pub struct Renamer {
+    config: RenameConfig,
+    skip_patterns: Vec<String>,
+}
+
+impl Renamer {
+    pub fn new(config: RenameConfig, skip_patterns: Vec<String>) -> Self {
+        Self {
+            config,
+            skip_patterns,
+        }
+    }
+
+    /// New file name for `path`, which may equal the current one
+    pub fn new_name(&self, path: &Path) -> Option<String> {
+        let filename = path.file_name()?.to_str()?;
+        let (stem, extension) = naming::split_extension(filename, &[]);
+
+        let (date, date_in_name) = self.find_date(path, stem);
+        // The date moves to {date}, so {name} should not repeat it
+        let moves_date = date.is_some() && self.config.template.contains("{date}");
+        let mut name = match date_in_name.filter(|_| moves_date) {
+            Some(range) => remove_range(stem, range),
+            None => stem.to_string(),
+        };
+        let mut extension = extension.map(str::to_string);
+        for transform in &self.config.transforms {
+            name = apply_transform(*transform, &name);
+            if *transform == Transform::Lowercase {
+                extension = extension.map(|e| e.to_lowercase());
+            }
+        }
+
+        let parent = path
+            .parent()
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let date = date
+            .map(|d| d.format(&self.config.date_format).to_string())
+            .unwrap_or_default();
+        let rendered = template::expand(&self.config.template, |variable| {
+            Ok(match variable {
+                "name" => name.clone(),
+                "original" => stem.to_string(),
+                "date" => date.clone(),
+                "parent" => parent.clone(),
+                other => return Err(format!("unknown variable {{{}}}", other)),
+            })
+        })
+        .ok()?;
+
+        // An empty {date} must not leave "_name" behind
+        let new_stem = rendered
+            .replace(['/', '\\'], "-")
+            .trim_matches(|c: char| matches!(c, '_' | '-' | '.' | ' '))
+            .to_string();
+        if new_stem.is_empty() {
+            return None;
+        }
+        Some(match extension {
+            Some(ext) => format!("{}.{}", new_stem, ext),
+            None => new_stem,
+        })
+    }
+
+    /// Date for `{date}` from the configured sources, plus where it sits in `stem` if the
+    /// name has one
+    fn find_date(
+        &self,
+        path: &Path,
+        stem: &str,
+    ) -> (Option<NaiveDate>, Option<std::ops::Range<usize>>) {
+        let in_name = detect_date(stem);
+        let date = self
+            .config
+            .date_sources
+            .iter()
+            .find_map(|source| match source {
+                DateSource::Exif => exif_date(path),
+                DateSource::Filename => in_name.as_ref().map(|(date, _)| *date),
+                DateSource::Modified => fs::metadata(path)
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .map(|t| DateTime::<Local>::from(t).date_naive()),
+            });
+        (date, in_name.map(|(_, range)| range))
+    }
+
+    /// Works out every rename in `dir` without touching anything
+    ///
+    /// Clashes (with files on disk or with another rename of the plan) get a `_N` suffix, so
+    /// no file is ever overwritten. Files whose name does not change are left out.
+    pub fn plan(&self, dir: &Path) -> io::Result<Vec<Rename>> {
+        let ignore = IgnoreMatcher::for_directory(dir, &self.skip_patterns)?;
+        let mut files: Vec<PathBuf> = fs::read_dir(dir)?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_file() && !ignore.is_ignored(p, false))
+            .collect();
+        files.sort();
+
+        let mut reserved = HashSet::new();
+        let mut renames = Vec::new();
+        for file in files {
+            let Some(name) = self.new_name(&file) else {
+                continue;
+            };
+            let wanted = file.with_file_name(&name);
+            if wanted == file {
+                reserved.insert(wanted);
+                continue;
+            }
+
+            let to = naming::resolve_conflict(&wanted, &[], |p| {
+                reserved.contains(p) || (p.exists() && !is_same_file(p, &file))
+            })?;
+            if to == file {
+                reserved.insert(to);
+                continue;
+            }
+            reserved.insert(to.clone());
+            renames.push(Rename {
+                numbered: to != wanted,
+                from: file,
+                to,
+            });
+        }
+
+        Ok(renames)
+    }
+}
+
+/// Applies renames in order, never replacing an existing file
+pub fn apply(base_path: &Path, renames: &[Rename]) -> RenameResult {
+    let output = Output::default();
+    let mut journal = Journal::new(base_path, &output);
+    let mut failures = Vec::new();
+
+    for rename in renames {
+        // Appeared after planning
+        if rename.to.exists() && !is_same_file(&rename.to, &rename.from) {
+            failures.push(FileFailure::new(
+                &rename.from,
+                TyrError::Conflict(rename.to.clone()),
+            ));
+            continue;
+        }
+        match fs::rename(&rename.from, &rename.to) {
+            Ok(()) => journal.record(&rename.from, &rename.to, &output),
+            Err(e) => failures.push(FileFailure::new(
+                &rename.from,
+                TyrError::io(&rename.from, e),
+            )),
+        }
+    }
+
+    RenameResult {
+        renamed: journal.moves.len(),
+        failures,
+        journal,
+    }
+}
+
+fn apply_transform(transform: Transform, name: &str) -> String {
+    match transform {
+        Transform::StripCopyMarkers => strip_copy_markers(name),
+        Transform::Slugify => slugify(name),
+        Transform::Lowercase => name.to_lowercase(),
+    }
+}
+
+static COPY_MARKER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^copy of\s+|(\s*\(\d{1,2}\)|(\s+|\s*-\s*)copy(\s*\(\d{1,2}\)|\s+\d{1,2})?)$")
+        .expect("valid regex")
+});
+
+/// `report (1)`, `report - Copy`, `Copy of report` → `report`
+///
+/// Only short counters count, so `Annual Report (2023)` keeps its year, and `copy` has to
+/// stand apart from the name, so `hard_copy` stays whole.
+fn strip_copy_markers(name: &str) -> String {
+    let mut name = name.to_string();
+    loop {
+        let stripped = COPY_MARKER.replace(&name, "").into_owned();
+        if stripped == name || stripped.trim().is_empty() {
+            return name;
+        }
+        name = stripped;
+    }
+}
+
+/// `Café Menü (final)` → `Cafe-Menu-final`; letters of other scripts are kept
+fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.nfkd().filter(|c| !is_combining_mark(*c)) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// `stem` without the bytes in `range`, separators around the gap merged
+fn remove_range(stem: &str, range: std::ops::Range<usize>) -> String {
+    let is_separator = |c: char| matches!(c, '_' | '-' | '.' | ' ');
+    let before = stem[..range.start].trim_end_matches(is_separator);
+    let after = stem[range.end..].trim_start_matches(is_separator);
+    match (before.is_empty(), after.is_empty()) {
+        (true, _) => after.to_string(),
+        (_, true) => before.to_string(),
+        // Keep the separator the name already used
+        _ => {
+            let separator = stem[before.len()..range.start]
+                .chars()
+                .next()
+                .unwrap_or('_');
+            format!("{}{}{}", before, separator, after)
+        }
+    }
+}
+
+/// DateTimeOriginal (or DateTime) from a photo's EXIF data
+fn exif_date(path: &Path) -> Option<NaiveDate> {
+    let file = File::open(path).ok()?;
+    let exif = exif::Reader::new()
+        .read_from_container(&mut BufReader::new(file))
+        .ok()?;
+    [exif::Tag::DateTimeOriginal, exif::Tag::DateTime]
+        .iter()
+        .find_map(|tag| {
+            let field = exif.get_field(*tag, exif::In::PRIMARY)?;
+            let exif::Value::Ascii(ref values) = field.value else {
+                return None;
+            };
+            let date = exif::DateTime::from_ascii(values.first()?).ok()?;
+            NaiveDate::from_ymd_opt(date.year.into(), date.month.into(), date.day.into())
+        })
+}
+
+#[cfg(unix)]
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (fs::metadata(a), fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+// TUI Implementation
+
+use crossterm::{
+    event::{self, Event, KeyCode},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Terminal,
+};
+use std::io::stdout;
+use std::time::Duration;
+
+pub struct RenameTuiApp {
+    base_path: PathBuf,
+    renames: Vec<Rename>,
+    list_state: ListState,
+    result: Option<RenameResult>,
+}
+
+impl RenameTuiApp {
+    pub fn new(config: RenameConfig, base_path: PathBuf, skip_patterns: Vec<String>) -> Self {
+        let renamer = Renamer::new(config, skip_patterns);
+        let renames = renamer.plan(&base_path).unwrap_or_default();
+        let mut list_state = ListState::default();
+        if !renames.is_empty() {
+            list_state.select(Some(0));
+        }
+
+        Self {
+            base_path,
+            renames,
+            list_state,
+            result: None,
+        }
+    }
+
+    pub fn renames(&self) -> &[Rename] {
+        &self.renames
+    }
+
+    /// What applying did, once it happened
+    pub fn take_result(&mut self) -> Option<RenameResult> {
+        self.result.take()
+    }
+
+    pub fn run(&mut self) -> io::Result<()> {
+        enable_raw_mode()?;
+        let mut stdout = stdout();
+        execute!(stdout, EnterAlternateScreen)?;
+
+        let backend = CrosstermBackend::new(stdout);
+        let mut terminal = Terminal::new(backend)?;
+
+        let result = self.run_app(&mut terminal);
+
+        disable_raw_mode()?;
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        terminal.show_cursor()?;
+
+        result
+    }
+
+    fn run_app(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    ) -> io::Result<()> {
+        loop {
+            terminal.draw(|f| self.draw_ui(f))?;
+
+            if event::poll(Duration::from_millis(100))? {
+                if let Event::Key(key) = event::read()? {
+                    match key.code {
+                        KeyCode::Char('q') | KeyCode::Esc => break,
+                        KeyCode::Down | KeyCode::Char('j') => self.select(1),
+                        KeyCode::Up | KeyCode::Char('k') => self.select(-1),
+                        KeyCode::Char('s') if self.result.is_none() && !self.renames.is_empty() => {
+                            self.result = Some(apply(&self.base_path, &self.renames));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn select(&mut self, step: isize) {
+        if self.renames.is_empty() {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0) as isize;
+        let next = (current + step).clamp(0, self.renames.len() as isize - 1);
+        self.list_state.select(Some(next as usize));
+    }
+
+    fn draw_ui(&mut self, f: &mut ratatui::Frame) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(10),
+                Constraint::Length(3),
+            ])
+            .split(f.size());
+
+        let title = Paragraph::new(format!(
+            " Tyr - Rename Files in {}",
+            self.base_path.display()
+        ))
+        .style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(Block::default().borders(Borders::ALL));
+        f.render_widget(title, chunks[0]);
+
+        let done = self.result.is_some();
+        let items: Vec<ListItem> = self
+            .renames
+            .iter()
+            .map(|rename| {
+                let name = |p: &Path| {
+                    p.file_name()
+                        .map(|n| n.to_string_lossy().into_owned())
+                        .unwrap_or_default()
+                };
+                let mut spans = vec![
+                    Span::raw(name(&rename.from)),
+                    Span::styled(" → ", Style::default().fg(Color::Gray)),
+                    Span::styled(name(&rename.to), Style::default().fg(Color::Green)),
+                ];
+                if rename.numbered {
+                    spans.push(Span::styled(
+                        "  (numbered, name was taken)",
+                        Style::default().fg(Color::Yellow),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let list_title = match &self.result {
+            Some(result) => format!(
+                " ✦ Renamed {} files, {} failed (undo with 'tyr undo') ",
+                result.renamed,
+                result.failures.len()
+            ),
+            None if self.renames.is_empty() => " Every name already fits the template ".to_string(),
+            None => format!(" Preview: {} renames ", self.renames.len()),
+        };
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(list_title))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        f.render_stateful_widget(list, chunks[1], &mut self.list_state);
+
+        let controls = if done {
+            " ↑/↓ Scroll | 'q' Quit"
+        } else {
+            " ↑/↓ Scroll | 's' Rename | 'q' Quit without renaming"
+        };
+        let widget = Paragraph::new(controls)
+            .style(Style::default().fg(Color::Yellow))
+            .block(Block::default().borders(Borders::ALL).title(" Controls "));
+        f.render_widget(widget, chunks[2]);
+    }
+
+    /// Prints the mapping and renames without UI interaction
+    pub fn auto_rename(&mut self) -> io::Result<()> {
+        for rename in &self.renames {
+            println!(
+                "   • {} → {}{}",
+                rename
+                    .from
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy(),
+                rename.to.file_name().unwrap_or_default().to_string_lossy(),
+                if rename.numbered { "  (numbered)" } else { "" }
+            );
+        }
+        self.result = Some(apply(&self.base_path, &self.renames));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_name_from_whatsapp_and_copies() {
+        let dir = tempfile::tempdir().unwrap();
+        let renamer = Renamer::new(RenameConfig::default(), Vec::new());
+        let name = |file: &str| {
+            let path = dir.path().join(file);
+            fs::write(&path, "x").unwrap();
+            renamer.new_name(&path).unwrap()
+        };
+
+        assert_eq!(name("IMG-20240131-WA0001.JPG"), "2024-01-31_img-wa0001.jpg");
+        assert_eq!(
+            name("2024-01-31_img-wa0001.jpg"),
+            "2024-01-31_img-wa0001.jpg"
+        );
+        let today = Local::now().format("%Y-%m-%d").to_string();
+        assert_eq!(
+            name("Café Menü (1).pdf"),
+            format!("{}_cafe-menu.pdf", today)
+        );
+        assert_eq!(
+            name("Copy of backup.tar.gz"),
+            format!("{}_backup.tar.gz", today)
+        );
+    }
+
+    #[test]
+    fn test_copy_markers_leave_years_and_words_alone() {
+        assert_eq!(strip_copy_markers("report (1)"), "report");
+        assert_eq!(strip_copy_markers("report - Copy (2)"), "report");
+        assert_eq!(strip_copy_markers("report copy 3"), "report");
+        assert_eq!(
+            strip_copy_markers("Annual Report (2023)"),
+            "Annual Report (2023)"
+        );
+        assert_eq!(
+            strip_copy_markers("Annual Report (2023) (1)"),
+            "Annual Report (2023)"
+        );
+        assert_eq!(strip_copy_markers("hard_copy"), "hard_copy");
+    }
+
+    #[test]
+    fn test_plan_numbers_clashes_and_undo_reverses() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        for name in ["Report.PDF", "report (1).pdf", "report.pdf"] {
+            fs::write(base.join(name), name).unwrap();
+        }
+        let config = RenameConfig {
+            template: "{name}".to_string(),
+            ..RenameConfig::default()
+        };
+
+        let renames = Renamer::new(config, Vec::new()).plan(base).unwrap();
+        // report.pdf keeps its name, the other two must not take it
+        let targets: HashSet<PathBuf> = renames.iter().map(|r| r.to.clone()).collect();
+        assert_eq!(renames.len(), 2);
+        assert!(!targets.contains(&base.join("report.pdf")));
+        assert!(renames.iter().all(|r| r.numbered));
+
+        let result = apply(base, &renames);
+        assert_eq!(result.renamed, 2);
+        assert_eq!(
+            fs::read_to_string(base.join("report.pdf")).unwrap(),
+            "report.pdf"
+        );
+
+        assert_eq!(result.journal.undo().restored, 2);
+        assert_eq!(
+            fs::read_to_string(base.join("Report.PDF")).unwrap(),
+            "Report.PDF"
+        );
+        assert!(base.join("report (1).pdf").exists());
+    }
+}
@@ -0,0 +1,761 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for the read-only disk usage report (`tyr report`) for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration as StdDuration, SystemTime};
+
+use super::buckets::{format_bytes, BucketConfig, BucketKind, BucketOrganizer};
+use super::categorise::FileOrganizerConfig;
+use super::ignore::IgnoreMatcher;
+use super::naming;
+
+/// What goes into a report besides the per-category/age/owner totals
+#[derive(Debug, Clone, Copy)]
+pub struct ReportOptions {
+    /// Length of the largest/stale/duplicate lists
+    pub top: usize,
+    /// Files not modified for this many days count as stale
+    pub stale_days: u64,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self {
+            top: 20,
+            stale_days: 365,
+        }
+    }
+}
+
+/// Output formats of `tyr report --format`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl ReportFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "json" => Some(ReportFormat::Json),
+            "csv" => Some(ReportFormat::Csv),
+            "md" | "markdown" => Some(ReportFormat::Markdown),
+            _ => None,
+        }
+    }
+}
+
+/// Everything `tyr report` found; paths are relative to `root`
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub root: PathBuf,
+    pub generated: String,
+    pub total_files: usize,
+    pub total_bytes: u64,
+    /// Bytes that deleting all but one copy of each duplicate would free
+    pub reclaimable_bytes: u64,
+    pub stale_days: u64,
+    pub categories: Vec<UsageRow>,
+    pub ages: Vec<UsageRow>,
+    pub owners: Vec<UsageRow>,
+    pub largest: Vec<FileRow>,
+    pub duplicates: Vec<DuplicateSet>,
+    pub stale: Vec<FileRow>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UsageRow {
+    pub name: String,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileRow {
+    pub path: PathBuf,
+    pub bytes: u64,
+    pub modified: Option<String>,
+}
+
+/// Files with identical contents (same size and BLAKE3 hash)
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateSet {
+    /// Size of each copy
+    pub bytes: u64,
+    pub files: Vec<PathBuf>,
+}
+
+struct ScannedFile {
+    path: PathBuf,
+    bytes: u64,
+    modified: Option<SystemTime>,
+}
+
+/// Walks `root` (ignore rules apply, symlinks are not followed) and builds the report
+pub fn scan(
+    root: &Path,
+    organizer_config: &FileOrganizerConfig,
+    buckets: &BucketConfig,
+    options: ReportOptions,
+) -> io::Result<Report> {
+    let ext_map = organizer_config.build_extension_map();
+    let mut ignore = IgnoreMatcher::for_directory(root, &organizer_config.skip_patterns)?;
+    let ages = BucketOrganizer::new(buckets.clone(), BucketKind::Age);
+    let owner_names = owner_names();
+    let now = SystemTime::now();
+
+    let mut categories: HashMap<String, UsageRow> = HashMap::new();
+    let mut age_rows: Vec<UsageRow> = ages.bucket_names().into_iter().map(usage_row).collect();
+    let mut owners: HashMap<String, UsageRow> = HashMap::new();
+    let mut files = Vec::new();
+
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        // Unreadable folders are skipped rather than failing the whole report
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if ignore.is_ignored(&path, metadata.is_dir()) {
+                continue;
+            }
+            if metadata.is_dir() {
+                ignore.enter(&path)?;
+                pending.push(path);
+                continue;
+            }
+            if !metadata.is_file() {
+                continue;
+            }
+
+            let bytes = metadata.len();
+            let category = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| {
+                    naming::extension_candidates(name)
+                        .into_iter()
+                        .find_map(|ext| ext_map.get(&ext))
+                })
+                .map_or("Extras", |(_, folder_name)| folder_name.as_str());
+            add(
+                categories
+                    .entry(category.to_string())
+                    .or_insert_with(|| usage_row(category)),
+                bytes,
+            );
+
+            let age = ages.bucket_for(&metadata, now);
+            if let Some(row) = age_rows.iter_mut().find(|row| row.name == age) {
+                add(row, bytes);
+            }
+
+            let owner = owner_of(&metadata, &owner_names);
+            add(
+                owners
+                    .entry(owner.clone())
+                    .or_insert_with(|| usage_row(&owner)),
+                bytes,
+            );
+
+            files.push(ScannedFile {
+                path,
+                bytes,
+                modified: metadata.modified().ok(),
+            });
+        }
+    }
+
+    let total_bytes = files.iter().map(|f| f.bytes).sum();
+    let duplicates = find_duplicates(&files);
+    let reclaimable_bytes = duplicates
+        .iter()
+        .map(|set| set.bytes * (set.files.len() as u64 - 1))
+        .sum();
+
+    let row = |file: &ScannedFile| FileRow {
+        path: file
+            .path
+            .strip_prefix(root)
+            .unwrap_or(&file.path)
+            .to_path_buf(),
+        bytes: file.bytes,
+        modified: file
+            .modified
+            .map(|t| DateTime::<Local>::from(t).format("%Y-%m-%d").to_string()),
+    };
+
+    let mut by_size: Vec<&ScannedFile> = files.iter().collect();
+    by_size.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.path.cmp(&b.path)));
+    let largest = by_size.iter().take(options.top).map(|f| row(f)).collect();
+
+    let stale_before = now.checked_sub(StdDuration::from_secs(
+        options.stale_days.saturating_mul(24 * 60 * 60),
+    ));
+    let mut stale: Vec<&ScannedFile> = files
+        .iter()
+        .filter(|f| {
+            f.modified
+                .zip(stale_before)
+                .is_some_and(|(t, before)| t < before)
+        })
+        .collect();
+    stale.sort_by_key(|f| f.modified);
+    let stale = stale.iter().take(options.top).map(|f| row(f)).collect();
+
+    Ok(Report {
+        root: root.to_path_buf(),
+        generated: Local::now().format("%Y-%m-%d %H:%M").to_string(),
+        total_files: files.len(),
+        total_bytes,
+        reclaimable_bytes,
+        stale_days: options.stale_days,
+        categories: sorted_rows(categories),
+        ages: age_rows,
+        owners: sorted_rows(owners),
+        largest,
+        duplicates: duplicates
+            .into_iter()
+            .take(options.top)
+            .map(|set| DuplicateSet {
+                bytes: set.bytes,
+                files: set
+                    .files
+                    .iter()
+                    .map(|p| p.strip_prefix(root).unwrap_or(p).to_path_buf())
+                    .collect(),
+            })
+            .collect(),
+        stale,
+    })
+}
+
+fn usage_row(name: &str) -> UsageRow {
+    UsageRow {
+        name: name.to_string(),
+        files: 0,
+        bytes: 0,
+    }
+}
+
+fn add(row: &mut UsageRow, bytes: u64) {
+    row.files += 1;
+    row.bytes += bytes;
+}
+
+/// Biggest first
+fn sorted_rows(rows: HashMap<String, UsageRow>) -> Vec<UsageRow> {
+    let mut rows: Vec<UsageRow> = rows.into_values().collect();
+    rows.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+    rows
+}
+
+/// Groups files by size, then hashes only the sizes that occur more than once
+///
+/// Sets come back with the most reclaimable space first. Empty files are not counted.
+fn find_duplicates(files: &[ScannedFile]) -> Vec<DuplicateSet> {
+    let mut by_size: HashMap<u64, Vec<&Path>> = HashMap::new();
+    for file in files.iter().filter(|f| f.bytes > 0) {
+        by_size.entry(file.bytes).or_default().push(&file.path);
+    }
+
+    let mut sets = Vec::new();
+    for (bytes, paths) in by_size.into_iter().filter(|(_, p)| p.len() > 1) {
+        let mut by_hash: HashMap<blake3::Hash, Vec<PathBuf>> = HashMap::new();
+        for path in paths {
+            let mut hasher = blake3::Hasher::new();
+            if File::open(path)
+                .and_then(|mut file| io::copy(&mut file, &mut hasher))
+                .is_ok()
+            {
+                by_hash
+                    .entry(hasher.finalize())
+                    .or_default()
+                    .push(path.to_path_buf());
+            }
+        }
+        for mut files in by_hash.into_values().filter(|f| f.len() > 1) {
+            files.sort();
+            sets.push(DuplicateSet { bytes, files });
+        }
+    }
+
+    sets.sort_by(|a, b| {
+        let wasted = |set: &DuplicateSet| set.bytes * (set.files.len() as u64 - 1);
+        wasted(b)
+            .cmp(&wasted(a))
+            .then_with(|| a.files.cmp(&b.files))
+    });
+    sets
+}
+
+/// User names by uid, from /etc/passwd
+#[cfg(unix)]
+fn owner_names() -> HashMap<u32, String> {
+    fs::read_to_string("/etc/passwd")
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split(':');
+            let name = fields.next()?;
+            let uid = fields.nth(1)?.parse().ok()?;
+            Some((uid, name.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(not(unix))]
+fn owner_names() -> HashMap<u32, String> {
+    HashMap::new()
+}
+
+#[cfg(unix)]
+fn owner_of(metadata: &fs::Metadata, names: &HashMap<u32, String>) -> String {
+    use std::os::unix::fs::MetadataExt;
+    let uid = metadata.uid();
+    names.get(&uid).cloned().unwrap_or_else(|| uid.to_string())
+}
+
+#[cfg(not(unix))]
+fn owner_of(_metadata: &fs::Metadata, _names: &HashMap<u32, String>) -> String {
+    "unknown".to_string()
+}
+
+impl Report {
+    /// Share of all bytes, in percent
+    fn percent(&self, bytes: u64) -> f64 {
+        if self.total_bytes == 0 {
+            0.0
+        } else {
+            bytes as f64 * 100.0 / self.total_bytes as f64
+        }
+    }
+
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Json => serde_json::to_string_pretty(self).unwrap_or_default(),
+            ReportFormat::Csv => self.to_csv(),
+            ReportFormat::Markdown => self.to_markdown(),
+        }
+    }
+
+    /// One table for everything: `section` says which list a row belongs to, `group`
+    /// numbers the duplicate sets
+    fn to_csv(&self) -> String {
+        let mut out = String::from("section,group,name,files,bytes,modified\n");
+        let mut line =
+            |section: &str, group: &str, name: &str, files: usize, bytes: u64, modified: &str| {
+                let _ = writeln!(
+                    out,
+                    "{},{},{},{},{},{}",
+                    section,
+                    group,
+                    csv_field(name),
+                    files,
+                    bytes,
+                    modified
+                );
+            };
+
+        for (section, rows) in [
+            ("category", &self.categories),
+            ("age", &self.ages),
+            ("owner", &self.owners),
+        ] {
+            for row in rows {
+                line(section, "", &row.name, row.files, row.bytes, "");
+            }
+        }
+        for (section, rows) in [("largest", &self.largest), ("stale", &self.stale)] {
+            for row in rows {
+                let path = row.path.display().to_string();
+                line(
+                    section,
+                    "",
+                    &path,
+                    1,
+                    row.bytes,
+                    row.modified.as_deref().unwrap_or(""),
+                );
+            }
+        }
+        for (i, set) in self.duplicates.iter().enumerate() {
+            for path in &set.files {
+                let path = path.display().to_string();
+                line("duplicate", &(i + 1).to_string(), &path, 1, set.bytes, "");
+            }
+        }
+        out
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# Disk usage of {}\n", self.root.display());
+        let _ = writeln!(
+            out,
+            "{} files, {} (report from {})\n",
+            self.total_files,
+            format_bytes(self.total_bytes),
+            self.generated
+        );
+
+        for (title, column, rows) in [
+            ("Categories", "Category", &self.categories),
+            ("Age", "Last modified", &self.ages),
+            ("Owners", "Owner", &self.owners),
+        ] {
+            let _ = writeln!(out, "## {}\n", title);
+            let _ = writeln!(out, "| {} | Files | Size | Share |", column);
+            let _ = writeln!(out, "|---|---:|---:|---:|");
+            for row in rows {
+                let _ = writeln!(
+                    out,
+                    "| {} | {} | {} | {:.1}% |",
+                    markdown_cell(&row.name),
+                    row.files,
+                    format_bytes(row.bytes),
+                    self.percent(row.bytes)
+                );
+            }
+            out.push('\n');
+        }
+
+        let file_table = |out: &mut String, title: &str, rows: &[FileRow]| {
+            let _ = writeln!(out, "## {}\n", title);
+            if rows.is_empty() {
+                let _ = writeln!(out, "None\n");
+                return;
+            }
+            let _ = writeln!(out, "| File | Size | Modified |");
+            let _ = writeln!(out, "|---|---:|---|");
+            for row in rows {
+                let _ = writeln!(
+                    out,
+                    "| {} | {} | {} |",
+                    markdown_cell(&row.path.display().to_string()),
+                    format_bytes(row.bytes),
+                    row.modified.as_deref().unwrap_or("")
+                );
+            }
+            out.push('\n');
+        };
+        file_table(&mut out, "Largest files", &self.largest);
+
+        let _ = writeln!(out, "## Likely duplicates\n");
+        if self.duplicates.is_empty() {
+            let _ = writeln!(out, "None\n");
+        } else {
+            let _ = writeln!(
+                out,
+                "{} could be freed by keeping one copy of each.\n",
+                format_bytes(self.reclaimable_bytes)
+            );
+            for set in &self.duplicates {
+                let files: Vec<String> = set
+                    .files
+                    .iter()
+                    .map(|p| format!("`{}`", p.display()))
+                    .collect();
+                let _ = writeln!(
+                    out,
+                    "- {} copies of {}: {}",
+                    set.files.len(),
+                    format_bytes(set.bytes),
+                    files.join(", ")
+                );
+            }
+            out.push('\n');
+        }
+
+        file_table(
+            &mut out,
+            &format!("Stale files (not modified in {} days)", self.stale_days),
+            &self.stale,
+        );
+        out
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn markdown_cell(value: &str) -> String {
+    value.replace('|', "\\|")
+}
+
+// TUI Implementation
+
+use crossterm::{
+    event::{self, Event, KeyCode},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Bar, BarChart, BarGroup, Block, Borders, List, ListItem, Paragraph},
+    Terminal,
+};
+use std::io::stdout;
+use std::time::Duration;
+
+/// Read-only dashboard for a report
+pub struct ReportTuiApp {
+    report: Report,
+}
+
+impl ReportTuiApp {
+    pub fn new(report: Report) -> Self {
+        Self { report }
+    }
+
+    pub fn run(&mut self) -> io::Result<()> {
+        enable_raw_mode()?;
+        let mut stdout = stdout();
+        execute!(stdout, EnterAlternateScreen)?;
+
+        let backend = CrosstermBackend::new(stdout);
+        let mut terminal = Terminal::new(backend)?;
+
+        let result = self.run_app(&mut terminal);
+
+        disable_raw_mode()?;
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        terminal.show_cursor()?;
+
+        result
+    }
+
+    fn run_app(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    ) -> io::Result<()> {
+        loop {
+            terminal.draw(|f| self.draw_ui(f))?;
+
+            if event::poll(Duration::from_millis(100))? {
+                if let Event::Key(key) = event::read()? {
+                    if matches!(key.code, KeyCode::Char('q') | KeyCode::Esc) {
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn draw_ui(&self, f: &mut ratatui::Frame) {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Percentage(50),
+                Constraint::Min(8),
+                Constraint::Length(3),
+            ])
+            .split(f.size());
+
+        let title = Paragraph::new(format!(
+            " Tyr - Disk Usage of {}  ({} files, {})",
+            self.report.root.display(),
+            self.report.total_files,
+            format_bytes(self.report.total_bytes)
+        ))
+        .style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(Block::default().borders(Borders::ALL));
+        f.render_widget(title, rows[0]);
+
+        let charts = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(40),
+                Constraint::Percentage(30),
+                Constraint::Percentage(30),
+            ])
+            .split(rows[1]);
+        self.draw_chart(
+            f,
+            charts[0],
+            " Categories ",
+            &self.report.categories,
+            Color::Cyan,
+        );
+        self.draw_chart(f, charts[1], " Age ", &self.report.ages, Color::Magenta);
+        self.draw_chart(f, charts[2], " Owners ", &self.report.owners, Color::Green);
+
+        let lists = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(34),
+                Constraint::Percentage(33),
+                Constraint::Percentage(33),
+            ])
+            .split(rows[2]);
+        let file_items = |rows: &[FileRow]| -> Vec<ListItem> {
+            rows.iter()
+                .map(|row| {
+                    ListItem::new(format!(
+                        "{:>9}  {}",
+                        format_bytes(row.bytes),
+                        row.path.display()
+                    ))
+                })
+                .collect()
+        };
+        f.render_widget(
+            List::new(file_items(&self.report.largest))
+                .block(Block::default().borders(Borders::ALL).title(" Largest ")),
+            lists[0],
+        );
+        let duplicates: Vec<ListItem> = self
+            .report
+            .duplicates
+            .iter()
+            .map(|set| {
+                ListItem::new(format!(
+                    "{} × {}  {}",
+                    set.files.len(),
+                    format_bytes(set.bytes),
+                    set.files
+                        .first()
+                        .map(|p| p.display().to_string())
+                        .unwrap_or_default()
+                ))
+            })
+            .collect();
+        f.render_widget(
+            List::new(duplicates).block(Block::default().borders(Borders::ALL).title(format!(
+                " Likely Duplicates ({} reclaimable) ",
+                format_bytes(self.report.reclaimable_bytes)
+            ))),
+            lists[1],
+        );
+        f.render_widget(
+            List::new(file_items(&self.report.stale)).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" Stale (> {} days) ", self.report.stale_days)),
+            ),
+            lists[2],
+        );
+
+        let controls = Paragraph::new(" 'q' Quit | Export with: tyr report --format json|csv|md")
+            .style(Style::default().fg(Color::Yellow))
+            .block(Block::default().borders(Borders::ALL).title(" Controls "));
+        f.render_widget(controls, rows[3]);
+    }
+
+    fn draw_chart(
+        &self,
+        f: &mut ratatui::Frame,
+        area: Rect,
+        title: &str,
+        rows: &[UsageRow],
+        color: Color,
+    ) {
+        let bars: Vec<Bar> = rows
+            .iter()
+            .map(|row| {
+                Bar::default()
+                    .value(row.bytes)
+                    .text_value(format!(
+                        "{} ({:.0}%)",
+                        format_bytes(row.bytes),
+                        self.report.percent(row.bytes)
+                    ))
+                    .label(Line::from(row.name.clone()))
+            })
+            .collect();
+
+        let chart = BarChart::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title.to_string()),
+            )
+            .direction(Direction::Horizontal)
+            .bar_width(1)
+            .bar_gap(0)
+            .bar_style(Style::default().fg(color))
+            .value_style(Style::default().fg(Color::White))
+            .data(BarGroup::default().bars(&bars));
+        f.render_widget(chart, area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_counts_categories_and_duplicates() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        fs::create_dir(base.join("nested")).unwrap();
+        fs::write(base.join("a.pdf"), "same contents").unwrap();
+        fs::write(base.join("nested").join("b.pdf"), "same contents").unwrap();
+        fs::write(base.join("song.mp3"), "other bytes!!").unwrap();
+        // Ignored by the nested folder's own .tyrignore only
+        fs::write(base.join("nested").join(".tyrignore"), "*.mp3\n").unwrap();
+        fs::write(base.join("nested").join("skipped.mp3"), "other bytes!!").unwrap();
+
+        let report = scan(
+            base,
+            &FileOrganizerConfig::default(),
+            &BucketConfig::default(),
+            ReportOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(report.total_files, 3);
+        assert_eq!(
+            report.categories[0],
+            UsageRow {
+                name: "Documents".to_string(),
+                files: 2,
+                bytes: 26
+            }
+        );
+        assert_eq!(report.ages[0].files, 3);
+        assert_eq!(report.duplicates.len(), 1);
+        assert_eq!(
+            report.duplicates[0].files,
+            vec![PathBuf::from("a.pdf"), PathBuf::from("nested/b.pdf")]
+        );
+        assert_eq!(report.reclaimable_bytes, 13);
+
+        let csv = report.render(ReportFormat::Csv);
+        assert!(csv.contains("category,,Documents,2,26,"));
+        assert!(csv.contains("duplicate,1,nested/b.pdf,1,13,"));
+        let markdown = report.render(ReportFormat::Markdown);
+        assert!(markdown.contains("| Documents | 2 | 26 B | 66.7% |"));
+    }
+}
@@ -0,0 +1,776 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for finding almost identical text files and images (`tyr similar`) for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use image::imageops::FilterType;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use super::ignore::IgnoreMatcher;
+use super::intelligent::{is_text_file, preprocess_text, read_file_lines};
+use super::pdf;
+use super::pipeline::{ExecuteResult, Plan};
+use super::transfer::Output;
+
+/// Hash functions in a MinHash signature; the similarity estimate is good to about ±0.05
+const SIGNATURE_LEN: usize = 128;
+
+/// LSH bands of the signature: files sharing a whole band are compared
+const BANDS: usize = 32;
+
+/// Formats the image decoder reads
+const IMAGE_EXTENSIONS: [&str; 6] = ["jpg", "jpeg", "png", "gif", "bmp", "webp"];
+
+/// Settings for `tyr similar` (`[near_duplicate_config]`)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct NearDuplicateConfig {
+    /// Share of word shingles two text files must have in common (0.0 to 1.0)
+    pub text_similarity: f64,
+    /// Share of the 64 bits of their difference hashes two images must agree on (0.0 to 1.0)
+    pub image_similarity: f64,
+    /// Words per shingle
+    pub shingle_size: usize,
+    /// Lines of text files read
+    pub max_lines_to_read: usize,
+    /// Images bigger than this are not decoded
+    pub max_image_mb: u64,
+    /// PDFs bigger than this are not read
+    pub max_pdf_mb: u64,
+}
+
+impl Default for NearDuplicateConfig {
+    fn default() -> Self {
+        Self {
+            text_similarity: 0.8,
+            image_similarity: 0.9,
+            shingle_size: 3,
+            max_lines_to_read: 2000,
+            max_image_mb: 50,
+            max_pdf_mb: 50,
+        }
+    }
+}
+
+impl NearDuplicateConfig {
+    /// Problems with the settings, as messages for `tyr config check`
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (key, value) in [
+            ("text_similarity", self.text_similarity),
+            ("image_similarity", self.image_similarity),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                problems.push(format!(
+                    "{} must be between 0.0 and 1.0 (got {})",
+                    key, value
+                ));
+            }
+        }
+        if self.shingle_size == 0 {
+            problems.push("shingle_size must be greater than 0".to_string());
+        }
+        problems
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Text,
+    Image,
+}
+
+/// Files that are almost the same; the first is the one to keep
+#[derive(Debug, Clone)]
+pub struct SimilarSet {
+    pub kind: Kind,
+    /// The least similar pair that linked the set
+    pub similarity: f64,
+    pub files: Vec<SimilarFile>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SimilarFile {
+    pub path: PathBuf,
+    pub bytes: u64,
+    pub modified: Option<SystemTime>,
+}
+
+impl SimilarSet {
+    /// Bytes that trashing all but the kept file would free
+    pub fn reclaimable(&self, keep: usize) -> u64 {
+        self.files
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != keep)
+            .map(|(_, f)| f.bytes)
+            .sum()
+    }
+}
+
+/// What was read of one file
+enum Fingerprint {
+    Text(Vec<u64>),
+    Image(u64),
+}
+
+/// Walks `root` (ignore rules apply) and groups almost identical files
+///
+/// Text files and the text of PDFs are compared by MinHash over word shingles, images by a 64-bit difference
+/// hash of a 9×8 grayscale thumbnail, so re-saved, resized and slightly edited copies
+/// match. Biggest sets come first.
+pub fn find(
+    root: &Path,
+    config: &NearDuplicateConfig,
+    skip_patterns: &[String],
+) -> io::Result<Vec<SimilarSet>> {
+    let files = list_files(root, skip_patterns)?;
+    let fingerprints: Vec<Option<Fingerprint>> = files
+        .par_iter()
+        .map(|file| fingerprint(&file.path, file.bytes, config))
+        .collect();
+
+    let mut texts = Vec::new();
+    let mut images = Vec::new();
+    for (i, print) in fingerprints.into_iter().enumerate() {
+        match print {
+            Some(Fingerprint::Text(signature)) => texts.push((i, signature)),
+            Some(Fingerprint::Image(hash)) => images.push((i, hash)),
+            None => {}
+        }
+    }
+
+    let mut sets = link(
+        &files,
+        &text_pairs(&texts, config.text_similarity),
+        Kind::Text,
+    );
+    sets.extend(link(
+        &files,
+        &image_pairs(&images, config.image_similarity),
+        Kind::Image,
+    ));
+    sets.sort_by(|a, b| {
+        b.files
+            .len()
+            .cmp(&a.files.len())
+            .then_with(|| b.reclaimable(0).cmp(&a.reclaimable(0)))
+            .then_with(|| a.files[0].path.cmp(&b.files[0].path))
+    });
+    Ok(sets)
+}
+
+fn list_files(root: &Path, skip_patterns: &[String]) -> io::Result<Vec<SimilarFile>> {
+    let mut ignore = IgnoreMatcher::for_directory(root, skip_patterns)?;
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if ignore.is_ignored(&path, metadata.is_dir()) {
+                continue;
+            }
+            if metadata.is_dir() {
+                ignore.enter(&path)?;
+                pending.push(path);
+            } else if metadata.is_file() && metadata.len() > 0 {
+                files.push(SimilarFile {
+                    path,
+                    bytes: metadata.len(),
+                    modified: metadata.modified().ok(),
+                });
+            }
+        }
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+fn fingerprint(path: &Path, bytes: u64, config: &NearDuplicateConfig) -> Option<Fingerprint> {
+    let extension = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+        if bytes > config.max_image_mb.saturating_mul(1024 * 1024) {
+            return None;
+        }
+        return difference_hash(path).map(Fingerprint::Image);
+    }
+    let text = if extension == "pdf" {
+        if bytes > config.max_pdf_mb.saturating_mul(1024 * 1024) {
+            return None;
+        }
+        let text = pdf::extract_text(path).ok()?;
+        text.lines()
+            .take(config.max_lines_to_read)
+            .collect::<Vec<_>>()
+            .join("\n")
+    } else if is_text_file(path) {
+        read_file_lines(path, config.max_lines_to_read).ok()?
+    } else {
+        return None;
+    };
+    let shingles = shingles(&preprocess_text(&text, &[]), config.shingle_size);
+    (!shingles.is_empty()).then(|| Fingerprint::Text(min_hash(&shingles)))
+}
+
+/// Hashes of every run of `size` consecutive words; shorter texts are one shingle
+fn shingles(words: &[String], size: usize) -> HashSet<u64> {
+    let hash = |words: &[String]| {
+        let mut hasher = DefaultHasher::new();
+        words.hash(&mut hasher);
+        hasher.finish()
+    };
+    if words.len() <= size {
+        return if words.is_empty() {
+            HashSet::new()
+        } else {
+            HashSet::from([hash(words)])
+        };
+    }
+    words.windows(size).map(hash).collect()
+}
+
+/// The smallest of each of `SIGNATURE_LEN` rehashes of the shingles
+fn min_hash(shingles: &HashSet<u64>) -> Vec<u64> {
+    (0..SIGNATURE_LEN as u64)
+        .map(|seed| {
+            shingles
+                .iter()
+                .map(|&shingle| mix(shingle ^ seed.wrapping_mul(0x9e37_79b9_7f4a_7c15)))
+                .min()
+                .unwrap_or(u64::MAX)
+        })
+        .collect()
+}
+
+/// splitmix64 finalizer
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// Share of signature slots two files agree on, which estimates their shingle Jaccard
+fn signature_similarity(a: &[u64], b: &[u64]) -> f64 {
+    let same = a.iter().zip(b).filter(|(x, y)| x == y).count();
+    same as f64 / a.len().max(1) as f64
+}
+
+/// dHash: whether each pixel of a 9×8 grayscale thumbnail is darker than its right neighbour
+fn difference_hash(path: &Path) -> Option<u64> {
+    let image = image::ImageReader::open(path)
+        .ok()?
+        .with_guessed_format()
+        .ok()?
+        .decode()
+        .ok()?;
+    let small = image.resize_exact(9, 8, FilterType::Triangle).to_luma8();
+
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            hash <<= 1;
+            if small.get_pixel(x, y)[0] < small.get_pixel(x + 1, y)[0] {
+                hash |= 1;
+            }
+        }
+    }
+    Some(hash)
+}
+
+/// Pairs of text files at least `threshold` alike, with their similarity
+///
+/// Only files sharing one LSH band of their signatures are compared.
+fn text_pairs(texts: &[(usize, Vec<u64>)], threshold: f64) -> Vec<(usize, usize, f64)> {
+    let rows = SIGNATURE_LEN / BANDS;
+    let mut candidates: HashSet<(usize, usize)> = HashSet::new();
+    for band in 0..BANDS {
+        let mut buckets: HashMap<&[u64], Vec<usize>> = HashMap::new();
+        for (slot, (_, signature)) in texts.iter().enumerate() {
+            buckets
+                .entry(&signature[band * rows..(band + 1) * rows])
+                .or_default()
+                .push(slot);
+        }
+        for bucket in buckets.values().filter(|b| b.len() > 1) {
+            for (n, &a) in bucket.iter().enumerate() {
+                for &b in &bucket[n + 1..] {
+                    candidates.insert((a, b));
+                }
+            }
+        }
+    }
+
+    candidates
+        .into_iter()
+        .filter_map(|(a, b)| {
+            let similarity = signature_similarity(&texts[a].1, &texts[b].1);
+            (similarity >= threshold).then_some((texts[a].0, texts[b].0, similarity))
+        })
+        .collect()
+}
+
+/// Pairs of images at least `threshold` alike; every pair is compared, a hash is one word
+fn image_pairs(images: &[(usize, u64)], threshold: f64) -> Vec<(usize, usize, f64)> {
+    let mut pairs = Vec::new();
+    for (n, &(a, hash_a)) in images.iter().enumerate() {
+        for &(b, hash_b) in &images[n + 1..] {
+            let similarity = 1.0 - (hash_a ^ hash_b).count_ones() as f64 / 64.0;
+            if similarity >= threshold {
+                pairs.push((a, b, similarity));
+            }
+        }
+    }
+    pairs
+}
+
+/// Joins matching pairs into sets (union-find)
+fn link(files: &[SimilarFile], pairs: &[(usize, usize, f64)], kind: Kind) -> Vec<SimilarSet> {
+    fn root(parent: &mut HashMap<usize, usize>, i: usize) -> usize {
+        let mut node = i;
+        while let Some(&up) = parent.get(&node).filter(|&&up| up != node) {
+            node = up;
+        }
+        parent.insert(i, node);
+        node
+    }
+
+    let mut parent: HashMap<usize, usize> = HashMap::new();
+    for &(a, b, _) in pairs.iter() {
+        let (ra, rb) = (root(&mut parent, a), root(&mut parent, b));
+        parent.insert(ra.max(rb), ra.min(rb));
+    }
+
+    let mut members: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut weakest: HashMap<usize, f64> = HashMap::new();
+    for &(a, _, similarity) in pairs.iter() {
+        let r = root(&mut parent, a);
+        let least = weakest.entry(r).or_insert(1.0);
+        *least = least.min(similarity);
+    }
+    let nodes: Vec<usize> = parent.keys().copied().collect();
+    for node in nodes {
+        let r = root(&mut parent, node);
+        members.entry(r).or_default().push(node);
+    }
+
+    members
+        .into_iter()
+        .map(|(r, indices)| {
+            let mut files: Vec<SimilarFile> = indices.iter().map(|&i| files[i].clone()).collect();
+            // Keep the image with the most pixels (then bytes), or the newest text
+            files.sort_by_cached_key(|file| {
+                let rank = match kind {
+                    Kind::Image => {
+                        let pixels = image::image_dimensions(&file.path)
+                            .map_or(0, |(w, h)| u64::from(w) * u64::from(h));
+                        (pixels, file.bytes, None)
+                    }
+                    Kind::Text => (0, 0, file.modified),
+                };
+                (std::cmp::Reverse(rank), file.path.clone())
+            });
+            SimilarSet {
+                kind,
+                similarity: weakest.get(&r).copied().unwrap_or(1.0),
+                files,
+            }
+        })
+        .collect()
+}
+
+/// A plan trashing every file of `sets` except the kept one
+pub fn trash_plan(root: &Path, output: &Output, sets: &[(&SimilarSet, usize)]) -> Plan {
+    Plan {
+        base_path: root.to_path_buf(),
+        output: output.clone(),
+        moves: Vec::new(),
+        trash: sets
+            .iter()
+            .flat_map(|(set, keep)| {
+                set.files
+                    .iter()
+                    .enumerate()
+                    .filter(move |(i, _)| i != keep)
+                    .map(|(_, f)| f.path.clone())
+            })
+            .collect(),
+        extract: Vec::new(),
+        already_extracted: Vec::new(),
+        failures: Vec::new(),
+    }
+}
+
+// TUI Implementation
+
+use crossterm::{
+    event::{self, Event, KeyCode},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Terminal,
+};
+use std::cell::Cell;
+use std::io::stdout;
+use std::time::Duration;
+
+use super::buckets::format_bytes;
+use super::pipeline;
+
+/// Review of near-duplicate sets: pick the file to keep, mark sets, trash the rest
+pub struct SimilarTuiApp {
+    root: PathBuf,
+    output: Output,
+    sets: Vec<SimilarSet>,
+    /// Index of the kept file of each set
+    keep: Vec<usize>,
+    /// Sets whose other files are trashed on 's'
+    marked: Vec<bool>,
+    selected: usize,
+    scroll: u16,
+    /// Rows the list showed last, so the selection can be kept in view
+    rows: Cell<u16>,
+    result: Option<ExecuteResult>,
+}
+
+impl SimilarTuiApp {
+    pub fn new(root: PathBuf, output: Output, sets: Vec<SimilarSet>) -> Self {
+        Self {
+            root,
+            output,
+            keep: vec![0; sets.len()],
+            marked: vec![false; sets.len()],
+            sets,
+            selected: 0,
+            scroll: 0,
+            rows: Cell::new(0),
+            result: None,
+        }
+    }
+
+    /// What trashing did, once it happened
+    pub fn take_result(&mut self) -> Option<ExecuteResult> {
+        self.result.take()
+    }
+
+    pub fn run(&mut self) -> io::Result<()> {
+        enable_raw_mode()?;
+        let mut stdout = stdout();
+        execute!(stdout, EnterAlternateScreen)?;
+
+        let backend = CrosstermBackend::new(stdout);
+        let mut terminal = Terminal::new(backend)?;
+
+        let result = self.run_app(&mut terminal);
+
+        disable_raw_mode()?;
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        terminal.show_cursor()?;
+
+        result
+    }
+
+    fn run_app(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    ) -> io::Result<()> {
+        loop {
+            terminal.draw(|f| self.draw_ui(f))?;
+
+            if event::poll(Duration::from_millis(100))? {
+                if let Event::Key(key) = event::read()? {
+                    let count = self.sets.get(self.selected).map_or(1, |s| s.files.len());
+                    match key.code {
+                        KeyCode::Char('q') | KeyCode::Esc => break,
+                        KeyCode::Up => self.select(self.selected.saturating_sub(1)),
+                        KeyCode::Down => self.select(self.selected + 1),
+                        KeyCode::Left => {
+                            if let Some(keep) = self.keep.get_mut(self.selected) {
+                                *keep = (*keep + count - 1) % count;
+                            }
+                        }
+                        KeyCode::Right => {
+                            if let Some(keep) = self.keep.get_mut(self.selected) {
+                                *keep = (*keep + 1) % count;
+                            }
+                        }
+                        KeyCode::Char(' ') => {
+                            if let Some(marked) = self.marked.get_mut(self.selected) {
+                                *marked = !*marked;
+                            }
+                        }
+                        KeyCode::Char('a') => {
+                            let all = self.marked.iter().all(|m| *m);
+                            self.marked.iter_mut().for_each(|m| *m = !all);
+                        }
+                        KeyCode::Char('s') if self.marked.contains(&true) => {
+                            self.result = Some(pipeline::execute_moves(&self.plan()));
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn plan(&self) -> Plan {
+        let marked: Vec<(&SimilarSet, usize)> = self
+            .sets
+            .iter()
+            .zip(&self.keep)
+            .zip(&self.marked)
+            .filter(|(_, marked)| **marked)
+            .map(|((set, keep), _)| (set, *keep))
+            .collect();
+        trash_plan(&self.root, &self.output, &marked)
+    }
+
+    /// Selects a set and scrolls just far enough to show it
+    fn select(&mut self, index: usize) {
+        self.selected = index.min(self.sets.len().saturating_sub(1));
+
+        // A header line per set, one per file and a blank line
+        let lines = |s: &SimilarSet| s.files.len() + 2;
+        let top: usize = self.sets[..self.selected].iter().map(lines).sum();
+        let bottom = top + self.sets.get(self.selected).map_or(1, lines);
+        let rows = usize::from(self.rows.get()).max(1);
+        if top < usize::from(self.scroll) {
+            self.scroll = top as u16;
+        } else if bottom > usize::from(self.scroll) + rows {
+            self.scroll = bottom.saturating_sub(rows) as u16;
+        }
+    }
+
+    fn draw_ui(&self, f: &mut ratatui::Frame) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(8),
+                Constraint::Length(3),
+            ])
+            .split(f.size());
+
+        let marked: Vec<(&SimilarSet, usize)> = self
+            .sets
+            .iter()
+            .zip(&self.keep)
+            .zip(&self.marked)
+            .filter(|(_, marked)| **marked)
+            .map(|((set, keep), _)| (set, *keep))
+            .collect();
+        let freed: u64 = marked
+            .iter()
+            .map(|(set, keep)| set.reclaimable(*keep))
+            .sum();
+        let title = Paragraph::new(format!(
+            " Tyr - Near-Duplicates in {}  ({} sets, {} marked, {} to free)",
+            self.root.display(),
+            self.sets.len(),
+            marked.len(),
+            format_bytes(freed)
+        ))
+        .style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(Block::default().borders(Borders::ALL));
+        f.render_widget(title, chunks[0]);
+
+        let mut lines = Vec::new();
+        for (index, set) in self.sets.iter().enumerate() {
+            let mut header = Style::default()
+                .fg(if self.marked[index] {
+                    Color::Red
+                } else {
+                    Color::Cyan
+                })
+                .add_modifier(Modifier::BOLD);
+            if index == self.selected {
+                header = header.add_modifier(Modifier::REVERSED);
+            }
+            lines.push(Line::from(Span::styled(
+                format!(
+                    " {} {} {} files, {:.0}% alike{}",
+                    if self.marked[index] { "[x]" } else { "[ ]" },
+                    match set.kind {
+                        Kind::Text => "Text ",
+                        Kind::Image => "Image",
+                    },
+                    set.files.len(),
+                    set.similarity * 100.0,
+                    if self.marked[index] {
+                        format!(
+                            ", frees {}",
+                            format_bytes(set.reclaimable(self.keep[index]))
+                        )
+                    } else {
+                        String::new()
+                    }
+                ),
+                header,
+            )));
+            for (i, file) in set.files.iter().enumerate() {
+                let kept = i == self.keep[index];
+                let (mark, color) = match (kept, self.marked[index]) {
+                    (true, _) => ("keep ", Color::Green),
+                    (false, true) => ("trash", Color::Red),
+                    (false, false) => ("     ", Color::Gray),
+                };
+                let path = file.path.strip_prefix(&self.root).unwrap_or(&file.path);
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "   {} {:>9}  {}",
+                        mark,
+                        format_bytes(file.bytes),
+                        path.display()
+                    ),
+                    Style::default().fg(color),
+                )));
+            }
+            lines.push(Line::from(""));
+        }
+
+        self.rows.set(chunks[1].height.saturating_sub(2));
+        let list = Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title(" Sets "))
+            .scroll((self.scroll, 0));
+        f.render_widget(list, chunks[1]);
+
+        let controls = Paragraph::new(
+            " ↑↓ Select | ←→ File to Keep | Space Mark | 'a' Mark All | 's' Trash Marked | 'q' Quit",
+        )
+        .style(Style::default().fg(Color::Yellow))
+        .block(Block::default().borders(Borders::ALL).title(" Controls "));
+        f.render_widget(controls, chunks[2]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{GrayImage, Luma};
+
+    #[test]
+    fn test_finds_edited_text_and_resized_images() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+
+        let words: Vec<String> = (0..200).map(|i| format!("word{}", i * 7 % 113)).collect();
+        let text = words.join(" ");
+        fs::write(base.join("notes.md"), &text).unwrap();
+        fs::write(base.join("notes (copy).md"), format!("{} extra", text)).unwrap();
+        fs::write(base.join("other.md"), "completely unrelated words here").unwrap();
+
+        let gradient = |size: u32| {
+            GrayImage::from_fn(size, size, |x, y| {
+                Luma([((x * 255 / size) ^ (y * 97 / size)) as u8])
+            })
+        };
+        gradient(64).save(base.join("photo.png")).unwrap();
+        gradient(32).save(base.join("photo_small.png")).unwrap();
+        GrayImage::from_fn(64, 64, |x, _| Luma([255 - (x * 4) as u8]))
+            .save(base.join("mirror.png"))
+            .unwrap();
+
+        let sets = find(base, &NearDuplicateConfig::default(), &[]).unwrap();
+        let names = |set: &SimilarSet| {
+            let mut names: Vec<String> = set
+                .files
+                .iter()
+                .map(|f| f.path.file_name().unwrap().to_string_lossy().into_owned())
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(sets.len(), 2, "{:?}", sets);
+        let text = sets.iter().find(|s| s.kind == Kind::Text).unwrap();
+        assert_eq!(names(text), ["notes (copy).md", "notes.md"]);
+        assert!(text.similarity >= 0.8);
+        let image = sets.iter().find(|s| s.kind == Kind::Image).unwrap();
+        assert_eq!(names(image), ["photo.png", "photo_small.png"]);
+        // The bigger image is kept
+        assert_eq!(image.files[0].path, base.join("photo.png"));
+
+        let plan = trash_plan(base, &Output::default(), &[(image, 0)]);
+        assert_eq!(plan.trash, [base.join("photo_small.png")]);
+    }
+
+    #[test]
+    fn test_finds_pdfs_with_the_same_text() {
+        use flate2::write::ZlibEncoder;
+        use std::io::Write;
+
+        let write_pdf = |path: &Path, lines: &[&str]| {
+            let content: String = lines
+                .iter()
+                .map(|line| format!("BT /F1 12 Tf 72 700 Td ({}) Tj ET\n", line))
+                .collect();
+            let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(content.as_bytes()).unwrap();
+            let stream = encoder.finish().unwrap();
+
+            let mut pdf = b"%PDF-1.4\n1 0 obj\n<< /Type /Catalog >>\nendobj\n".to_vec();
+            pdf.extend(
+                format!(
+                    "4 0 obj\n<< /Length {} /Filter /FlateDecode >>\nstream\n",
+                    stream.len()
+                )
+                .as_bytes(),
+            );
+            pdf.extend(&stream);
+            pdf.extend(b"\nendstream\nendobj\n%%EOF\n");
+            fs::write(path, pdf).unwrap();
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        let lines: Vec<String> = (0..40)
+            .map(|i| format!("clause{} binds tenant{} and landlord{}", i, i * 3, i * 7))
+            .collect();
+        let mut lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        write_pdf(&base.join("lease.pdf"), &lines);
+        lines.push("signed in Oslo");
+        write_pdf(&base.join("lease signed.pdf"), &lines);
+        write_pdf(&base.join("menu.pdf"), &["soup of the day", "apple pie"]);
+
+        let sets = find(base, &NearDuplicateConfig::default(), &[]).unwrap();
+        assert_eq!(sets.len(), 1, "{:?}", sets);
+        assert_eq!(sets[0].kind, Kind::Text);
+        let mut names: Vec<&Path> = sets[0].files.iter().map(|f| f.path.as_path()).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [base.join("lease signed.pdf"), base.join("lease.pdf")]
+        );
+    }
+}
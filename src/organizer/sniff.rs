@@ -0,0 +1,236 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for detecting file types from their content (magic bytes) for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// Bytes read from the start of a file; enough for every signature below and the
+/// first entries of a ZIP central directory
+const SNIFF_LEN: usize = 8192;
+
+/// A file type recognised from content
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sniffed {
+    pub mime: &'static str,
+    /// Canonical extension for the type, used to look up the category
+    pub extension: &'static str,
+}
+
+impl Sniffed {
+    const fn new(mime: &'static str, extension: &'static str) -> Self {
+        Self { mime, extension }
+    }
+
+    /// Plain UTF-8 text; only a guess, never a reason to override a real extension
+    pub fn is_text(&self) -> bool {
+        self.mime == TEXT.mime
+    }
+
+    /// A generic ZIP, which is also the container of docx, jar, apk, ...
+    pub fn is_container(&self) -> bool {
+        self.mime == ZIP.mime
+    }
+}
+
+const TEXT: Sniffed = Sniffed::new("text/plain", "txt");
+const ZIP: Sniffed = Sniffed::new("application/zip", "zip");
+
+/// Sniffs the type of the file at `path` from its first bytes
+pub fn sniff_file(path: &Path) -> io::Result<Option<Sniffed>> {
+    let mut buffer = Vec::with_capacity(SNIFF_LEN);
+    File::open(path)?
+        .take(SNIFF_LEN as u64)
+        .read_to_end(&mut buffer)?;
+    Ok(sniff_bytes(&buffer))
+}
+
+/// Sniffs a type from the start of a file's content
+pub fn sniff_bytes(bytes: &[u8]) -> Option<Sniffed> {
+    sniff_magic(bytes).or_else(|| looks_like_text(bytes).then_some(TEXT))
+}
+
+fn sniff_magic(b: &[u8]) -> Option<Sniffed> {
+    let at = |offset: usize, magic: &[u8]| b.get(offset..offset + magic.len()) == Some(magic);
+
+    let sniffed = match () {
+        // Images
+        _ if at(0, b"\x89PNG\r\n\x1a\n") => Sniffed::new("image/png", "png"),
+        _ if at(0, b"\xff\xd8\xff") => Sniffed::new("image/jpeg", "jpg"),
+        _ if at(0, b"GIF87a") || at(0, b"GIF89a") => Sniffed::new("image/gif", "gif"),
+        _ if at(0, b"RIFF") && at(8, b"WEBP") => Sniffed::new("image/webp", "webp"),
+        _ if at(0, b"II*\0") || at(0, b"MM\0*") => Sniffed::new("image/tiff", "tiff"),
+        _ if at(0, b"\0\0\x01\0") => Sniffed::new("image/x-icon", "ico"),
+
+        // Documents
+        _ if at(0, b"%PDF-") => Sniffed::new("application/pdf", "pdf"),
+        _ if at(0, b"{\\rtf") => Sniffed::new("application/rtf", "rtf"),
+        _ if at(0, b"PK\x03\x04") => sniff_zip(b),
+
+        // Archives
+        _ if at(0, b"\x1f\x8b") => Sniffed::new("application/gzip", "gz"),
+        _ if at(0, b"7z\xbc\xaf\x27\x1c") => Sniffed::new("application/x-7z-compressed", "7z"),
+        _ if at(0, b"Rar!\x1a\x07") => Sniffed::new("application/vnd.rar", "rar"),
+        _ if at(0, b"\xfd7zXZ\0") => Sniffed::new("application/x-xz", "xz"),
+        _ if at(0, b"BZh") => Sniffed::new("application/x-bzip2", "bz2"),
+        _ if at(0, b"\x28\xb5\x2f\xfd") => Sniffed::new("application/zstd", "zst"),
+        _ if at(257, b"ustar") => Sniffed::new("application/x-tar", "tar"),
+
+        // Executables
+        _ if at(0, b"\x7fELF") => Sniffed::new("application/x-executable", "elf"),
+        _ if at(0, b"MZ") => Sniffed::new("application/vnd.microsoft.portable-executable", "exe"),
+        _ if at(0, b"\xcf\xfa\xed\xfe") || at(0, b"\xce\xfa\xed\xfe") => {
+            Sniffed::new("application/x-mach-binary", "app")
+        }
+
+        // Audio / video
+        _ if at(4, b"ftyp") => sniff_ftyp(b),
+        _ if at(0, b"ID3") || at(0, b"\xff\xfb") || at(0, b"\xff\xf3") => {
+            Sniffed::new("audio/mpeg", "mp3")
+        }
+        _ if at(0, b"fLaC") => Sniffed::new("audio/flac", "flac"),
+        _ if at(0, b"OggS") => Sniffed::new("audio/ogg", "ogg"),
+        _ if at(0, b"RIFF") && at(8, b"WAVE") => Sniffed::new("audio/wav", "wav"),
+        _ if at(0, b"RIFF") && at(8, b"AVI ") => Sniffed::new("video/x-msvideo", "avi"),
+        _ if at(0, b"\x1a\x45\xdf\xa3") => Sniffed::new("video/x-matroska", "mkv"),
+
+        // Data and fonts
+        _ if at(0, b"SQLite format 3\0") => Sniffed::new("application/vnd.sqlite3", "sqlite"),
+        _ if at(0, b"wOFF") => Sniffed::new("font/woff", "woff"),
+        _ if at(0, b"wOF2") => Sniffed::new("font/woff2", "woff2"),
+        _ if at(0, b"OTTO") => Sniffed::new("font/otf", "otf"),
+        _ if at(0, b"\0\x01\0\0") => Sniffed::new("font/ttf", "ttf"),
+
+        _ => return None,
+    };
+
+    Some(sniffed)
+}
+
+/// Tells OOXML, OpenDocument and EPUB apart from plain ZIP archives
+fn sniff_zip(b: &[u8]) -> Sniffed {
+    // OpenDocument and EPUB store an uncompressed `mimetype` entry first
+    if b.get(30..38) == Some(b"mimetype") {
+        let rest = &b[38..b.len().min(38 + 64)];
+        let types: [(&[u8], Sniffed); 4] = [
+            (
+                b"application/epub+zip",
+                Sniffed::new("application/epub+zip", "epub"),
+            ),
+            (
+                b"application/vnd.oasis.opendocument.text",
+                Sniffed::new("application/vnd.oasis.opendocument.text", "odt"),
+            ),
+            (
+                b"application/vnd.oasis.opendocument.spreadsheet",
+                Sniffed::new("application/vnd.oasis.opendocument.spreadsheet", "ods"),
+            ),
+            (
+                b"application/vnd.oasis.opendocument.presentation",
+                Sniffed::new("application/vnd.oasis.opendocument.presentation", "odp"),
+            ),
+        ];
+        if let Some((_, sniffed)) = types.iter().find(|(magic, _)| rest.starts_with(magic)) {
+            return *sniffed;
+        }
+    }
+
+    // Office Open XML: look for the part folders among the first entry names
+    let contains = |needle: &[u8]| b.windows(needle.len()).any(|w| w == needle);
+    if contains(b"word/") {
+        Sniffed::new(
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            "docx",
+        )
+    } else if contains(b"xl/") {
+        Sniffed::new(
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            "xlsx",
+        )
+    } else if contains(b"ppt/") {
+        Sniffed::new(
+            "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+            "pptx",
+        )
+    } else {
+        ZIP
+    }
+}
+
+/// ISO base media files (MP4 family), told apart by their major brand
+fn sniff_ftyp(b: &[u8]) -> Sniffed {
+    match b.get(8..12) {
+        Some(b"qt  ") => Sniffed::new("video/quicktime", "mov"),
+        Some(b"M4A ") | Some(b"M4B ") => Sniffed::new("audio/mp4", "m4a"),
+        Some(b"heic") | Some(b"heix") | Some(b"mif1") => Sniffed::new("image/heic", "heic"),
+        Some(b"avif") => Sniffed::new("image/avif", "avif"),
+        _ => Sniffed::new("video/mp4", "mp4"),
+    }
+}
+
+/// UTF-8 without NUL bytes and with hardly any control characters
+fn looks_like_text(bytes: &[u8]) -> bool {
+    if bytes.is_empty() || bytes.contains(&0) {
+        return false;
+    }
+
+    // The sample may cut a multi-byte character in half
+    let text = match std::str::from_utf8(bytes) {
+        Ok(text) => text,
+        Err(e) if e.error_len().is_none() => {
+            std::str::from_utf8(&bytes[..e.valid_up_to()]).unwrap_or_default()
+        }
+        Err(_) => return false,
+    };
+
+    let total = text.chars().count();
+    let control = text
+        .chars()
+        .filter(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t' | '\x0c'))
+        .count();
+
+    total > 0 && control * 100 <= total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_magic_numbers() {
+        assert_eq!(
+            sniff_bytes(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR")
+                .unwrap()
+                .extension,
+            "png"
+        );
+        assert_eq!(sniff_bytes(b"%PDF-1.7\n").unwrap().extension, "pdf");
+        assert_eq!(
+            sniff_bytes(b"\0\0\0\x18ftypqt  \0\0\0\0")
+                .unwrap()
+                .extension,
+            "mov"
+        );
+
+        let mut docx = b"PK\x03\x04".to_vec();
+        docx.extend_from_slice(&[0; 26]);
+        docx.extend_from_slice(b"word/document.xml");
+        assert_eq!(sniff_bytes(&docx).unwrap().extension, "docx");
+    }
+
+    #[test]
+    fn test_text_heuristic() {
+        assert!(sniff_bytes("# Notes\nüber alles — 日本語\n".as_bytes())
+            .unwrap()
+            .is_text());
+        assert_eq!(sniff_bytes(b"\x01\x02\x03binary\0junk"), None);
+        // A multi-byte character cut off at the end of the sample is fine
+        assert!(sniff_bytes(&"añ".as_bytes()[..2]).unwrap().is_text());
+    }
+}
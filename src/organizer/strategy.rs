@@ -0,0 +1,267 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for the organizer trait every mode implements, and the shared planning for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::ignore::IgnoreMatcher;
+use super::naming;
+use super::pipeline::{Plan, PlannedMove};
+use super::transfer::{Output, TransferMode};
+use crate::error::FileFailure;
+
+/// Where an organizer wants a file to go
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Target {
+    /// Folder relative to the output root, possibly nested (`Documents/2024`)
+    Folder(String),
+    /// Into the trash (categories with `action = "trash"`)
+    Trash,
+    /// Stays where it is, e.g. a file without a look-alike
+    Keep,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Assignment {
+    pub file: PathBuf,
+    pub target: Target,
+}
+
+/// A target for every analyzed file, sorted by path
+pub type Assignments = Vec<Assignment>;
+
+/// One setting an organizer reads, with its current value
+#[derive(Debug, Clone, PartialEq)]
+pub struct Setting {
+    pub key: String,
+    pub value: String,
+}
+
+/// The tyr.toml section an organizer is configured by
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigSchema {
+    pub section: &'static str,
+    pub settings: Vec<Setting>,
+}
+
+impl ConfigSchema {
+    /// Top-level keys of a serializable config section; nested tables are only counted
+    pub fn of(section: &'static str, config: &impl Serialize) -> Self {
+        let table = toml::Table::try_from(config).unwrap_or_default();
+        let settings = table
+            .into_iter()
+            .map(|(key, value)| Setting {
+                key,
+                value: match value {
+                    toml::Value::Table(table) => format!("{{{} entries}}", table.len()),
+                    value => value.to_string(),
+                },
+            })
+            .collect();
+        Self { section, settings }
+    }
+}
+
+/// A way of deciding where files go: by category, name similarity, clustering, size, ...
+///
+/// Organizers only decide; listing the files, numbering clashing names, moving and
+/// recording the undo journal are shared by every mode (see `list_files` and `plan`).
+pub trait Organizer {
+    /// Name for titles and logs, e.g. "Filename similarity"
+    fn name(&self) -> &'static str;
+
+    /// The settings this organizer reads, with their current values
+    fn config_schema(&self) -> ConfigSchema;
+
+    /// Target for each of `files`; files left out stay where they are
+    fn analyze(&self, files: &[PathBuf]) -> io::Result<Assignments>;
+
+    /// Multi-dot extensions kept whole when clashing names are numbered (`tar.gz`)
+    fn compound_extensions(&self) -> &[String] {
+        &[]
+    }
+
+    /// Whether its folders are groups it made up, so corrections to them are worth
+    /// remembering (see `feedback`); category folders are not
+    fn learns(&self) -> bool {
+        false
+    }
+
+    /// Why `files`, which `analyze` put into one folder, belong together, one line per
+    /// reason (`--explain`); empty when the organizer has nothing to add
+    fn explain(&self, _files: &[PathBuf]) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Files the last `analyze` could not fully decide on, e.g. because the plugin failed
+    /// for them; they still got a target. Taking them clears the list.
+    fn take_failures(&self) -> Vec<FileFailure> {
+        Vec::new()
+    }
+}
+
+/// Files directly inside `base_path`, minus `skip_patterns` and `.tyrignore` matches, sorted
+pub fn list_files(base_path: &Path, skip_patterns: &[String]) -> io::Result<Vec<PathBuf>> {
+    let ignore = IgnoreMatcher::for_directory(base_path, skip_patterns)?;
+    let mut files: Vec<PathBuf> = fs::read_dir(base_path)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|path| path.is_file() && !ignore.is_ignored(path, false))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Turns assignments into moves under the output root, without touching anything
+///
+/// Clashing names are numbered, against the disk and the rest of the plan. Files that
+/// stay go to `leftovers` (a folder under the root) when one is given, and trash
+/// targets are left alone while copying, since a copy never deletes anything.
+pub fn plan(
+    base_path: &Path,
+    output: &Output,
+    organizer: &dyn Organizer,
+    assignments: &[Assignment],
+    leftovers: Option<&str>,
+) -> io::Result<Plan> {
+    let root = output.root(base_path);
+    let mut reserved = HashSet::new();
+    let mut moves = Vec::new();
+    let mut trash = Vec::new();
+
+    for assignment in assignments {
+        let folder = match &assignment.target {
+            Target::Folder(folder) => folder.as_str(),
+            Target::Keep => match leftovers {
+                Some(folder) => folder,
+                None => continue,
+            },
+            Target::Trash if output.mode == TransferMode::Copy => continue,
+            Target::Trash => {
+                trash.push(assignment.file.clone());
+                continue;
+            }
+        };
+        let Some(filename) = assignment.file.file_name() else {
+            continue;
+        };
+        // Free on disk and not promised to another file of this plan
+        let to = naming::resolve_conflict(
+            &root.join(folder).join(filename),
+            organizer.compound_extensions(),
+            |p| p.exists() || reserved.contains(p),
+        )?;
+        if to == assignment.file {
+            continue;
+        }
+        reserved.insert(to.clone());
+        moves.push(PlannedMove {
+            from: assignment.file.clone(),
+            to,
+        });
+    }
+
+    Ok(Plan {
+        base_path: base_path.to_path_buf(),
+        output: output.clone(),
+        moves,
+        trash,
+        extract: Vec::new(),
+        already_extracted: Vec::new(),
+        failures: organizer.take_failures(),
+    })
+}
+
+/// Lists, analyzes and plans `base_path` in one go; files that stay are not moved
+pub fn plan_directory(
+    organizer: &dyn Organizer,
+    base_path: &Path,
+    skip_patterns: &[String],
+    output: &Output,
+) -> io::Result<Plan> {
+    let files = list_files(base_path, skip_patterns)?;
+    let assignments = organizer.analyze(&files)?;
+    plan(base_path, output, organizer, &assignments, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::organizer::pipeline;
+
+    /// Files go to a folder named after their first letter, `x*` files stay
+    struct ByInitial;
+
+    impl Organizer for ByInitial {
+        fn name(&self) -> &'static str {
+            "By initial"
+        }
+
+        fn config_schema(&self) -> ConfigSchema {
+            ConfigSchema {
+                section: "by_initial",
+                settings: Vec::new(),
+            }
+        }
+
+        fn analyze(&self, files: &[PathBuf]) -> io::Result<Assignments> {
+            Ok(files
+                .iter()
+                .map(|file| {
+                    let name = file.file_name().unwrap().to_string_lossy();
+                    let target = match &name[..1] {
+                        "x" => Target::Keep,
+                        initial => Target::Folder(initial.to_uppercase()),
+                    };
+                    Assignment {
+                        file: file.clone(),
+                        target,
+                    }
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn test_custom_organizer_plans_and_undoes() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        for name in ["apple.txt", "avocado.txt", "xylophone.txt"] {
+            fs::write(base.join(name), name).unwrap();
+        }
+        // Taken on disk, so apple.txt gets a number
+        fs::create_dir(base.join("A")).unwrap();
+        fs::write(base.join("A/apple.txt"), "old").unwrap();
+
+        let files = list_files(base, &[]).unwrap();
+        let assignments = ByInitial.analyze(&files).unwrap();
+        let kept = plan(base, &Output::default(), &ByInitial, &assignments, None).unwrap();
+        assert_eq!(kept.moves.len(), 2);
+        assert_eq!(kept.moves[0].to, base.join("A/apple_1.txt"));
+
+        let plan = plan(
+            base,
+            &Output::default(),
+            &ByInitial,
+            &assignments,
+            Some("tyr-skip"),
+        )
+        .unwrap();
+        assert_eq!(plan.moves[2].to, base.join("tyr-skip/xylophone.txt"));
+
+        let result = pipeline::execute_moves(&plan);
+        assert_eq!(result.files_moved, 3);
+        assert_eq!(fs::read_to_string(base.join("A/apple.txt")).unwrap(), "old");
+        assert_eq!(result.journal.undo().restored, 3);
+        assert!(base.join("xylophone.txt").exists());
+    }
+}
@@ -0,0 +1,257 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for expanding destination templates like "Documents/{year}" for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use chrono::{DateTime, Datelike, Local};
+use std::path::{Component, Path, PathBuf};
+
+use super::sniff;
+
+/// Variables a destination template may use
+pub const VARIABLES: [&str; 8] = [
+    "ext", "year", "month", "day", "size", "mime", "parent", "lang",
+];
+
+/// What a template is expanded against
+pub struct TemplateContext<'a> {
+    pub path: &'a Path,
+    /// Matched extension (`tar.gz` for compound ones), if any
+    pub extension: Option<&'a str>,
+}
+
+/// Checks a template for unknown variables, unbalanced braces and paths leaving the
+/// target folder
+pub fn validate(template: &str) -> Result<(), String> {
+    for part in parse(template)? {
+        if let Part::Variable(name) = part {
+            if !VARIABLES.contains(&name) {
+                return Err(format!(
+                    "unknown variable {{{}}} (available: {})",
+                    name,
+                    VARIABLES
+                        .iter()
+                        .map(|v| format!("{{{}}}", v))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+        }
+    }
+
+    if !stays_inside(Path::new(template)) {
+        return Err("must be a relative path inside the target folder".to_string());
+    }
+
+    Ok(())
+}
+
+/// Whether joining `path` onto the target folder stays inside it (no `..`, not absolute)
+pub fn stays_inside(path: &Path) -> bool {
+    !path.is_absolute()
+        && !path.components().any(|c| {
+            matches!(
+                c,
+                Component::ParentDir | Component::RootDir | Component::Prefix(_)
+            )
+        })
+}
+
+/// Expands `template` into a relative folder path, e.g. `Media/Images/png`
+///
+/// Values that cannot be worked out fall back to a placeholder (`unknown`, `Other`) so a
+/// file always has somewhere to go.
+pub fn render(template: &str, ctx: &TemplateContext) -> Result<PathBuf, String> {
+    let mut modified: Option<Option<DateTime<Local>>> = None;
+    let mut modified_date = || {
+        *modified.get_or_insert_with(|| {
+            ctx.path
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .map(DateTime::<Local>::from)
+        })
+    };
+
+    let rendered = expand(template, |name| {
+        Ok(match name {
+            "ext" => ctx.extension.unwrap_or("unknown").to_lowercase(),
+            "year" => date_part(modified_date(), |d| d.year().to_string()),
+            "month" => date_part(modified_date(), |d| format!("{:02}", d.month())),
+            "day" => date_part(modified_date(), |d| format!("{:02}", d.day())),
+            "size" => size_bucket(ctx.path.metadata().map(|m| m.len()).unwrap_or(0)).to_string(),
+            // Full type on purpose: "image/png" nests as image/png
+            "mime" => sniff::sniff_file(ctx.path)
+                .ok()
+                .flatten()
+                .map(|s| s.mime)
+                .unwrap_or("application/octet-stream")
+                .to_string(),
+            "parent" => ctx
+                .path
+                .parent()
+                .and_then(|p| p.file_name())
+                .map(|n| sanitize_component(&n.to_string_lossy()))
+                .unwrap_or_else(|| "unknown".to_string()),
+            "lang" => language_for(ctx.extension.unwrap_or("")).to_string(),
+            other => return Err(format!("unknown variable {{{}}}", other)),
+        })
+    })?;
+
+    // Drop empty segments so "{parent}/x" never turns into an absolute path
+    let path: PathBuf = rendered
+        .split('/')
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty() && *segment != "." && *segment != "..")
+        .collect();
+
+    if path.as_os_str().is_empty() {
+        Err(format!("'{}' expands to an empty path", template))
+    } else {
+        Ok(path)
+    }
+}
+
+/// Replaces every `{variable}` in `template` with what `value` returns for its name
+///
+/// Shared by destination and rename templates, which differ only in their variables.
+pub fn expand(
+    template: &str,
+    mut value: impl FnMut(&str) -> Result<String, String>,
+) -> Result<String, String> {
+    let mut expanded = String::new();
+    for part in parse(template)? {
+        match part {
+            Part::Text(text) => expanded.push_str(text),
+            Part::Variable(name) => expanded.push_str(&value(name)?),
+        }
+    }
+    Ok(expanded)
+}
+
+enum Part<'a> {
+    Text(&'a str),
+    Variable(&'a str),
+}
+
+fn parse(template: &str) -> Result<Vec<Part<'_>>, String> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+
+    while let Some(open) = rest.find(['{', '}']) {
+        if rest[open..].starts_with('}') {
+            return Err(format!("unmatched '}}' in '{}'", template));
+        }
+        let close = rest[open..]
+            .find('}')
+            .map(|i| open + i)
+            .ok_or_else(|| format!("unclosed '{{' in '{}'", template))?;
+
+        if open > 0 {
+            parts.push(Part::Text(&rest[..open]));
+        }
+        parts.push(Part::Variable(rest[open + 1..close].trim()));
+        rest = &rest[close + 1..];
+    }
+
+    if !rest.is_empty() {
+        parts.push(Part::Text(rest));
+    }
+    Ok(parts)
+}
+
+fn date_part(date: Option<DateTime<Local>>, format: impl Fn(&DateTime<Local>) -> String) -> String {
+    date.as_ref()
+        .map(format)
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Size buckets used by `{size}`
+pub fn size_bucket(bytes: u64) -> &'static str {
+    const KB: u64 = 1024;
+    const MB: u64 = 1024 * KB;
+    const GB: u64 = 1024 * MB;
+
+    match bytes {
+        b if b < 100 * KB => "Tiny",
+        b if b < 10 * MB => "Small",
+        b if b < 100 * MB => "Medium",
+        b if b < GB => "Large",
+        _ => "Huge",
+    }
+}
+
+/// Programming language for `{lang}`, by extension
+fn language_for(extension: &str) -> &'static str {
+    match extension.to_lowercase().as_str() {
+        "rs" => "Rust",
+        "py" => "Python",
+        "js" | "jsx" | "mjs" | "cjs" | "user.js" | "min.js" => "JavaScript",
+        "ts" | "tsx" | "d.ts" => "TypeScript",
+        "html" | "htm" => "HTML",
+        "css" | "scss" | "sass" | "min.css" => "CSS",
+        "c" | "h" => "C",
+        "cpp" | "cc" | "cxx" | "hpp" => "C++",
+        "java" => "Java",
+        "kt" => "Kotlin",
+        "go" => "Go",
+        "php" => "PHP",
+        "rb" => "Ruby",
+        "swift" => "Swift",
+        "dart" => "Dart",
+        "scala" => "Scala",
+        "sh" | "bash" | "zsh" => "Shell",
+        "bat" | "ps1" => "Windows Scripts",
+        "r" => "R",
+        "lua" => "Lua",
+        "vim" => "Vim",
+        "sql" => "SQL",
+        _ => "Other",
+    }
+}
+
+/// Keeps a value usable as a single folder name
+fn sanitize_component(value: &str) -> String {
+    value.replace(['/', '\\'], "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_render_nested_destination() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("main.rs");
+        fs::write(&file, "fn main() {}").unwrap();
+        let ctx = TemplateContext {
+            path: &file,
+            extension: Some("rs"),
+        };
+
+        let year = Local::now().year().to_string();
+        assert_eq!(
+            render("Code/{lang}/{ext}", &ctx).unwrap(),
+            PathBuf::from("Code/Rust/rs")
+        );
+        assert_eq!(
+            render("Docs/{year}/{size}", &ctx).unwrap(),
+            PathBuf::from(format!("Docs/{}/Tiny", year))
+        );
+        assert_eq!(render("{mime}", &ctx).unwrap(), PathBuf::from("text/plain"));
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_templates() {
+        assert!(validate("Media/Images/{ext}").is_ok());
+        assert!(validate("Media/{colour}").is_err());
+        assert!(validate("Media/{ext").is_err());
+        assert!(validate("../outside").is_err());
+        assert!(validate("/abs/{ext}").is_err());
+    }
+}
@@ -0,0 +1,235 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for splitting filenames and file content into word tokens for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Characters treated as word separators before Unicode segmentation.
+/// UAX #29 keeps `snake_case` and `file.name` together, which is wrong for filenames.
+const SEPARATORS: [char; 10] = ['_', '.', '-', '(', ')', '[', ']', '{', '}', '+'];
+
+/// Splits text into lowercase, diacritic-folded words
+///
+/// Words come from Unicode word segmentation, so CJK, Cyrillic and accented text survive,
+/// and are then split further on camelCase / PascalCase boundaries
+/// (`QuarterlyReportDraft` -> `quarterly`, `report`, `draft`).
+pub fn split_words(text: &str) -> Vec<String> {
+    let spaced: String = text
+        .chars()
+        .map(|c| if SEPARATORS.contains(&c) { ' ' } else { c })
+        .collect();
+
+    spaced
+        .unicode_words()
+        .flat_map(split_case_boundaries)
+        .map(|word| fold_diacritics(&word.to_lowercase()))
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Tokenizes text for similarity scoring
+///
+/// Same as [`split_words`], but drops purely numeric words, single characters (except
+/// ideographs, which carry a whole word) and stop words of the given languages.
+pub fn tokenize(text: &str, stop_word_languages: &[String]) -> Vec<String> {
+    split_words(text)
+        .into_iter()
+        .filter(|word| is_meaningful(word))
+        .filter(|word| !is_stop_word(word, stop_word_languages))
+        .collect()
+}
+
+/// Returns true if a token is worth keeping for comparisons
+pub fn is_meaningful(word: &str) -> bool {
+    if word.chars().all(|c| c.is_numeric()) {
+        return false;
+    }
+
+    let mut chars = word.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => is_ideographic(c),
+        (Some(_), Some(_)) => true,
+        _ => false,
+    }
+}
+
+/// Splits a single word on lower->upper transitions and on the end of an uppercase run
+/// (`HTTPServer` -> `HTTP`, `Server`)
+fn split_case_boundaries(word: &str) -> Vec<String> {
+    let chars: Vec<char> = word.chars().collect();
+    let mut parts = Vec::new();
+    let mut current = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if i > 0 && c.is_uppercase() {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+
+            if prev.is_lowercase() || (prev.is_uppercase() && next_is_lower) {
+                parts.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(c);
+    }
+
+    if !current.is_empty() {
+        parts.push(current);
+    }
+
+    parts
+}
+
+/// Removes accents from Latin letters (`café` -> `cafe`, `Übersicht` -> `ubersicht`)
+///
+/// Only marks sitting on an ASCII base letter are dropped, since marks in other scripts
+/// (Japanese dakuten, Cyrillic breve) change which letter it is.
+pub fn fold_diacritics(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    let mut last_base_ascii = false;
+
+    for c in text.nfd() {
+        if is_combining_mark(c) {
+            if !last_base_ascii {
+                folded.push(c);
+            }
+            continue;
+        }
+        last_base_ascii = c.is_ascii_alphabetic();
+        folded.push(c);
+    }
+
+    folded.nfc().collect()
+}
+
+/// Returns true for CJK ideographs, where a single character is a whole word
+pub fn is_ideographic(c: char) -> bool {
+    matches!(c as u32,
+        0x3400..=0x4DBF     // CJK Extension A
+        | 0x4E00..=0x9FFF   // CJK Unified Ideographs
+        | 0xF900..=0xFAFF   // CJK Compatibility Ideographs
+        | 0x20000..=0x2FA1F // CJK Extensions B-F and supplement
+    )
+}
+
+// Stop words (stored diacritic-folded, matching the output of split_words)
+
+const STOP_WORDS_EN: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "in", "into", "is", "it", "of",
+    "on", "or", "that", "the", "this", "to", "was", "were", "with", "copy", "new",
+];
+
+const STOP_WORDS_DE: &[&str] = &[
+    "aber", "als", "am", "an", "auf", "aus", "bei", "das", "dem", "den", "der", "des", "die",
+    "ein", "eine", "einer", "und", "fur", "im", "in", "ist", "mit", "nach", "oder", "von", "vom",
+    "zu", "zum", "zur", "kopie", "neu",
+];
+
+const STOP_WORDS_FR: &[&str] = &[
+    "au", "aux", "avec", "ce", "ces", "dans", "de", "des", "du", "en", "est", "et", "la", "le",
+    "les", "leur", "par", "pour", "sur", "un", "une", "copie", "nouveau",
+];
+
+const STOP_WORDS_ES: &[&str] = &[
+    "al", "con", "de", "del", "el", "en", "es", "la", "las", "lo", "los", "para", "por", "que",
+    "un", "una", "uno", "unos", "unas", "y", "copia", "nuevo",
+];
+
+const STOP_WORDS_IT: &[&str] = &[
+    "al", "alla", "con", "da", "dal", "dei", "del", "della", "di", "e", "gli", "il", "in", "la",
+    "le", "lo", "per", "su", "un", "una", "uno", "copia", "nuovo",
+];
+
+const STOP_WORDS_PT: &[&str] = &[
+    "ao", "com", "da", "das", "de", "do", "dos", "e", "em", "na", "nas", "no", "nos", "o", "os",
+    "para", "por", "um", "uma", "copia", "novo",
+];
+
+const STOP_WORDS_NL: &[&str] = &[
+    "de", "den", "der", "het", "een", "en", "in", "is", "met", "naar", "op", "te", "van", "voor",
+    "kopie", "nieuw",
+];
+
+const STOP_WORDS_RU: &[&str] = &[
+    "в",
+    "во",
+    "и",
+    "из",
+    "к",
+    "на",
+    "не",
+    "о",
+    "об",
+    "от",
+    "по",
+    "с",
+    "со",
+    "у",
+    "для",
+    "за",
+    "копия",
+    "новый",
+];
+
+/// Returns the built-in stop word list for a language code (`en`, `de`, `fr`, ...)
+pub fn stop_words_for(language: &str) -> Option<&'static [&'static str]> {
+    match language.to_lowercase().as_str() {
+        "en" | "english" => Some(STOP_WORDS_EN),
+        "de" | "german" => Some(STOP_WORDS_DE),
+        "fr" | "french" => Some(STOP_WORDS_FR),
+        "es" | "spanish" => Some(STOP_WORDS_ES),
+        "it" | "italian" => Some(STOP_WORDS_IT),
+        "pt" | "portuguese" => Some(STOP_WORDS_PT),
+        "nl" | "dutch" => Some(STOP_WORDS_NL),
+        "ru" | "russian" => Some(STOP_WORDS_RU),
+        _ => None,
+    }
+}
+
+/// Checks a (lowercased, folded) word against the stop lists of the given languages
+pub fn is_stop_word(word: &str, languages: &[String]) -> bool {
+    languages
+        .iter()
+        .filter_map(|lang| stop_words_for(lang))
+        .any(|list| list.contains(&word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_camel_case_and_snake_case_split() {
+        assert_eq!(
+            split_words("QuarterlyReportDraft"),
+            vec!["quarterly", "report", "draft"]
+        );
+        assert_eq!(
+            split_words("HTTPServer_config"),
+            vec!["http", "server", "config"]
+        );
+        assert_eq!(split_words("file.name-v2"), vec!["file", "name", "v2"]);
+    }
+
+    #[test]
+    fn test_unicode_words_are_kept() {
+        assert_eq!(split_words("Отчёт_2024"), vec!["отчёт", "2024"]);
+        assert_eq!(split_words("Café Übersicht"), vec!["cafe", "ubersicht"]);
+        assert!(tokenize("会议记录", &[]).contains(&"会".to_string()));
+    }
+
+    #[test]
+    fn test_stop_words_are_removed() {
+        let langs = vec!["en".to_string(), "de".to_string()];
+        assert_eq!(
+            tokenize("The_Report_of_the_Year für Max", &langs),
+            vec!["report", "year", "max"]
+        );
+        assert_eq!(tokenize("the report", &[]), vec!["the", "report"]);
+    }
+}
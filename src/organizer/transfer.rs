@@ -0,0 +1,366 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for getting files to their destination (move, copy, other disks) for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use std::fs::{self, File, FileTimes, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use super::hooks::HooksConfig;
+use super::trash;
+
+/// Chunk size for streamed copies
+const COPY_BUFFER: usize = 1024 * 1024;
+
+/// Whether organized files leave the source folder
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransferMode {
+    #[default]
+    Move,
+    Copy,
+}
+
+/// Where organized files end up and how they get there
+#[derive(Debug, Clone, Default)]
+pub struct Output {
+    /// Root for the category/group folders (`--dest`); None organizes in place
+    pub dest_root: Option<PathBuf>,
+    pub mode: TransferMode,
+    /// Updated while files are transferred, for a progress gauge
+    pub progress: Option<Arc<TransferProgress>>,
+    /// Commands run before, during and after executing a plan
+    pub hooks: HooksConfig,
+}
+
+/// Counters of a running transfer, safe to read from another thread
+///
+/// Bytes are only counted for streamed copies; a same-disk rename is instant.
+#[derive(Debug, Default)]
+pub struct TransferProgress {
+    pub files_total: AtomicUsize,
+    pub files_done: AtomicUsize,
+    pub bytes_total: AtomicU64,
+    pub bytes_done: AtomicU64,
+}
+
+impl TransferProgress {
+    /// Resets the counters for a run of `files_total` transfers
+    pub fn start(&self, files_total: usize) {
+        self.files_total.store(files_total, Ordering::Relaxed);
+        self.files_done.store(0, Ordering::Relaxed);
+        self.bytes_total.store(0, Ordering::Relaxed);
+        self.bytes_done.store(0, Ordering::Relaxed);
+    }
+
+    /// True while a copy is streaming
+    pub fn is_copying(&self) -> bool {
+        self.bytes_done.load(Ordering::Relaxed) < self.bytes_total.load(Ordering::Relaxed)
+    }
+
+    /// Percentage for a gauge: bytes while a copy streams, files otherwise
+    pub fn percent(&self) -> u16 {
+        let (done, total) = if self.is_copying() {
+            (
+                self.bytes_done.load(Ordering::Relaxed),
+                self.bytes_total.load(Ordering::Relaxed),
+            )
+        } else {
+            (
+                self.files_done.load(Ordering::Relaxed) as u64,
+                self.files_total.load(Ordering::Relaxed) as u64,
+            )
+        };
+        (done.min(total) * 100)
+            .checked_div(total)
+            .map_or(0, |percent| percent as u16)
+    }
+}
+
+impl Output {
+    /// Folder the category/group folders are created in
+    pub fn root<'a>(&'a self, base_path: &'a Path) -> &'a Path {
+        self.dest_root.as_deref().unwrap_or(base_path)
+    }
+
+    /// Past tense for logs and summaries ("Moved", "Copied")
+    pub fn verb(&self) -> &'static str {
+        match self.mode {
+            TransferMode::Move => "Moved",
+            TransferMode::Copy => "Copied",
+        }
+    }
+
+    /// Starts the progress counters, if anyone is watching
+    pub fn start_progress(&self, files_total: usize) {
+        if let Some(progress) = &self.progress {
+            progress.start(files_total);
+        }
+    }
+
+    /// Moves or copies `from` to `to`, creating `to`'s folder when needed
+    pub fn transfer(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let progress = self.progress.as_deref();
+        let result = to
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| match self.mode {
+                TransferMode::Move => move_file_with_progress(from, to, progress),
+                TransferMode::Copy => copy_verified(from, to, progress),
+            });
+
+        if let Some(progress) = progress {
+            progress.files_done.fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+
+    /// Sends `path` to the desktop trash, counting it like a transfer
+    pub fn trash(&self, path: &Path) -> io::Result<PathBuf> {
+        let result = trash::trash_file(path);
+        if let Some(progress) = &self.progress {
+            progress.files_done.fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+}
+
+/// Renames `from` to `to`, falling back to a verified copy and delete when they are on
+/// different filesystems (where `rename` fails with EXDEV)
+pub fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    move_file_with_progress(from, to, None)
+}
+
+fn move_file_with_progress(
+    from: &Path,
+    to: &Path,
+    progress: Option<&TransferProgress>,
+) -> io::Result<()> {
+    match fs::rename(from, to) {
+        Err(e) if e.kind() == io::ErrorKind::CrossesDevices => {
+            copy_verified(from, to, progress)?;
+            fs::remove_file(from)
+        }
+        result => result,
+    }
+}
+
+/// Streams `from` into a new file `to` and checks the result before reporting success
+///
+/// Permissions, timestamps and extended attributes are carried over and the data is
+/// fsynced. The copy must match the source in size and BLAKE3 hash; a copy that does
+/// not is deleted again, so the caller can safely remove the source only on `Ok`.
+pub fn copy_verified(
+    from: &Path,
+    to: &Path,
+    progress: Option<&TransferProgress>,
+) -> io::Result<()> {
+    let mut source = File::open(from)?;
+    let metadata = source.metadata()?;
+    // Never overwrite: conflicts are resolved before we get here
+    let mut target = OpenOptions::new().write(true).create_new(true).open(to)?;
+
+    let result =
+        stream_copy(&mut source, &mut target, metadata.len(), progress).and_then(|source_hash| {
+            target.set_times(
+                FileTimes::new()
+                    .set_accessed(metadata.accessed()?)
+                    .set_modified(metadata.modified()?),
+            )?;
+            copy_xattrs(from, to)?;
+            target.sync_all()?;
+            verify_copy(to, metadata.len(), &source_hash)?;
+            // Last, so a read-only source doesn't lock us out of the steps above
+            fs::set_permissions(to, metadata.permissions())
+        });
+
+    if result.is_err() {
+        drop(target);
+        let _ = fs::remove_file(to);
+    }
+    result
+}
+
+/// Copies everything from `source` to `target`, returning the hash of what was read
+fn stream_copy(
+    source: &mut File,
+    target: &mut File,
+    len: u64,
+    progress: Option<&TransferProgress>,
+) -> io::Result<blake3::Hash> {
+    if let Some(progress) = progress {
+        progress.bytes_total.fetch_add(len, Ordering::Relaxed);
+    }
+
+    let mut hasher = blake3::Hasher::new();
+    let mut buffer = vec![0; COPY_BUFFER];
+    let mut copied = 0;
+    let result = loop {
+        let read = match source.read(&mut buffer) {
+            Ok(0) => break Ok(hasher.finalize()),
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => break Err(e),
+        };
+        if let Err(e) = target.write_all(&buffer[..read]) {
+            break Err(e);
+        }
+        hasher.update(&buffer[..read]);
+        copied += read as u64;
+        if let Some(progress) = progress {
+            progress
+                .bytes_done
+                .fetch_add(read as u64, Ordering::Relaxed);
+        }
+    };
+
+    // Keep the byte counters balanced even when the file changed size or the copy failed
+    if let Some(progress) = progress {
+        if copied < len {
+            progress
+                .bytes_done
+                .fetch_add(len - copied, Ordering::Relaxed);
+        } else {
+            progress
+                .bytes_total
+                .fetch_add(copied - len, Ordering::Relaxed);
+        }
+    }
+    result
+}
+
+/// Reads the copy back and compares it with what was written
+fn verify_copy(path: &Path, expected_len: u64, expected_hash: &blake3::Hash) -> io::Result<()> {
+    let mut hasher = blake3::Hasher::new();
+    let len = io::copy(&mut File::open(path)?, &mut hasher)?;
+
+    if len != expected_len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "copy of {} has {} bytes, expected {}",
+                path.display(),
+                len,
+                expected_len
+            ),
+        ));
+    }
+    if hasher.finalize() != *expected_hash {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("copy of {} does not match the source", path.display()),
+        ));
+    }
+    Ok(())
+}
+
+/// Carries extended attributes over; skipped when the target filesystem has none
+#[cfg(unix)]
+fn copy_xattrs(from: &Path, to: &Path) -> io::Result<()> {
+    let names = match xattr::list(from) {
+        Ok(names) => names,
+        Err(e) if e.kind() == io::ErrorKind::Unsupported => return Ok(()),
+        Err(e) => return Err(e),
+    };
+
+    for name in names {
+        let Some(value) = xattr::get(from, &name)? else {
+            continue;
+        };
+        match xattr::set(to, &name, &value) {
+            Err(e) if e.kind() == io::ErrorKind::Unsupported => return Ok(()),
+            result => result?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn copy_xattrs(_from: &Path, _to: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_keeps_the_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let from = dir.path().join("notes.txt");
+        let to = dir
+            .path()
+            .join("sorted")
+            .join("Documents")
+            .join("notes.txt");
+        fs::write(&from, "hello").unwrap();
+
+        let output = Output {
+            dest_root: Some(dir.path().join("sorted")),
+            mode: TransferMode::Copy,
+            progress: Some(Arc::new(TransferProgress::default())),
+            ..Output::default()
+        };
+        output.start_progress(1);
+        output.transfer(&from, &to).unwrap();
+
+        assert!(from.exists());
+        assert_eq!(fs::read_to_string(&to).unwrap(), "hello");
+        assert_eq!(output.root(dir.path()), dir.path().join("sorted"));
+
+        let progress = output.progress.as_ref().unwrap();
+        assert_eq!(progress.bytes_done.load(Ordering::Relaxed), 5);
+        assert_eq!(progress.percent(), 100);
+    }
+
+    #[test]
+    fn test_copy_of_read_only_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let from = dir.path().join("report.pdf");
+        let to = dir.path().join("copy.pdf");
+        fs::write(&from, "report").unwrap();
+        let mut permissions = fs::metadata(&from).unwrap().permissions();
+        permissions.set_readonly(true);
+        fs::set_permissions(&from, permissions).unwrap();
+
+        copy_verified(&from, &to, None).unwrap();
+
+        assert_eq!(fs::read_to_string(&to).unwrap(), "report");
+        assert!(fs::metadata(&to).unwrap().permissions().readonly());
+    }
+
+    #[test]
+    fn test_verified_copy_keeps_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        let from = dir.path().join("photo.raw");
+        let to = dir.path().join("copy.raw");
+        fs::write(&from, vec![7u8; COPY_BUFFER + 10]).unwrap();
+        let modified =
+            std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000);
+        File::options()
+            .write(true)
+            .open(&from)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+
+        copy_verified(&from, &to, None).unwrap();
+
+        let copied = fs::metadata(&to).unwrap();
+        assert_eq!(copied.len(), (COPY_BUFFER + 10) as u64);
+        assert_eq!(copied.modified().unwrap(), modified);
+        assert_eq!(
+            copied.permissions(),
+            fs::metadata(&from).unwrap().permissions()
+        );
+        // Never overwrites an existing file
+        assert!(copy_verified(&from, &to, None).is_err());
+        assert!(to.exists());
+    }
+}
@@ -0,0 +1,443 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for the freedesktop.org trash (send, list, restore) for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use chrono::{Local, NaiveDateTime};
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+const INFO_EXTENSION: &str = "trashinfo";
+const DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// A file sitting in one of the trash directories
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrashEntry {
+    /// Where the file lives now (`<trash>/files/<name>`)
+    pub trashed_path: PathBuf,
+    /// Where it was deleted from
+    pub original_path: PathBuf,
+    pub deleted: Option<NaiveDateTime>,
+}
+
+/// Moves `path` into the trash of its filesystem and returns where it ended up
+///
+/// Files on the home filesystem go to `$XDG_DATA_HOME/Trash`; files on other mounts go
+/// to `<mount>/.Trash/$uid` (when the admin created it) or `<mount>/.Trash-$uid`.
+pub fn trash_file(path: &Path) -> io::Result<PathBuf> {
+    let path = std::path::absolute(path)?;
+    let trash = trash_dir_for(&path)?;
+    let files_dir = trash.root.join("files");
+    let info_dir = trash.root.join("info");
+    fs::create_dir_all(&files_dir)?;
+    fs::create_dir_all(&info_dir)?;
+
+    // Per-mount trash cans store paths relative to the mount
+    let stored_path = match &trash.top_dir {
+        Some(top) => path.strip_prefix(top).unwrap_or(&path).to_path_buf(),
+        None => path.clone(),
+    };
+    let info = format!(
+        "[Trash Info]\nPath={}\nDeletionDate={}\n",
+        encode_path(&stored_path),
+        Local::now().format(DATE_FORMAT)
+    );
+
+    // The spec reserves the name by creating the .trashinfo atomically first
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "file".to_string());
+    for n in 1..1000 {
+        let candidate = if n == 1 {
+            name.clone()
+        } else {
+            format!("{}.{}", name, n)
+        };
+        let info_path = info_dir.join(format!("{}.{}", candidate, INFO_EXTENSION));
+        let mut info_file = match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&info_path)
+        {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        };
+
+        let trashed_path = files_dir.join(&candidate);
+        let result = info_file
+            .write_all(info.as_bytes())
+            .and_then(|_| fs::rename(&path, &trashed_path));
+        if let Err(e) = result {
+            let _ = fs::remove_file(&info_path);
+            return Err(e);
+        }
+        return Ok(trashed_path);
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        format!("No free name in the trash for {}", path.display()),
+    ))
+}
+
+/// Every file in the home trash and the trash directories of mounted filesystems,
+/// newest first
+pub fn list() -> io::Result<Vec<TrashEntry>> {
+    let mut entries = Vec::new();
+    for trash in all_trash_dirs()? {
+        let Ok(infos) = fs::read_dir(trash.root.join("info")) else {
+            continue;
+        };
+        for info in infos.filter_map(|e| e.ok()).map(|e| e.path()) {
+            if info.extension().is_some_and(|ext| ext == INFO_EXTENSION) {
+                if let Some(entry) = read_info(&trash, &info) {
+                    entries.push(entry);
+                }
+            }
+        }
+    }
+
+    entries.sort_by_key(|e| std::cmp::Reverse(e.deleted));
+    Ok(entries)
+}
+
+/// Puts a trashed file back where it came from
+pub fn restore(entry: &TrashEntry) -> io::Result<PathBuf> {
+    restore_to(&entry.trashed_path, &entry.original_path)?;
+    Ok(entry.original_path.clone())
+}
+
+/// Moves `trashed_path` (a file in `<trash>/files`) to `to` and drops its .trashinfo
+pub fn restore_to(trashed_path: &Path, to: &Path) -> io::Result<()> {
+    if to.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", to.display()),
+        ));
+    }
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    super::transfer::move_file(trashed_path, to)?;
+
+    if let Some(info) = info_path_for(trashed_path) {
+        let _ = fs::remove_file(info);
+    }
+    Ok(())
+}
+
+/// `<trash>/info/<name>.trashinfo` for `<trash>/files/<name>`
+fn info_path_for(trashed_path: &Path) -> Option<PathBuf> {
+    let name = trashed_path.file_name()?.to_string_lossy();
+    let root = trashed_path.parent()?.parent()?;
+    Some(
+        root.join("info")
+            .join(format!("{}.{}", name, INFO_EXTENSION)),
+    )
+}
+
+fn read_info(trash: &TrashDir, info_path: &Path) -> Option<TrashEntry> {
+    let content = fs::read_to_string(info_path).ok()?;
+    let mut original = None;
+    let mut deleted = None;
+    for line in content.lines() {
+        if let Some(value) = line.strip_prefix("Path=") {
+            original = Some(decode_path(value));
+        } else if let Some(value) = line.strip_prefix("DeletionDate=") {
+            deleted = NaiveDateTime::parse_from_str(value.trim(), DATE_FORMAT).ok();
+        }
+    }
+
+    let original = original?;
+    let original_path = match &trash.top_dir {
+        Some(top) if original.is_relative() => top.join(original),
+        _ => original,
+    };
+    let name = info_path.file_stem()?;
+    Some(TrashEntry {
+        trashed_path: trash.root.join("files").join(name),
+        original_path,
+        deleted,
+    })
+}
+
+/// A trash can and, for per-mount ones, the mount it belongs to
+struct TrashDir {
+    root: PathBuf,
+    top_dir: Option<PathBuf>,
+}
+
+fn home_trash() -> io::Result<PathBuf> {
+    let data_home = match env::var_os("XDG_DATA_HOME").filter(|v| !v.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => {
+            let home = env::var_os("HOME").ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "Could not determine HOME directory",
+                )
+            })?;
+            PathBuf::from(home).join(".local").join("share")
+        }
+    };
+    Ok(data_home.join("Trash"))
+}
+
+#[cfg(unix)]
+fn trash_dir_for(path: &Path) -> io::Result<TrashDir> {
+    use std::os::unix::fs::MetadataExt;
+
+    let home = home_trash()?;
+    let device = fs::symlink_metadata(path)?.dev();
+
+    // The home trash may not exist yet; its nearest existing ancestor tells the device
+    let home_device = home
+        .ancestors()
+        .find_map(|dir| fs::metadata(dir).ok())
+        .map(|m| m.dev());
+    if home_device == Some(device) {
+        return Ok(TrashDir {
+            root: home,
+            top_dir: None,
+        });
+    }
+
+    // Climb to the top of the mount the file is on
+    let mut top = path.parent().unwrap_or(Path::new("/"));
+    while let Some(parent) = top.parent() {
+        match fs::metadata(parent) {
+            Ok(meta) if meta.dev() == device => top = parent,
+            _ => break,
+        }
+    }
+
+    let root = mount_trash(top, true)?.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("No usable trash directory on {}", top.display()),
+        )
+    })?;
+    Ok(TrashDir {
+        root,
+        top_dir: Some(top.to_path_buf()),
+    })
+}
+
+#[cfg(not(unix))]
+fn trash_dir_for(_path: &Path) -> io::Result<TrashDir> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "The trash is only supported on Linux and other freedesktop.org systems",
+    ))
+}
+
+/// `<top>/.Trash/$uid` when the shared `.Trash` is set up safely (a sticky directory,
+/// not a symlink), otherwise `<top>/.Trash-$uid`. With `create` false only existing
+/// ones are returned.
+#[cfg(unix)]
+fn mount_trash(top: &Path, create: bool) -> io::Result<Option<PathBuf>> {
+    use std::os::unix::fs::PermissionsExt;
+
+    // SAFETY: getuid never fails and has no preconditions
+    let uid = unsafe { libc::getuid() };
+
+    let shared = top.join(".Trash");
+    if let Ok(meta) = fs::symlink_metadata(&shared) {
+        let sticky = meta.permissions().mode() & 0o1000 != 0;
+        if meta.is_dir() && sticky {
+            let dir = shared.join(uid.to_string());
+            if dir.is_dir() || (create && fs::create_dir(&dir).is_ok()) {
+                return Ok(Some(dir));
+            }
+        }
+    }
+
+    let own = top.join(format!(".Trash-{}", uid));
+    if own.is_dir() {
+        return Ok(Some(own));
+    }
+    if create {
+        fs::create_dir(&own)?;
+        fs::set_permissions(&own, fs::Permissions::from_mode(0o700))?;
+        return Ok(Some(own));
+    }
+    Ok(None)
+}
+
+/// The home trash plus the trash directories of every mounted filesystem
+fn all_trash_dirs() -> io::Result<Vec<TrashDir>> {
+    let mut dirs = vec![TrashDir {
+        root: home_trash()?,
+        top_dir: None,
+    }];
+
+    #[cfg(unix)]
+    if let Ok(mounts) = fs::read_to_string("/proc/self/mounts") {
+        for mount in mounts.lines().filter_map(|l| l.split_whitespace().nth(1)) {
+            let top = PathBuf::from(mount.replace("\\040", " "));
+            if let Ok(Some(root)) = mount_trash(&top, false) {
+                if !dirs.iter().any(|d| d.root == root) {
+                    dirs.push(TrashDir {
+                        root,
+                        top_dir: Some(top),
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(dirs)
+}
+
+/// Percent-encodes a path the way .trashinfo files store it, byte for byte, so names that
+/// are not valid UTF-8 survive the round trip
+fn encode_path(path: &Path) -> String {
+    #[cfg(unix)]
+    let bytes = {
+        use std::os::unix::ffi::OsStrExt;
+        path.as_os_str().as_bytes().to_vec()
+    };
+    #[cfg(not(unix))]
+    let bytes = path.to_string_lossy().into_owned().into_bytes();
+
+    let mut encoded = String::new();
+    for byte in bytes {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+fn decode_path(encoded: &str) -> PathBuf {
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStringExt;
+        PathBuf::from(std::ffi::OsString::from_vec(decoded))
+    }
+    #[cfg(not(unix))]
+    PathBuf::from(String::from_utf8_lossy(&decoded).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paths_round_trip_through_trashinfo_encoding() {
+        let path = Path::new("/home/me/My Files/ünïcode%.txt");
+        let encoded = encode_path(path);
+        assert_eq!(encoded, "/home/me/My%20Files/%C3%BCn%C3%AFcode%25.txt");
+        assert_eq!(decode_path(&encoded), path);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            let path = Path::new(std::ffi::OsStr::from_bytes(b"/tmp/latin1-\xe9t\xe9.txt"));
+            let encoded = encode_path(path);
+            assert_eq!(encoded, "/tmp/latin1-%E9t%E9.txt");
+            assert_eq!(decode_path(&encoded), path);
+        }
+    }
+
+    #[test]
+    fn test_trash_and_restore_in_home_trash() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("setup.part");
+        fs::write(&file, "junk").unwrap();
+
+        let trash = TrashDir {
+            root: dir.path().join("Trash"),
+            top_dir: None,
+        };
+        fs::create_dir_all(trash.root.join("files")).unwrap();
+        fs::create_dir_all(trash.root.join("info")).unwrap();
+        let trashed = trash.root.join("files").join("setup.part");
+        fs::rename(&file, &trashed).unwrap();
+        let info = trash.root.join("info").join("setup.part.trashinfo");
+        fs::write(
+            &info,
+            format!(
+                "[Trash Info]\nPath={}\nDeletionDate=2026-01-02T03:04:05\n",
+                encode_path(&file)
+            ),
+        )
+        .unwrap();
+
+        let entry = read_info(&trash, &info).unwrap();
+        assert_eq!(entry.original_path, file);
+        assert_eq!(entry.trashed_path, trashed);
+
+        restore(&entry).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "junk");
+        assert!(!info.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_trash_file_list_and_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("old draft.txt");
+        fs::write(&file, "draft").unwrap();
+
+        let previous = env::var_os("XDG_DATA_HOME");
+        env::set_var("XDG_DATA_HOME", dir.path().join("data"));
+        let trashed = trash_file(&file);
+        let listed = list();
+        match previous {
+            Some(value) => env::set_var("XDG_DATA_HOME", value),
+            None => env::remove_var("XDG_DATA_HOME"),
+        }
+
+        let trashed = trashed.unwrap();
+        assert_eq!(
+            trashed,
+            dir.path().join("data/Trash/files").join("old draft.txt")
+        );
+        assert!(!file.exists());
+        let entry = listed
+            .unwrap()
+            .into_iter()
+            .find(|e| e.trashed_path == trashed)
+            .unwrap();
+        assert_eq!(entry.original_path, file);
+        assert!(entry.deleted.is_some());
+
+        restore_to(&entry.trashed_path, &entry.original_path).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "draft");
+        assert!(!dir
+            .path()
+            .join("data/Trash/info/old draft.txt.trashinfo")
+            .exists());
+    }
+}
@@ -0,0 +1,841 @@
+//  Copyright (C) 2026 Dawood Khan
+//  SPDX-License-Identifier: GPL-3.0-or-later
+
+// Maintainer Dawood (Nurysso) contact - nurysso [at] proton.me
+
+/*
+ * This file is responsible for the TUI shared by every organizer mode for
+ * tyr(https:github.com/Nurysso/tyr) project
+*/
+use crossterm::{
+    event::{self, Event, KeyCode},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Gauge, List, ListItem, Paragraph, Wrap},
+    Terminal,
+};
+use std::cell::Cell;
+use std::fs;
+use std::io::{self, stdout};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::buckets::format_bytes;
+use super::feedback::Feedback;
+use super::pipeline::{self, ExecuteResult, Plan};
+use super::strategy::{self, Assignments, Organizer, Target};
+use super::template;
+use super::transfer::{Output, TransferMode, TransferProgress};
+
+/// Folder under the output root for files the organizer leaves alone, when asked for
+const LEFTOVERS_FOLDER: &str = "tyr-skip";
+
+/// Files and bytes going to one target, for the review screen
+struct TargetSummary {
+    target: Target,
+    files: Vec<String>,
+    bytes: u64,
+}
+
+enum AppState {
+    Ready,
+    Review,
+    Organizing,
+    Complete(ExecuteResult),
+}
+
+/// Analyze, review and organize with any `Organizer`
+pub struct OrganizerTuiApp {
+    organizer: Box<dyn Organizer>,
+    base_path: PathBuf,
+    skip_patterns: Vec<String>,
+    output: Output,
+    progress: Arc<TransferProgress>,
+    state: AppState,
+    assignments: Assignments,
+    summary: Vec<TargetSummary>,
+    /// Move files the organizer leaves alone into `tyr-skip` ('k')
+    move_leftovers: bool,
+    /// Summary entry 'r' and 'x' act on
+    selected: usize,
+    /// New name for the selected folder while it is being typed
+    rename_input: Option<String>,
+    /// Corrections made on the review screen, for the caller to keep
+    learned: Feedback,
+    /// Show why the selected folder's files belong together ('e', `--explain`)
+    explain: bool,
+    /// The organizer's reasons for the selected folder
+    explanation: Vec<String>,
+    scroll: u16,
+    /// Rows the review screen showed last, so the selection can be kept in view
+    review_rows: Cell<u16>,
+    logs: Vec<String>,
+}
+
+impl OrganizerTuiApp {
+    pub fn new(
+        organizer: Box<dyn Organizer>,
+        base_path: PathBuf,
+        skip_patterns: Vec<String>,
+        mut output: Output,
+    ) -> Self {
+        let progress = Arc::new(TransferProgress::default());
+        output.progress = Some(Arc::clone(&progress));
+
+        Self {
+            organizer,
+            base_path,
+            skip_patterns,
+            output,
+            progress,
+            state: AppState::Ready,
+            assignments: Assignments::new(),
+            summary: Vec::new(),
+            move_leftovers: false,
+            selected: 0,
+            rename_input: None,
+            learned: Feedback::default(),
+            explain: false,
+            explanation: Vec::new(),
+            scroll: 0,
+            review_rows: Cell::new(0),
+            logs: Vec::new(),
+        }
+    }
+
+    /// What organizing did, once it happened
+    pub fn take_result(&mut self) -> Option<ExecuteResult> {
+        match std::mem::replace(&mut self.state, AppState::Ready) {
+            AppState::Complete(result) => Some(result),
+            _ => None,
+        }
+    }
+
+    /// Renames and ungrouped folders from the review screen, when the organizer
+    /// learns from them (see `Organizer::learns`)
+    pub fn take_learned(&mut self) -> Feedback {
+        std::mem::take(&mut self.learned)
+    }
+
+    /// Starts with the explanation panel open, and prints the reasons in `auto_organize`
+    pub fn show_explanations(&mut self, on: bool) {
+        self.explain = on;
+    }
+
+    pub fn run(&mut self) -> io::Result<()> {
+        enable_raw_mode()?;
+        let mut stdout = stdout();
+        execute!(stdout, EnterAlternateScreen)?;
+
+        let backend = CrosstermBackend::new(stdout);
+        let mut terminal = Terminal::new(backend)?;
+
+        let result = self.run_app(&mut terminal);
+
+        disable_raw_mode()?;
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        terminal.show_cursor()?;
+
+        result
+    }
+
+    fn run_app(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    ) -> io::Result<()> {
+        loop {
+            terminal.draw(|f| self.draw_ui(f))?;
+
+            if event::poll(Duration::from_millis(100))? {
+                if let Event::Key(key) = event::read()? {
+                    if let Some(input) = &mut self.rename_input {
+                        match key.code {
+                            KeyCode::Enter => {
+                                let name = input.trim().to_string();
+                                self.rename_input = None;
+                                self.rename_selected(name);
+                            }
+                            KeyCode::Esc => self.rename_input = None,
+                            KeyCode::Backspace => {
+                                input.pop();
+                            }
+                            KeyCode::Char('/' | '\\') => {}
+                            KeyCode::Char(c) => input.push(c),
+                            _ => {}
+                        }
+                        continue;
+                    }
+                    match (key.code, &self.state) {
+                        (KeyCode::Char('q') | KeyCode::Esc, _) => break,
+                        (KeyCode::Char('a') | KeyCode::Char('d'), AppState::Ready) => {
+                            self.analyze()?;
+                        }
+                        (KeyCode::Char('s'), AppState::Ready) => {
+                            self.analyze()?;
+                            self.organize_with_progress(terminal)?;
+                        }
+                        (KeyCode::Char('s'), AppState::Review) => {
+                            self.organize_with_progress(terminal)?;
+                        }
+                        (KeyCode::Char('k'), AppState::Review) => {
+                            self.move_leftovers = !self.move_leftovers;
+                        }
+                        (KeyCode::Char('r'), AppState::Review) => {
+                            if let Some(Target::Folder(folder)) = self.selected_target() {
+                                self.rename_input = Some(folder.clone());
+                            }
+                        }
+                        (KeyCode::Char('x'), AppState::Review) => self.ungroup_selected(),
+                        (KeyCode::Char('e'), AppState::Review) => {
+                            self.explain = !self.explain;
+                            self.select(self.selected);
+                        }
+                        (KeyCode::Up, AppState::Review) => {
+                            self.select(self.selected.saturating_sub(1));
+                        }
+                        (KeyCode::Down, AppState::Review) => self.select(self.selected + 1),
+                        (KeyCode::Up, _) => self.scroll = self.scroll.saturating_sub(1),
+                        (KeyCode::Down, _) => self.scroll = self.scroll.saturating_add(1),
+                        (KeyCode::PageUp, _) => self.scroll = self.scroll.saturating_sub(10),
+                        (KeyCode::PageDown, _) => self.scroll = self.scroll.saturating_add(10),
+                        _ => {}
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Lists and analyzes the directory, then sums up the targets for review
+    fn analyze(&mut self) -> io::Result<()> {
+        let files = strategy::list_files(&self.base_path, &self.skip_patterns)?;
+        self.log(format!("Found {} files", files.len()));
+        self.assignments = self.organizer.analyze(&files)?;
+        self.summarize();
+
+        let folders = self.folders().count();
+        self.log(format!(
+            "{} files into {} folders",
+            self.folders().map(|s| s.files.len()).sum::<usize>(),
+            folders
+        ));
+        self.state = AppState::Review;
+        self.select(0);
+        Ok(())
+    }
+
+    /// Sums up the assignments per target, for review
+    fn summarize(&mut self) {
+        let mut summary: Vec<TargetSummary> = Vec::new();
+        for assignment in &self.assignments {
+            let name = assignment
+                .file
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let bytes = fs::metadata(&assignment.file).map_or(0, |m| m.len());
+            match summary.iter_mut().find(|s| s.target == assignment.target) {
+                Some(entry) => {
+                    entry.files.push(name);
+                    entry.bytes += bytes;
+                }
+                None => summary.push(TargetSummary {
+                    target: assignment.target.clone(),
+                    files: vec![name],
+                    bytes,
+                }),
+            }
+        }
+        // Biggest folders first; the trash and files that stay go last
+        summary.sort_by(|a, b| match (&a.target, &b.target) {
+            (Target::Folder(_), Target::Folder(_)) => b.bytes.cmp(&a.bytes),
+            (a, b) => a.cmp(b),
+        });
+        self.summary = summary;
+    }
+
+    fn selected_target(&self) -> Option<&Target> {
+        self.summary.get(self.selected).map(|s| &s.target)
+    }
+
+    /// Selects a summary entry and scrolls just far enough to show it
+    fn select(&mut self, index: usize) {
+        self.selected = index.min(self.summary.len().saturating_sub(1));
+
+        // Two header lines, then a label, up to two files and "... and N more" per entry
+        let lines = |s: &TargetSummary| 1 + s.files.len().min(2) + usize::from(s.files.len() > 2);
+        let top = 2 + self.summary[..self.selected]
+            .iter()
+            .map(lines)
+            .sum::<usize>();
+        let bottom = top + self.summary.get(self.selected).map_or(1, lines);
+        let rows = usize::from(self.review_rows.get()).max(1);
+
+        if self.selected == 0 {
+            self.scroll = 0;
+        } else if top < usize::from(self.scroll) {
+            self.scroll = top as u16;
+        } else if bottom > usize::from(self.scroll) + rows {
+            self.scroll = bottom.saturating_sub(rows) as u16;
+        }
+
+        self.explanation = match self.selected_target() {
+            Some(target) if self.explain => self.explain_target(&target.clone()),
+            _ => Vec::new(),
+        };
+    }
+
+    /// Why the organizer sent its files to `target`; nothing for files that stay
+    fn explain_target(&self, target: &Target) -> Vec<String> {
+        if *target == Target::Keep {
+            return Vec::new();
+        }
+        let files: Vec<PathBuf> = self
+            .assignments
+            .iter()
+            .filter(|a| a.target == *target)
+            .map(|a| a.file.clone())
+            .collect();
+        self.organizer.explain(&files)
+    }
+
+    /// Moves the selected folder's files to `name`, or into the folder of that name
+    fn rename_selected(&mut self, name: String) {
+        let Some(Target::Folder(old)) = self.selected_target().cloned() else {
+            return;
+        };
+        if name.is_empty() || name == old {
+            return;
+        }
+        if !template::stays_inside(Path::new(&name)) {
+            self.log(format!("✗ '{}' is not a folder name", name));
+            return;
+        }
+
+        let new = Target::Folder(name.clone());
+        let files = self.summary[self.selected].files.clone();
+        let mates: Vec<String> = self
+            .summary
+            .iter()
+            .find(|s| s.target == new)
+            .map(|s| s.files.clone())
+            .unwrap_or_default();
+        for assignment in &mut self.assignments {
+            if assignment.target == Target::Folder(old.clone()) {
+                assignment.target = new.clone();
+            }
+        }
+
+        if self.organizer.learns() {
+            if mates.is_empty() {
+                self.learned.name_folder(&files, &name);
+            } else {
+                self.learned.link_all(&files, &mates);
+            }
+        }
+        let verb = if mates.is_empty() {
+            "renamed to"
+        } else {
+            "merged into"
+        };
+        self.log(format!("✓ {} {} {}", old, verb, name));
+
+        self.summarize();
+        let index = self.summary.iter().position(|s| s.target == new);
+        self.select(index.unwrap_or(0));
+    }
+
+    /// Leaves the selected folder's files where they are
+    fn ungroup_selected(&mut self) {
+        let Some(Target::Folder(folder)) = self.selected_target().cloned() else {
+            return;
+        };
+        let files = self.summary[self.selected].files.clone();
+        for assignment in &mut self.assignments {
+            if assignment.target == Target::Folder(folder.clone()) {
+                assignment.target = Target::Keep;
+            }
+        }
+
+        if self.organizer.learns() {
+            self.learned.separate_all(&files, &files);
+        }
+        self.log(format!(
+            "✓ Ungrouped {}: {} files stay",
+            folder,
+            files.len()
+        ));
+
+        self.summarize();
+        self.select(self.selected);
+    }
+
+    fn folders(&self) -> impl Iterator<Item = &TargetSummary> {
+        self.summary
+            .iter()
+            .filter(|s| matches!(s.target, Target::Folder(_)))
+    }
+
+    fn kept(&self) -> usize {
+        self.summary
+            .iter()
+            .filter(|s| s.target == Target::Keep)
+            .map(|s| s.files.len())
+            .sum()
+    }
+
+    fn plan(&self) -> io::Result<Plan> {
+        strategy::plan(
+            &self.base_path,
+            &self.output,
+            self.organizer.as_ref(),
+            &self.assignments,
+            self.move_leftovers.then_some(LEFTOVERS_FOLDER),
+        )
+    }
+
+    /// Executes the plan on a worker thread while the gauge follows the transfer progress
+    fn organize_with_progress(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    ) -> io::Result<()> {
+        let plan = self.plan()?;
+        self.state = AppState::Organizing;
+        self.progress.start(plan.moves.len() + plan.trash.len());
+
+        let this = &*self;
+        let result = std::thread::scope(|scope| {
+            let worker = scope.spawn(|| pipeline::execute_moves(&plan));
+            while !worker.is_finished() {
+                terminal.draw(|f| this.draw_ui(f))?;
+                std::thread::sleep(Duration::from_millis(100));
+            }
+            worker
+                .join()
+                .map_err(|_| io::Error::other("organizer thread panicked"))
+        })?;
+
+        self.finish(result);
+        Ok(())
+    }
+
+    fn finish(&mut self, result: ExecuteResult) {
+        for entry in result.journal.moves.iter().rev().take(3).rev() {
+            let name = entry.from.file_name().unwrap_or_default().to_string_lossy();
+            self.log(format!("✓ {} → {}", name, entry.to.display()));
+        }
+        for failure in &result.failures {
+            self.log(format!("✗ {}", failure));
+        }
+        self.state = AppState::Complete(result);
+        self.scroll = 0;
+    }
+
+    fn log(&mut self, message: String) {
+        self.logs.push(message);
+    }
+
+    /// Label for a target on the review screen
+    fn target_label(&self, target: &Target) -> String {
+        match target {
+            Target::Folder(folder) => folder.clone(),
+            Target::Trash if self.output.mode == TransferMode::Copy => {
+                "Trash (left alone while copying)".to_string()
+            }
+            Target::Trash => "Trash".to_string(),
+            Target::Keep if self.move_leftovers => format!("{} (no folder)", LEFTOVERS_FOLDER),
+            Target::Keep => "Stay in place (no folder)".to_string(),
+        }
+    }
+
+    fn draw_ui(&self, f: &mut ratatui::Frame) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(10),
+                Constraint::Length(5),
+                Constraint::Length(3),
+            ])
+            .split(f.size());
+
+        let title = Paragraph::new(format!(" Tyr - {}", self.organizer.name()))
+            .style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .block(Block::default().borders(Borders::ALL));
+        f.render_widget(title, chunks[0]);
+
+        match &self.state {
+            AppState::Ready => self.draw_ready_state(f, chunks[1]),
+            AppState::Review => self.draw_review_state(f, chunks[1]),
+            AppState::Organizing => self.draw_organizing_state(f, chunks[1]),
+            AppState::Complete(result) => self.draw_complete_state(f, chunks[1], result),
+        }
+
+        self.draw_logs(f, chunks[2]);
+        self.draw_controls(f, chunks[3]);
+    }
+
+    fn draw_ready_state(&self, f: &mut ratatui::Frame, area: Rect) {
+        let schema = self.organizer.config_schema();
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                " Ready to Organize Files",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            self.path_line("Directory: ", &self.base_path.display().to_string()),
+            self.path_line(
+                "Destination: ",
+                &self.output.root(&self.base_path).display().to_string(),
+            ),
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("Settings [{}]:", schema.section),
+                Style::default().fg(Color::Cyan),
+            )),
+        ];
+        for setting in &schema.settings {
+            lines.push(Line::from(vec![
+                Span::raw(format!("  {} = ", setting.key)),
+                Span::styled(setting.value.clone(), Style::default().fg(Color::Yellow)),
+            ]));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            " Press 'a' to analyze and review, 's' to organize straight away",
+            Style::default().fg(Color::Green),
+        )));
+
+        let widget = Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title(" Status "))
+            .scroll((self.scroll, 0));
+        f.render_widget(widget, area);
+    }
+
+    fn path_line(&self, label: &'static str, path: &str) -> Line<'static> {
+        Line::from(vec![
+            Span::raw(label),
+            Span::styled(path.to_string(), Style::default().fg(Color::Yellow)),
+        ])
+    }
+
+    /// Every target with its share of the bytes and a few of its files
+    fn draw_review_state(&self, f: &mut ratatui::Frame, area: Rect) {
+        let area = if self.explain {
+            let halves = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .split(area);
+            self.draw_explanation(f, halves[1]);
+            halves[0]
+        } else {
+            area
+        };
+
+        let total_bytes: u64 = self.summary.iter().map(|s| s.bytes).sum();
+        let bar_width = area.width.saturating_sub(60).clamp(10, 30) as f64;
+
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!(
+                    "✓ Analysis Complete - {} files, {} folders",
+                    self.assignments.len(),
+                    self.folders().count()
+                ),
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+        ];
+
+        for (index, entry) in self.summary.iter().enumerate() {
+            let share = if total_bytes == 0 {
+                0.0
+            } else {
+                entry.bytes as f64 / total_bytes as f64
+            };
+            let color = match entry.target {
+                Target::Folder(_) => Color::Cyan,
+                Target::Trash => Color::Red,
+                Target::Keep => Color::Gray,
+            };
+            let mut label = Style::default().fg(color).add_modifier(Modifier::BOLD);
+            if index == self.selected {
+                label = label.add_modifier(Modifier::REVERSED);
+            }
+            lines.push(Line::from(vec![
+                Span::styled(format!(" {:28}", self.target_label(&entry.target)), label),
+                Span::styled(
+                    format!(
+                        "{:width$}",
+                        "█".repeat((share * bar_width).round() as usize),
+                        width = bar_width as usize
+                    ),
+                    Style::default().fg(color),
+                ),
+                Span::raw(format!(
+                    " {:>9}  {:>3.0}%  {} files",
+                    format_bytes(entry.bytes),
+                    share * 100.0,
+                    entry.files.len()
+                )),
+            ]));
+            for (i, name) in entry.files.iter().take(2).enumerate() {
+                let branch = if i + 1 == entry.files.len().min(2) {
+                    "└─"
+                } else {
+                    "├─"
+                };
+                lines.push(Line::from(Span::styled(
+                    format!("   {} {}", branch, name),
+                    Style::default().fg(Color::Gray),
+                )));
+            }
+            if entry.files.len() > 2 {
+                lines.push(Line::from(Span::styled(
+                    format!("   ... and {} more", entry.files.len() - 2),
+                    Style::default().fg(Color::Gray),
+                )));
+            }
+        }
+
+        self.review_rows.set(area.height.saturating_sub(2));
+        let widget = Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title(" Review "))
+            .scroll((self.scroll, 0));
+        f.render_widget(widget, area);
+    }
+
+    /// The organizer's reasons for the selected folder, next to the review
+    fn draw_explanation(&self, f: &mut ratatui::Frame, area: Rect) {
+        let lines: Vec<Line> = if self.explanation.is_empty() {
+            vec![Line::from(Span::styled(
+                "Nothing to explain for this entry",
+                Style::default().fg(Color::Gray),
+            ))]
+        } else {
+            self.explanation
+                .iter()
+                .map(|line| Line::from(line.as_str()))
+                .collect()
+        };
+        let title = match self.selected_target() {
+            Some(target) => format!(" Why {} ", self.target_label(target)),
+            None => " Why ".to_string(),
+        };
+        let widget = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::ALL).title(title));
+        f.render_widget(widget, area);
+    }
+
+    fn draw_organizing_state(&self, f: &mut ratatui::Frame, area: Rect) {
+        let files = format!(
+            "{}/{} files",
+            self.progress.files_done.load(Ordering::Relaxed),
+            self.progress.files_total.load(Ordering::Relaxed)
+        );
+        let label = if self.progress.is_copying() {
+            const MB: f64 = 1024.0 * 1024.0;
+            format!(
+                " Copying {:.1} of {:.1} MB ({})",
+                self.progress.bytes_done.load(Ordering::Relaxed) as f64 / MB,
+                self.progress.bytes_total.load(Ordering::Relaxed) as f64 / MB,
+                files
+            )
+        } else {
+            format!(" Organizing... ({})", files)
+        };
+
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Organizing Files "),
+            )
+            .gauge_style(Style::default().fg(Color::Cyan))
+            .label(label)
+            .percent(self.progress.percent());
+        f.render_widget(gauge, area);
+    }
+
+    fn draw_complete_state(&self, f: &mut ratatui::Frame, area: Rect, result: &ExecuteResult) {
+        let count = |label: String, value: usize, color: Color| {
+            Line::from(vec![
+                Span::raw(label),
+                Span::styled(
+                    value.to_string(),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ),
+            ])
+        };
+        let title = if result.aborted {
+            Span::styled(
+                "✗ Aborted by the pre_run hook, nothing was moved",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )
+        } else {
+            Span::styled(
+                "✦ Organization Complete!",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            )
+        };
+        let mut lines = vec![
+            Line::from(title),
+            Line::from(""),
+            count(
+                format!(" Files {}: ", self.output.verb().to_lowercase()),
+                result.files_moved,
+                Color::Green,
+            ),
+        ];
+        if result.files_trashed > 0 {
+            lines.push(count(
+                " Files trashed: ".to_string(),
+                result.files_trashed,
+                Color::Yellow,
+            ));
+        }
+        lines.push(count(
+            " Files failed: ".to_string(),
+            result.failures.len(),
+            Color::Red,
+        ));
+
+        if !result.failures.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "! Failures:",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )));
+            for failure in &result.failures {
+                lines.push(Line::from(format!("  • {}", failure)));
+            }
+        }
+        if result.files_moved > 0 {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                " Run 'tyr undo' to revert this run",
+                Style::default().fg(Color::Gray),
+            )));
+        }
+
+        let widget = Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title(" Results "))
+            .scroll((self.scroll, 0));
+        f.render_widget(widget, area);
+    }
+
+    fn draw_logs(&self, f: &mut ratatui::Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .logs
+            .iter()
+            .rev()
+            .take(3)
+            .map(|log| {
+                let color = match log.chars().next() {
+                    Some('✓') => Color::Green,
+                    Some('✗') => Color::Red,
+                    _ => Color::Gray,
+                };
+                ListItem::new(log.as_str()).style(Style::default().fg(color))
+            })
+            .collect();
+
+        let list = List::new(items).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Recent Activity "),
+        );
+        f.render_widget(list, area);
+    }
+
+    fn draw_controls(&self, f: &mut ratatui::Frame, area: Rect) {
+        let controls = match &self.state {
+            _ if self.rename_input.is_some() => format!(
+                " New folder name: {}▏ | Enter Apply | Esc Cancel",
+                self.rename_input.as_deref().unwrap_or_default()
+            ),
+            AppState::Ready => " 'a' Analyze | 's' Start | 'q' Quit".to_string(),
+            AppState::Review => format!(
+                " 's' Start | ↑↓ Select | 'r' Rename | 'x' Ungroup | 'e' Explain{} | 'q' Quit",
+                if self.kept() > 0 {
+                    " | 'k' Toggle tyr-skip Folder"
+                } else {
+                    ""
+                }
+            ),
+            AppState::Organizing => " Organizing... Please wait".to_string(),
+            AppState::Complete(_) => " ↑↓ Scroll | 'q' Quit".to_string(),
+        };
+
+        let widget = Paragraph::new(controls)
+            .style(Style::default().fg(Color::Yellow))
+            .block(Block::default().borders(Borders::ALL).title(" Controls "));
+        f.render_widget(widget, area);
+    }
+
+    /// Analyzes, prints the folders and organizes without UI interaction
+    pub fn auto_organize(&mut self) -> io::Result<()> {
+        self.analyze()?;
+
+        println!(
+            "\n✓ Analysis Complete: {} files, {} folders",
+            self.assignments.len(),
+            self.folders().count()
+        );
+        for entry in &self.summary {
+            println!(
+                "   • {:28} {:>9}  {} files",
+                self.target_label(&entry.target),
+                format_bytes(entry.bytes),
+                entry.files.len()
+            );
+            if self.explain {
+                for line in self.explain_target(&entry.target) {
+                    println!("       {}", line);
+                }
+            }
+        }
+
+        let plan = self.plan()?;
+        let result = pipeline::execute_moves(&plan);
+        if result.aborted {
+            self.finish(result);
+            return Ok(());
+        }
+
+        println!("\n✦ Organization Complete!\n");
+        println!("Summary:");
+        println!(
+            "   • Files {}:  {}",
+            self.output.verb().to_lowercase(),
+            result.files_moved
+        );
+        if result.files_trashed > 0 {
+            println!("   • Files trashed: {}", result.files_trashed);
+        }
+        println!("   • Files failed: {}", result.failures.len());
+
+        self.finish(result);
+        Ok(())
+    }
+}